@@ -1,17 +1,37 @@
 use anyhow::{Context, Result};
+use chrono::{DateTime, Utc};
 use colored::*;
+use std::collections::hash_map::Entry;
+use std::collections::{HashMap, HashSet};
 use std::env;
+use std::path::{Path, PathBuf};
 use std::sync::Arc;
 
 use crate::app_deps::{
-    GitHubApiFactory, PromptInterface, RateLimiterInterface, RealGitHubApiFactory, RealPrompt,
-    RealRateLimiter,
+    GitHubApi, GitHubApiFactory, PromptInterface, RateLimiterInterface, RealGitHubApiFactory,
+    RealPrompt, RealRateLimiter,
 };
+use crate::bundle;
 use crate::config;
+use crate::config_edit;
+use crate::constants;
+use crate::doctor;
 use crate::error;
+use crate::generate;
+use crate::import;
+use crate::last_selection;
+use crate::lint;
+use crate::overwrite::{OverwriteDecisions, OverwritePolicy};
 use crate::paths;
+use crate::policy;
+use crate::progress::{CellStatus, Progress};
 use crate::prompt;
+use crate::rotation;
+use crate::schema;
+use crate::secret_ref;
+use crate::selector;
 use crate::validation;
+use crate::vault;
 
 #[derive(Debug, Clone, PartialEq)]
 pub struct UpdateResult {
@@ -49,6 +69,169 @@ impl UpdateResult {
     }
 }
 
+/// Options for a non-interactive or partially interactive run, usually set from the command line.
+#[derive(Debug, Clone, Default)]
+pub struct RunOptions {
+    /// Target repositories in any of these groups instead of showing the selection menu.
+    pub groups: Vec<String>,
+    /// Target repositories carrying any of these tags instead of showing the selection menu.
+    pub tags: Vec<String>,
+    /// Target repositories matching these `--repo` patterns (see [`selector`]).
+    pub repos: Vec<String>,
+    /// Target the repositories picked in the selection menu last time (see [`last_selection`]).
+    pub last: bool,
+    /// Read secrets from this encrypted bundle instead of prompting (see [`bundle`]).
+    pub bundle: Option<PathBuf>,
+    /// age identity file for `bundle`; defaults to [`bundle::default_identity_path`].
+    pub identity: Option<PathBuf>,
+    /// Generate random values for these secrets instead of prompting (see [`generate`]).
+    pub generate: Vec<generate::GenerateSpec>,
+    /// Append generated values to this file instead of printing them.
+    pub generate_output: Option<PathBuf>,
+    /// Refuse to upload values with lint warnings, not just errors (see [`lint`]).
+    pub strict: bool,
+    /// Decide about existing secrets without asking, over each repository's
+    /// `overwrite` setting (see [`crate::overwrite`]).
+    pub overwrite: Option<OverwritePolicy>,
+}
+
+impl RunOptions {
+    /// Whether the command line already determines which repositories to target.
+    pub fn has_repository_filter(&self) -> bool {
+        !self.groups.is_empty() || !self.tags.is_empty() || !self.repos.is_empty() || self.last
+    }
+
+    /// Resolve the command-line selection into repository indices, in config order.
+    fn select(&self, repositories: &[config::Repository]) -> Result<Vec<usize>> {
+        if !self.last {
+            return selector::select(repositories, &self.groups, &self.tags, &self.repos);
+        }
+
+        let saved = last_selection::load(&paths::get_last_selection_path())?;
+        if saved.repositories.is_empty() {
+            anyhow::bail!(
+                "No saved selection yet; pick repositories in the selection menu once before using --last"
+            );
+        }
+        let missing = saved.missing(repositories);
+        if !missing.is_empty() {
+            println!(
+                "{} {}",
+                "⚠ No longer configured, skipped:".yellow(),
+                missing.join(", ")
+            );
+        }
+        let indices = saved.indices(repositories);
+        if indices.is_empty() {
+            anyhow::bail!("None of the repositories selected last time are configured anymore");
+        }
+        Ok(indices)
+    }
+}
+
+/// Where `rotate` gets the new value from.
+#[derive(Debug, Clone, Default)]
+pub enum NewValue {
+    /// A literal value or reference (`env:`, `file:`, `cmd:`, `provider:`).
+    Given(String),
+    /// A randomly generated value, shown once.
+    Generate(generate::GenerateSpec),
+    /// Ask for the value on the terminal without echoing it.
+    #[default]
+    Prompt,
+}
+
+/// Options for `rotate`.
+#[derive(Debug, Clone, Default)]
+pub struct RotateOptions {
+    /// Name of the secret to rotate.
+    pub secret: String,
+    /// Where the new value comes from.
+    pub value: NewValue,
+    /// Also create the secret in selected repositories that don't have it.
+    pub create: bool,
+    /// Skip the confirmation prompt.
+    pub yes: bool,
+    /// Limit the repositories considered (`--repo`, `--group`, `--tag`), and
+    /// refuse values with lint warnings when `strict`.
+    pub selection: RunOptions,
+}
+
+/// Options for `age`.
+#[derive(Debug, Clone, Default)]
+pub struct AgeOptions {
+    /// Policies from `--policy`, checked before those in config.
+    pub policies: Vec<policy::RotationPolicy>,
+    /// Print machine-readable JSON instead of a table.
+    pub json: bool,
+    /// Limit the repositories considered (`--repo`, `--group`, `--tag`).
+    pub selection: RunOptions,
+}
+
+/// One secret in an `age` report.
+#[derive(Debug, Clone, PartialEq)]
+pub struct SecretAge {
+    /// Repository as `owner/name`.
+    pub repository: String,
+    /// Secret name.
+    pub secret: String,
+    /// When the secret was last updated, if GitHub reported it.
+    pub updated_at: Option<DateTime<Utc>>,
+    /// The matching policy as `pattern` and maximum age text.
+    pub policy: Option<(String, String)>,
+    /// Whether the secret is older than its policy allows.
+    pub stale: bool,
+}
+
+/// Result of `age`: secrets oldest first, and repositories that couldn't be listed.
+#[derive(Debug, Clone, Default)]
+pub struct AgeReport {
+    pub secrets: Vec<SecretAge>,
+    /// `owner/name: error` for each repository whose secrets couldn't be listed.
+    pub unchecked: Vec<String>,
+}
+
+impl AgeReport {
+    /// Fail when any secret is stale or any repository couldn't be checked.
+    pub fn check(&self) -> Result<()> {
+        let stale = self.secrets.iter().filter(|s| s.stale).count();
+        if stale > 0 {
+            anyhow::bail!("{} secret(s) are older than their rotation policy", stale);
+        }
+        if !self.unchecked.is_empty() {
+            anyhow::bail!(
+                "Could not list secrets in {} repository(ies): {}",
+                self.unchecked.len(),
+                self.unchecked.join("; ")
+            );
+        }
+        Ok(())
+    }
+}
+
+/// A change to the local secret vault, from `vault rename/remove`.
+#[derive(Debug, Clone)]
+pub enum VaultAction {
+    /// Rename a secret set.
+    Rename { from: String, to: String },
+    /// Remove a secret set.
+    Remove(String),
+}
+
+/// A non-interactive change to the config file, from `config add/remove/set-alias`.
+#[derive(Debug, Clone)]
+pub enum ConfigAction {
+    /// Append a repository.
+    Add(config::Repository),
+    /// Remove the repository with this `owner/name` path.
+    Remove(String),
+    /// Set or clear the alias of the repository with this `owner/name` path.
+    SetAlias {
+        repository: String,
+        alias: Option<String>,
+    },
+}
+
 pub struct App;
 
 impl App {
@@ -74,7 +257,8 @@ impl App {
         repo_results
     }
 
-    pub async fn run() -> Result<()> {
+    /// Load `GITHUB_TOKEN` (after loading `.env` files) and validate its format.
+    fn load_token() -> Result<Arc<String>> {
         // Load .env file from XDG config directory or current directory
         paths::load_env_file();
 
@@ -84,364 +268,1315 @@ impl App {
         // Validate token format
         validation::validate_token(&token_str).context("Invalid GitHub token format")?;
 
-        let token = Arc::new(token_str);
+        Ok(Arc::new(token_str))
+    }
 
-        // Find config file in XDG config directory or current directory
-        let config_path = paths::find_config_file()?;
-        let config = config::Config::from_file(
-            config_path
-                .to_str()
-                .context("Config path is not valid UTF-8")?,
-        )
-        .with_context(|| format!("Failed to load config from {}", config_path.display()))?;
+    /// Path of the config file to edit: the existing one, or where a new one should be created.
+    fn config_edit_path() -> PathBuf {
+        match paths::find_config_file() {
+            Ok(path) => path,
+            Err(_) => paths::get_config_creation_path(),
+        }
+    }
+
+    /// Write the config to disk, preserving comments and formatting of the existing file.
+    fn save_config(config_path: &Path, config: &config::Config) -> Result<()> {
+        println!("Saving configuration to {}...", config_path.display());
+        config_edit::save(config_path, config)?;
+        println!("{}", "Configuration saved successfully.".green());
+        Ok(())
+    }
+
+    /// Load the merged config used by commands that talk to GitHub.
+    fn load_run_config() -> Result<config::Config> {
+        // Merge config files from CONFIG_PATH, the current directory and XDG locations
+        let layers = paths::find_config_layers();
+        if layers.is_empty() {
+            anyhow::bail!(
+                "No config file found. Run `github-secrets config` to create one at {}",
+                paths::get_config_creation_path().display()
+            );
+        }
+        config::Config::load_layered(&layers)
+    }
+
+    pub async fn run(options: RunOptions) -> Result<()> {
+        let token = Self::load_token()?;
+        let config = Self::load_run_config()?;
 
         // Initialize real adapters and delegate to injectable runner
         let factory = RealGitHubApiFactory;
         let prompt_impl = RealPrompt;
         let mut rate_limiter = RealRateLimiter::new();
 
-        Self::run_with_deps(&factory, &prompt_impl, &mut rate_limiter, token, config).await
+        Self::run_with_options(
+            &factory,
+            &prompt_impl,
+            &mut rate_limiter,
+            token,
+            config,
+            &options,
+        )
+        .await
     }
 
-    pub async fn config() -> Result<()> {
-        let _factory = RealGitHubApiFactory;
-        let prompt_impl = RealPrompt;
-        let _rate_limiter = RealRateLimiter::new();
-        // We don't need factory or rate limiter for config really, but we need prompt.
+    /// Rotate a secret in every configured repository that has it (`rotate`).
+    pub async fn rotate(options: RotateOptions) -> Result<()> {
+        let token = Self::load_token()?;
+        let config = Self::load_run_config()?;
 
-        Self::config_with_deps(&prompt_impl).await
+        Self::rotate_with_deps(
+            &RealGitHubApiFactory,
+            &RealPrompt,
+            &mut RealRateLimiter::new(),
+            token,
+            config,
+            &options,
+            &paths::get_rotation_log_path(),
+        )
+        .await
     }
 
-    pub async fn config_with_deps<P>(prompt_impl: &P) -> Result<()>
+    /// Same as `rotate`, with injected dependencies and rotation log path.
+    ///
+    /// Repositories are checked with a secrets listing. Those that have the
+    /// secret are updated after a single confirmation; those that don't are
+    /// listed, and only get the secret with `--create`.
+    pub async fn rotate_with_deps<F, P, RL>(
+        factory: &F,
+        prompt_impl: &P,
+        rate_limiter: &mut RL,
+        token: Arc<String>,
+        config: config::Config,
+        options: &RotateOptions,
+        log_path: &Path,
+    ) -> Result<()>
     where
-        P: PromptInterface,
+        F: GitHubApiFactory + Sync,
+        P: PromptInterface + Sync,
+        RL: RateLimiterInterface + Send,
     {
-        // 1. Check if config exists or use creation path
-        let config_path = match paths::find_config_file() {
-            Ok(path) => path,
-            Err(_) => paths::get_config_creation_path(),
+        let secret = options.secret.as_str();
+        validation::validate_secret_key(secret)?;
+
+        let repositories = config.get_repositories();
+        let indices: Vec<usize> = if options.selection.has_repository_filter() {
+            options.selection.select(repositories)?
+        } else {
+            (0..repositories.len()).collect()
         };
 
-        // 2. Load existing config or default to empty
-        let initial_config = if config_path.exists() {
-            match config::Config::from_file(config_path.to_str().unwrap()) {
-                Ok(cfg) => cfg,
-                Err(e) => {
-                    println!("Warning: Failed to parse existing config: {}", e);
-                    // Ask user if they want to overwrite? Or just show empty?
-                    // For now, let's treat as empty/new to allow recovery via UI
-                    config::Config {
-                        repositories: vec![],
-                        repository: None,
+        let records = rotation::load(log_path)?;
+        if let Some(last) = rotation::last_rotation(&records, secret) {
+            println!(
+                "{} {} ({})",
+                "Last rotated:".cyan(),
+                prompt::format_date(&last.rotated_at),
+                last.rotated_at.bright_black()
+            );
+        }
+
+        println!(
+            "{} {} {} {} {}...\n",
+            "Looking for".cyan(),
+            format!("'{}'", secret).bright_cyan(),
+            "in".cyan(),
+            indices.len().to_string().bright_cyan(),
+            "repository/repositories".cyan()
+        );
+
+        let mut targets: Vec<(usize, Box<dyn GitHubApi>)> = Vec::new();
+        let mut missing = Vec::new();
+        let mut unchecked = Vec::new();
+        for &index in &indices {
+            let repo = &repositories[index];
+            let client = factory.create(
+                token.as_ref().clone(),
+                repo.owner.clone(),
+                repo.name.clone(),
+            )?;
+
+            rate_limiter.wait_if_needed().await;
+            let listing = client.list_secrets().await;
+            rate_limiter.release();
+
+            match listing {
+                Ok(secrets) => match secrets.iter().find(|s| s.name.eq_ignore_ascii_case(secret)) {
+                    Some(found) => {
+                        let updated = found
+                            .updated_at
+                            .as_deref()
+                            .map(|d| format!("updated {}", prompt::format_date(d)))
+                            .unwrap_or_default();
+                        println!(
+                            "  {} {} {}",
+                            "✓".green(),
+                            repo.display_name(),
+                            updated.bright_black()
+                        );
+                        targets.push((index, client));
                     }
+                    None if options.create => {
+                        println!(
+                            "  {} {} {}",
+                            "+".cyan(),
+                            repo.display_name(),
+                            "(not present; will be created)".cyan()
+                        );
+                        targets.push((index, client));
+                    }
+                    None => {
+                        println!(
+                            "  {} {} {}",
+                            "-".bright_black(),
+                            repo.display_name(),
+                            "(not present; use --create to add it)".bright_black()
+                        );
+                        missing.push(index);
+                    }
+                },
+                Err(e) => {
+                    println!(
+                        "  {} {} {}",
+                        "!".red(),
+                        repo.display_name(),
+                        format!(
+                            "(could not list secrets: {})",
+                            error::format_error_chain(&e)
+                        )
+                        .red()
+                    );
+                    unchecked.push(repo.path());
                 }
             }
-        } else {
-            config::Config {
-                repositories: vec![],
-                repository: None,
+        }
+        println!();
+
+        if targets.is_empty() {
+            anyhow::bail!(
+                "Secret '{}' was not found in any selected repository{}",
+                secret,
+                if missing.is_empty() {
+                    ""
+                } else {
+                    "; use --create to add it"
+                }
+            );
+        }
+
+        let question = format!("Rotate '{}' in {} repositories?", secret, targets.len());
+        if !options.yes && !prompt_impl.confirm_action(&question)? {
+            println!("{}", "Rotation cancelled.".yellow());
+            return Ok(());
+        }
+
+        let raw_value = match &options.value {
+            NewValue::Given(value) => value.clone(),
+            NewValue::Generate(spec) => {
+                let generated = generate::generate_all(std::slice::from_ref(spec));
+                Self::reveal_generated(&generated, None)?;
+                generated[0].value.clone()
             }
+            NewValue::Prompt => rpassword::prompt_password(format!("New value for {}: ", secret))
+                .context("Failed to read new value")?,
         };
+        if raw_value.is_empty() {
+            anyhow::bail!("The new value cannot be empty");
+        }
+        let value = secret_ref::resolve_value(&raw_value, &config.providers)?;
+        Self::check_lint(secret, &value, options.selection.strict)?;
 
-        // 3. Launch TUI Dashboard
-        if let Some(new_config) = prompt_impl.manage_config(initial_config)? {
-            // 4. Save if changed/requested
-            if let Some(parent) = config_path.parent() {
-                std::fs::create_dir_all(parent)?;
+        let mut rotated = Vec::new();
+        let mut failed = unchecked;
+        for (index, client) in &targets {
+            let repo = &repositories[*index];
+            rate_limiter.wait_if_needed().await;
+            let result = client.update_secret(secret, &value).await;
+            rate_limiter.release();
+
+            match result {
+                Ok(()) => {
+                    println!("  {} {}", "✓".green(), repo.display_name());
+                    rotated.push(repo.path());
+                }
+                Err(e) => {
+                    println!(
+                        "  {} {}: {}",
+                        "✗".red(),
+                        repo.display_name(),
+                        error::format_error_chain(&e).red()
+                    );
+                    failed.push(repo.path());
+                }
             }
+        }
 
-            println!("Saving configuration to {}...", config_path.display());
-            let toml_string = toml::to_string(&new_config)?;
-            std::fs::write(&config_path, toml_string)?;
-            println!("{}", "Configuration saved successfully.".green());
-        } else {
-            println!("Configuration unchanged.");
+        if !rotated.is_empty() {
+            let record = rotation::RotationRecord::now(secret, rotated.clone(), failed.clone());
+            println!(
+                "\n{} {} {}",
+                "Rotated in".green(),
+                rotated.len().to_string().bright_green(),
+                format!("repositories at {}", record.rotated_at).green()
+            );
+            rotation::append(log_path, record)?;
         }
 
+        if !failed.is_empty() {
+            anyhow::bail!(
+                "Failed to rotate '{}' in {}: {}",
+                secret,
+                failed.len(),
+                failed.join(", ")
+            );
+        }
         Ok(())
     }
 
-    /// Same logic as `run` but with injectable dependencies to enable testing.
-    pub async fn run_with_deps<F, P, RL>(
+    /// Report the age of every secret in the selected repositories.
+    ///
+    /// Exits with an error when a secret is older than its rotation policy, so
+    /// the command can gate CI.
+    pub async fn age(options: AgeOptions) -> Result<()> {
+        let token = Self::load_token()?;
+        let config = Self::load_run_config()?;
+
+        let report = Self::age_with_deps(
+            &RealGitHubApiFactory,
+            &mut RealRateLimiter::new(),
+            token,
+            config,
+            &options,
+        )
+        .await?;
+        if options.json {
+            println!("{}", Self::age_json(&report)?);
+        } else {
+            Self::print_age_report(&report);
+        }
+        report.check()
+    }
+
+    /// Same as `age`, with injected dependencies; returns the report instead of printing it.
+    ///
+    /// Secrets are sorted oldest first; those without an update time come last.
+    pub async fn age_with_deps<F, RL>(
         factory: &F,
-        prompt_impl: &P,
         rate_limiter: &mut RL,
         token: Arc<String>,
         config: config::Config,
-    ) -> Result<()>
+        options: &AgeOptions,
+    ) -> Result<AgeReport>
     where
         F: GitHubApiFactory + Sync,
-        P: PromptInterface + Sync,
         RL: RateLimiterInterface + Send,
     {
+        let policies = policy::compile_all(
+            &options
+                .policies
+                .iter()
+                .chain(&config.rotation_policy)
+                .cloned()
+                .collect::<Vec<_>>(),
+        )?;
+
         let repositories = config.get_repositories();
-        let selected_indices = prompt_impl
-            .select_repositories(repositories)
-            .context("Failed to select repositories")?;
+        let indices: Vec<usize> = if options.selection.has_repository_filter() {
+            options.selection.select(repositories)?
+        } else {
+            (0..repositories.len()).collect()
+        };
+
+        let now = Utc::now();
+        let mut report = AgeReport::default();
+        for index in indices {
+            let repo = &repositories[index];
+            let client = factory.create(
+                token.as_ref().clone(),
+                repo.owner.clone(),
+                repo.name.clone(),
+            )?;
 
-        let secrets = prompt_impl
-            .prompt_secrets()
-            .context("Failed to read secrets from user")?;
+            rate_limiter.wait_if_needed().await;
+            let listing = client.list_secrets().await;
+            rate_limiter.release();
 
-        if secrets.is_empty() {
-            println!("{}", "No secrets to update.".yellow());
-            return Ok(());
+            let secrets = match listing {
+                Ok(secrets) => secrets,
+                Err(e) => {
+                    report.unchecked.push(format!(
+                        "{}: {}",
+                        repo.path(),
+                        error::format_error_chain(&e)
+                    ));
+                    continue;
+                }
+            };
+            for secret in secrets {
+                let updated_at = secret
+                    .updated_at
+                    .as_deref()
+                    .and_then(|d| DateTime::parse_from_rfc3339(d).ok())
+                    .map(|d| d.with_timezone(&Utc));
+                let matched = policy::find(&policies, &secret.name);
+                let stale = match (matched, updated_at) {
+                    (Some(p), Some(updated)) => now.signed_duration_since(updated) > p.max_age,
+                    _ => false,
+                };
+                report.secrets.push(SecretAge {
+                    repository: repo.path(),
+                    secret: secret.name,
+                    updated_at,
+                    policy: matched.map(|p| (p.pattern.clone(), p.max_age_text.clone())),
+                    stale,
+                });
+            }
+        }
+
+        report
+            .secrets
+            .sort_by_key(|s| (s.updated_at.is_none(), s.updated_at));
+        Ok(report)
+    }
+
+    /// Print an `age` report as a table.
+    fn print_age_report(report: &AgeReport) {
+        let width = report
+            .secrets
+            .iter()
+            .map(|s| s.repository.len() + s.secret.len() + 1)
+            .max()
+            .unwrap_or(0);
+        for secret in &report.secrets {
+            let name = format!("{}/{}", secret.repository, secret.secret);
+            let age = secret
+                .updated_at
+                .map(|d| prompt::format_date(&d.to_rfc3339()))
+                .unwrap_or_else(|| "unknown".to_string());
+            let policy = secret
+                .policy
+                .as_ref()
+                .map(|(pattern, max_age)| format!("{} ≤ {}", pattern, max_age))
+                .unwrap_or_default();
+            if secret.stale {
+                println!(
+                    "  {} {:width$}  {:>16}  {}",
+                    "✗".red(),
+                    name,
+                    age.red(),
+                    format!("STALE ({})", policy).red(),
+                );
+            } else {
+                println!(
+                    "  {} {:width$}  {:>16}  {}",
+                    "✓".green(),
+                    name,
+                    age,
+                    policy.bright_black(),
+                );
+            }
+        }
+        for unchecked in &report.unchecked {
+            println!("  {} {}", "!".red(), unchecked.red());
         }
 
+        let stale = report.secrets.iter().filter(|s| s.stale).count();
         println!(
-            "\n{} {} {} {} {}...\n",
-            "Processing".cyan(),
-            secrets.len().to_string().bright_cyan(),
-            "secret(s) across".cyan(),
-            selected_indices.len().to_string().bright_cyan(),
-            "repository/repositories".cyan()
+            "\n{} secret(s), {} stale",
+            report.secrets.len(),
+            if stale > 0 {
+                stale.to_string().red()
+            } else {
+                stale.to_string().green()
+            }
         );
+    }
 
-        let mut all_results = Vec::new();
-        let mut all_failed_secrets: Vec<(usize, prompt::SecretPair)> = Vec::new();
+    /// Render an `age` report as JSON.
+    pub fn age_json(report: &AgeReport) -> Result<String> {
+        let secrets: Vec<serde_json::Value> = report
+            .secrets
+            .iter()
+            .map(|s| {
+                serde_json::json!({
+                    "repository": s.repository,
+                    "secret": s.secret,
+                    "updated_at": s.updated_at.map(|d| d.to_rfc3339()),
+                    "age_days": s.updated_at.map(|d| Utc::now().signed_duration_since(d).num_days()),
+                    "policy": s.policy.as_ref().map(|(pattern, _)| pattern),
+                    "max_age": s.policy.as_ref().map(|(_, max_age)| max_age),
+                    "stale": s.stale,
+                })
+            })
+            .collect();
+        Ok(serde_json::to_string_pretty(&serde_json::json!({
+            "secrets": secrets,
+            "unchecked": report.unchecked,
+        }))?)
+    }
 
-        for &repo_index in &selected_indices {
-            let selected_repo = &repositories[repo_index];
-            let repo_display = selected_repo.display_name();
+    pub async fn config() -> Result<()> {
+        let _factory = RealGitHubApiFactory;
+        let prompt_impl = RealPrompt;
+        let _rate_limiter = RealRateLimiter::new();
+        // We don't need factory or rate limiter for config really, but we need prompt.
 
-            println!("{}", "=".repeat(60).bright_black());
+        Self::config_with_deps(&prompt_impl).await
+    }
+
+    /// Run setup diagnostics and print a pass/warn/fail checklist.
+    ///
+    /// Returns an error if any check failed, so the process exits nonzero.
+    pub async fn doctor() -> Result<()> {
+        let results = doctor::run_checks().await;
+        doctor::print_report(&results);
+
+        let (_, _, failed) = doctor::count_by_status(&results);
+        if failed > 0 {
+            anyhow::bail!("{} doctor check(s) failed", failed);
+        }
+
+        Ok(())
+    }
+
+    pub async fn config_with_deps<P>(prompt_impl: &P) -> Result<()>
+    where
+        P: PromptInterface,
+    {
+        // 1. Check if config exists or use creation path
+        let config_path = Self::config_edit_path();
+
+        // 2. Load the file being edited, refusing to go on if it can't be read:
+        // saving over it would drop everything the dashboard couldn't load
+        let original = if config_path.exists() {
+            Some(std::fs::read_to_string(&config_path).with_context(|| {
+                format!("Failed to read config file: {}", config_path.display())
+            })?)
+        } else {
+            None
+        };
+        let initial_config = match &original {
+            Some(_) => config::Config::load_editable(
+                config_path
+                    .to_str()
+                    .context("Config path is not valid UTF-8")?,
+            )
+            .with_context(|| {
+                format!(
+                    "Failed to load config from {}; fix it before editing it here",
+                    config_path.display()
+                )
+            })?,
+            None => config::Config::default(),
+        };
+        let unchanged = match &original {
+            Some(text) => text.clone(),
+            None => config_edit::render(None, &config::Config::default())?,
+        };
+
+        // 3. Launch TUI Dashboard
+        match prompt_impl.manage_config(initial_config)? {
+            // 4. Save only if something changed
+            Some(new_config)
+                if config_edit::render(original.as_deref(), &new_config)? != unchanged =>
+            {
+                Self::save_config(&config_path, &new_config)?;
+            }
+            _ => println!("Configuration unchanged."),
+        }
+
+        Ok(())
+    }
+
+    /// Load the merged config used by `run`, or an empty config if no file exists yet.
+    fn load_layered_or_default() -> Result<config::Config> {
+        let layers = paths::find_config_layers();
+        if layers.is_empty() {
+            return Ok(config::Config::default());
+        }
+        config::Config::load_layered(&layers)
+    }
+
+    /// Print the configured repositories, as a table or as JSON.
+    pub fn config_list(json: bool) -> Result<()> {
+        let config = Self::load_layered_or_default()?;
+        println!("{}", Self::format_config_list(&config, json)?);
+        Ok(())
+    }
+
+    /// Format repositories for `config list`.
+    ///
+    /// The JSON form is an array of objects with `owner`, `name`, `alias`,
+    /// `groups`, `tags` and `source` fields.
+    pub fn format_config_list(config: &config::Config, json: bool) -> Result<String> {
+        let repositories = config.get_repositories();
+
+        if json {
+            let entries: Vec<serde_json::Value> = repositories
+                .iter()
+                .map(|r| {
+                    serde_json::json!({
+                        "owner": r.owner,
+                        "name": r.name,
+                        "alias": r.alias,
+                        "groups": r.groups,
+                        "tags": r.tags,
+                        "source": r.source.as_ref().map(|s| s.display().to_string()),
+                    })
+                })
+                .collect();
+            return Ok(serde_json::to_string_pretty(&entries)?);
+        }
+
+        if repositories.is_empty() {
+            return Ok("No repositories configured.".to_string());
+        }
+
+        let lines: Vec<String> = repositories
+            .iter()
+            .map(|r| {
+                let mut line = r.display_name();
+                if !r.groups.is_empty() {
+                    line.push_str(&format!(" [groups: {}]", r.groups.join(", ")));
+                }
+                if !r.tags.is_empty() {
+                    line.push_str(&format!(" [tags: {}]", r.tags.join(", ")));
+                }
+                line
+            })
+            .collect();
+        Ok(lines.join("\n"))
+    }
+
+    /// Validate the merged config without touching GitHub.
+    pub fn config_validate() -> Result<()> {
+        let layers = paths::find_config_layers();
+        if layers.is_empty() {
+            anyhow::bail!(
+                "No config file found. Create one with `github-secrets config add owner/name`"
+            );
+        }
+
+        let config = config::Config::load_layered(&layers)?;
+        println!(
+            "{} {} repository(ies) in {}",
+            "✓".green(),
+            config.get_repositories().len(),
+            layers
+                .iter()
+                .map(|p| p.display().to_string())
+                .collect::<Vec<_>>()
+                .join(", ")
+        );
+        Ok(())
+    }
+
+    /// Upgrade every config layer to the current schema version.
+    ///
+    /// Each changed file is rewritten atomically, keeping a `.bak` copy.
+    pub fn config_migrate() -> Result<()> {
+        let layers = paths::find_config_layers();
+        if layers.is_empty() {
+            anyhow::bail!("No config file found to migrate");
+        }
+
+        for path in &layers {
+            Self::config_migrate_at(path)?;
+        }
+        Ok(())
+    }
+
+    /// Upgrade a single config file to the current schema version.
+    ///
+    /// Returns whether the file was changed.
+    pub fn config_migrate_at(config_path: &Path) -> Result<bool> {
+        let content = std::fs::read_to_string(config_path)
+            .with_context(|| format!("Failed to read config file: {}", config_path.display()))?;
+
+        let Some(migrated) = schema::migrate(&content)
+            .with_context(|| format!("Failed to migrate {}", config_path.display()))?
+        else {
             println!(
-                "{} {}",
-                "Repository:".bright_cyan(),
-                repo_display.bright_cyan().bold()
+                "{} is already at version {}",
+                config_path.display(),
+                constants::schema::CURRENT_VERSION
             );
-            println!("{}", "=".repeat(60).bright_black());
+            return Ok(false);
+        };
 
-            let github_client = factory.create(
-                token.as_ref().clone(),
-                selected_repo.owner.clone(),
-                selected_repo.name.clone(),
-            )?;
+        config_edit::write_atomic(config_path, &migrated)?;
+        println!(
+            "{} Migrated {} to version {} (previous version saved as {})",
+            "✓".green(),
+            config_path.display(),
+            constants::schema::CURRENT_VERSION,
+            config_edit::backup_path(config_path).display()
+        );
+        Ok(true)
+    }
 
-            for secret in &secrets {
-                // Wait for rate limit before making API call
-                rate_limiter.wait_if_needed().await;
+    /// Apply a non-interactive change to the config file.
+    ///
+    /// Edits the same file as the `config` dashboard, creating it at
+    /// [`paths::get_config_creation_path`] if no config exists yet.
+    pub fn config_edit(action: ConfigAction) -> Result<()> {
+        Self::config_edit_layered(
+            &Self::config_edit_path(),
+            &paths::find_config_layers(),
+            action,
+        )
+    }
 
-                let secret_info = github_client
-                    .get_secret_info(&secret.key)
-                    .await
-                    .context("Failed to check if secret exists")?;
+    /// Same as `config_edit`, for an explicit config path.
+    pub fn config_edit_at(config_path: &Path, action: ConfigAction) -> Result<()> {
+        Self::config_edit_layered(config_path, &[], action)
+    }
 
-                rate_limiter.release();
+    /// Same as `config_edit_at`, refusing to remove or re-alias a repository
+    /// that `config list` shows from another of the merged `layers`, as only
+    /// `config_path` is edited.
+    pub fn config_edit_layered(
+        config_path: &Path,
+        layers: &[PathBuf],
+        action: ConfigAction,
+    ) -> Result<()> {
+        let mut config = if config_path.exists() {
+            config::Config::load_editable(
+                config_path
+                    .to_str()
+                    .context("Config path is not valid UTF-8")?,
+            )
+            .with_context(|| format!("Failed to load config from {}", config_path.display()))?
+        } else {
+            config::Config::default()
+        };
 
-                if let Some(info) = &secret_info {
-                    let last_updated = info.updated_at.as_deref();
-                    if !prompt_impl.confirm_secret_update(&secret.key, last_updated)? {
-                        println!(
-                            "{} {} {} {}",
-                            "⊘".yellow(),
-                            "Skipping secret".yellow(),
-                            format!("'{}'", secret.key).bright_yellow(),
-                            format!("in {}", repo_display).yellow()
-                        );
-                        all_results.push(UpdateResult {
-                            secret_name: secret.key.clone(),
-                            repository: repo_display.clone(),
-                            success: false,
-                            error: Some("User declined to overwrite".to_string()),
-                        });
-                        continue;
-                    }
+        let target = match &action {
+            ConfigAction::Add(_) => None,
+            ConfigAction::Remove(path) => Some(path),
+            ConfigAction::SetAlias { repository, .. } => Some(repository),
+        };
+        if let Some(target) = target
+            && !config.contains(target.trim())
+            && let Some(source) = Self::layer_defining(layers, target)
+        {
+            anyhow::bail!(
+                "Repository '{}' is defined in {}, but config commands edit {}; \
+                 run with CONFIG_PATH={} to edit that file",
+                target.trim(),
+                source.display(),
+                config_path.display(),
+                source.display()
+            );
+        }
+
+        let message = match action {
+            ConfigAction::Add(repository) => {
+                let path = repository.path();
+                config.add_repository(repository)?;
+                format!("Added {}", path)
+            }
+            ConfigAction::Remove(path) => {
+                let removed = config.remove_repository(&path)?;
+                format!("Removed {}", removed.path())
+            }
+            ConfigAction::SetAlias { repository, alias } => {
+                config.set_alias(&repository, alias.clone())?;
+                match alias {
+                    Some(alias) => format!("Set alias of {} to '{}'", repository, alias),
+                    None => format!("Cleared alias of {}", repository),
                 }
+            }
+        };
 
-                // Wait for rate limit before making API call
-                rate_limiter.wait_if_needed().await;
+        Self::save_config(config_path, &config)?;
+        println!("{} {}", "✓".green(), message);
+        Ok(())
+    }
 
-                let update_result = github_client
-                    .update_secret(&secret.key, &secret.value)
-                    .await;
+    /// The config file among `layers` that defines `repository`, if any.
+    fn layer_defining(layers: &[PathBuf], repository: &str) -> Option<PathBuf> {
+        config::Config::load_layered(layers)
+            .ok()?
+            .repositories
+            .into_iter()
+            .find(|r| r.path().eq_ignore_ascii_case(repository.trim()))?
+            .source
+    }
 
-                rate_limiter.release();
+    /// List secret sets in the local vault (`vault list`), showing key names only.
+    pub fn vault_list() -> Result<()> {
+        let path = paths::get_vault_path();
+        if !path.exists() {
+            println!(
+                "{} {}",
+                "No vault yet; save a secret set from the secret input screen (Ctrl+S) to create"
+                    .yellow(),
+                path.display()
+            );
+            return Ok(());
+        }
+        let passphrase = vault::read_passphrase("Vault passphrase: ")?;
+        println!("{}", Self::vault_list_at(&path, &passphrase)?);
+        Ok(())
+    }
 
-                match update_result {
-                    Ok(()) => {
-                        println!(
-                            "{} {} {} {} {}",
-                            "✓".green(),
-                            "Successfully updated secret".green(),
-                            format!("'{}'", secret.key).bright_green(),
-                            "in".green(),
-                            repo_display.bright_green()
-                        );
-                        all_results.push(UpdateResult {
-                            secret_name: secret.key.clone(),
-                            repository: repo_display.clone(),
-                            success: true,
-                            error: None,
-                        });
-                    }
-                    Err(e) => {
-                        // Extract detailed error message from error chain
-                        let detailed_error = error::format_error_chain(&e);
+    /// Format the secret sets of the vault at `path`, one per line with their keys.
+    pub fn vault_list_at(path: &Path, passphrase: &str) -> Result<String> {
+        let vault = vault::Vault::open(path, passphrase)?;
+        let names = vault.set_names();
+        if names.is_empty() {
+            return Ok("No secret sets saved.".to_string());
+        }
+        Ok(names
+            .iter()
+            .map(|name| {
+                let keys: Vec<String> = vault
+                    .get(name)
+                    .unwrap_or_default()
+                    .into_iter()
+                    .map(|s| s.key)
+                    .collect();
+                format!("{} ({}): {}", name, keys.len(), keys.join(", "))
+            })
+            .collect::<Vec<_>>()
+            .join("\n"))
+    }
 
-                        println!(
-                            "{} {} {} {} {}",
-                            "✗".red(),
-                            "Failed to update secret".red(),
-                            format!("'{}'", secret.key).bright_red(),
-                            "in".red(),
-                            repo_display.bright_red()
-                        );
-                        println!(
-                            "{} {}",
-                            "  Reason:".bright_red(),
-                            detailed_error.bright_red()
-                        );
+    /// Apply a `vault rename/remove` change to the local vault.
+    pub fn vault_edit(action: VaultAction) -> Result<()> {
+        let path = paths::get_vault_path();
+        if !path.exists() {
+            anyhow::bail!("No vault found at {}", path.display());
+        }
+        let passphrase = vault::read_passphrase("Vault passphrase: ")?;
+        let message = Self::vault_edit_at(&path, &passphrase, action)?;
+        println!("{} {}", "✓".green(), message);
+        Ok(())
+    }
 
-                        all_results.push(UpdateResult {
-                            secret_name: secret.key.clone(),
-                            repository: repo_display.clone(),
-                            success: false,
-                            error: Some(detailed_error.clone()),
-                        });
-                        all_failed_secrets.push((repo_index, secret.clone()));
-                    }
-                }
+    /// Same as `vault_edit`, for an explicit vault path and passphrase.
+    ///
+    /// Returns a description of the change.
+    pub fn vault_edit_at(path: &Path, passphrase: &str, action: VaultAction) -> Result<String> {
+        let mut vault = vault::Vault::open(path, passphrase)?;
+        let message = match action {
+            VaultAction::Rename { from, to } => {
+                vault.rename(&from, &to)?;
+                format!("Renamed secret set '{}' to '{}'", from, to)
+            }
+            VaultAction::Remove(name) => {
+                vault.remove(&name)?;
+                format!("Removed secret set '{}'", name)
             }
-            println!();
+        };
+        vault.save(path, passphrase)?;
+        Ok(message)
+    }
+
+    /// Import repositories from a GitHub organization or user into the config file.
+    pub async fn config_import(options: import::ImportOptions) -> Result<()> {
+        let token = Self::load_token()?;
+
+        let config_path = Self::config_edit_path();
+        let config = if config_path.exists() {
+            config::Config::load_editable(
+                config_path
+                    .to_str()
+                    .context("Config path is not valid UTF-8")?,
+            )
+            .with_context(|| format!("Failed to load config from {}", config_path.display()))?
+        } else {
+            config::Config::default()
+        };
+
+        let factory = RealGitHubApiFactory;
+        let prompt_impl = RealPrompt;
+
+        match Self::config_import_with_deps(&factory, &prompt_impl, token, &options, config).await?
+        {
+            Some(new_config) => Self::save_config(&config_path, &new_config)?,
+            None => println!("Configuration unchanged."),
         }
 
-        println!("\n{}", "=".repeat(60).bright_black());
-        println!("{}", "Overall Summary".bright_cyan().bold());
-        println!("{}", "=".repeat(60).bright_black());
+        Ok(())
+    }
 
-        let (success_count, failure_count) = Self::count_results(&all_results);
+    /// Same logic as `config_import` but with injectable dependencies to enable testing.
+    ///
+    /// Returns the updated config, or `None` if there was nothing to import.
+    pub async fn config_import_with_deps<F, P>(
+        factory: &F,
+        prompt_impl: &P,
+        token: Arc<String>,
+        options: &import::ImportOptions,
+        mut config: config::Config,
+    ) -> Result<Option<config::Config>>
+    where
+        F: GitHubApiFactory + Sync,
+        P: PromptInterface + Sync,
+    {
+        let api = factory.create(token.as_ref().clone(), options.owner.clone(), String::new())?;
+        let listing = api.list_repositories(options.kind).await?;
 
         println!(
-            "{} {}",
-            "Total operations:".cyan(),
-            all_results.len().to_string().bright_cyan()
+            "{} {} {} {}",
+            "Found".cyan(),
+            listing.len().to_string().bright_cyan(),
+            "repositories for".cyan(),
+            options.owner.bright_cyan()
         );
-        println!(
-            "{} {}",
-            "Successful:".green(),
-            success_count.to_string().bright_green()
+
+        let candidates = import::filter_candidates(&listing, options, &config);
+        for (path, reason) in &candidates.invalid {
+            println!(
+                "{} {} {}",
+                "⚠".yellow(),
+                format!("Skipping {}:", path).yellow(),
+                reason
+            );
+        }
+        if candidates.already_configured > 0 {
+            println!(
+                "{} {}",
+                candidates.already_configured.to_string().bright_black(),
+                "matching repositories are already configured".bright_black()
+            );
+        }
+
+        if candidates.repositories.is_empty() {
+            println!("{}", "No new repositories to import.".yellow());
+            return Ok(None);
+        }
+
+        let chosen = prompt_impl
+            .select_import(&candidates.repositories)
+            .context("Failed to select repositories to import")?;
+        let added = config.add_repositories(
+            chosen
+                .into_iter()
+                .map(|i| candidates.repositories[i].clone()),
         );
+
         println!(
-            "{} {}",
-            "Failed:".red(),
-            failure_count.to_string().bright_red()
+            "{} {} {}",
+            "✓".green(),
+            added.to_string().bright_green(),
+            "repositories imported".green()
         );
 
-        // Aggregate results by repository for breakdown
-        let repo_results = Self::aggregate_by_repository(&all_results);
+        Ok(Some(config))
+    }
+
+    /// Same logic as `run` but with injectable dependencies to enable testing.
+    pub async fn run_with_deps<F, P, RL>(
+        factory: &F,
+        prompt_impl: &P,
+        rate_limiter: &mut RL,
+        token: Arc<String>,
+        config: config::Config,
+    ) -> Result<()>
+    where
+        F: GitHubApiFactory + Sync,
+        P: PromptInterface + Sync,
+        RL: RateLimiterInterface + Send,
+    {
+        Self::run_with_options(
+            factory,
+            prompt_impl,
+            rate_limiter,
+            token,
+            config,
+            &RunOptions::default(),
+        )
+        .await
+    }
 
-        println!("\n{}", "Per-repository breakdown:".cyan());
-        for (repo, results) in &repo_results {
-            let repo_success = results.iter().filter(|r| r.success).count();
-            let repo_failure = results.len() - repo_success;
+    /// Resolve which repositories to target, from the command line or the selection menu.
+    pub fn resolve_selection<P>(
+        prompt_impl: &P,
+        repositories: &[config::Repository],
+        options: &RunOptions,
+    ) -> Result<Vec<usize>>
+    where
+        P: PromptInterface,
+    {
+        if options.has_repository_filter() {
+            let indices = options.select(repositories)?;
             println!(
-                "  {}: {} {}, {} {}",
-                repo.bright_cyan(),
-                repo_success.to_string().bright_green(),
-                "successful".green(),
-                repo_failure.to_string().bright_red(),
-                "failed".red()
+                "{} {}\n",
+                "Using repositories:".cyan(),
+                indices
+                    .iter()
+                    .map(|&i| repositories[i].display_name())
+                    .collect::<Vec<_>>()
+                    .join(", ")
+                    .bright_cyan()
             );
+            return Ok(indices);
         }
 
-        if failure_count > 0 {
-            println!("\n{}", "Failed operations:".red().bold());
-            for result in &all_results {
-                if !result.success {
-                    println!(
-                        "  {} {} {} {}",
-                        "✗".red(),
-                        result.secret_name.bright_red(),
-                        format!("in {}", result.repository).red(),
-                        format!(
-                            "→ {}",
-                            result
-                                .error
-                                .as_ref()
-                                .unwrap_or(&"Unknown error".to_string())
-                        )
-                        .bright_red()
-                    );
+        prompt_impl
+            .select_repositories(repositories)
+            .context("Failed to select repositories")
+    }
+
+    /// Print lint findings for the value of `key`.
+    ///
+    /// Returns an error if the findings should stop the upload.
+    fn check_lint(key: &str, value: &str, strict: bool) -> Result<()> {
+        let issues = lint::lint_value(key, value);
+        for issue in &issues {
+            let line = format!("  {} {}: {}", "⚠", key, issue);
+            match issue.severity {
+                lint::Severity::Warning => println!("{}", line.yellow()),
+                lint::Severity::Error => println!("{}", line.red()),
+            }
+        }
+        if lint::blocks_upload(&issues, strict) {
+            anyhow::bail!(
+                "Value of '{}' failed lint: {}",
+                key,
+                lint::summarize(&issues)
+            );
+        }
+        Ok(())
+    }
+
+    /// Resolve a raw value, linting values that came from a reference.
+    ///
+    /// Returns the value with the lint findings that don't stop the upload, for
+    /// the caller to show. Literal values are linted before any upload starts
+    /// instead.
+    fn resolve_checked(
+        key: &str,
+        raw: &str,
+        providers: &crate::provider::Providers,
+        strict: bool,
+    ) -> Result<(String, Vec<lint::LintIssue>)> {
+        let value = secret_ref::resolve_value(raw, providers)?;
+        if !secret_ref::SecretSource::parse(raw).is_reference() {
+            return Ok((value, Vec::new()));
+        }
+        let issues = lint::lint_value(key, &value);
+        if lint::blocks_upload(&issues, strict) {
+            anyhow::bail!(
+                "Value of '{}' failed lint: {}",
+                key,
+                lint::summarize(&issues)
+            );
+        }
+        Ok((value, issues))
+    }
+
+    /// When each secret's value last changed, for the bare `if-newer-than`
+    /// policy: the file behind a `file:` reference, else the bundle it came
+    /// from. Values typed, generated or looked up during the run are new.
+    fn value_times(
+        secrets: &[prompt::SecretPair],
+        bundle: Option<&Path>,
+        generated: &[generate::GenerateSpec],
+        now: DateTime<Utc>,
+    ) -> Vec<DateTime<Utc>> {
+        let modified = |path: &Path| {
+            std::fs::metadata(path)
+                .and_then(|m| m.modified())
+                .map(DateTime::<Utc>::from)
+                .ok()
+        };
+        secrets
+            .iter()
+            .map(|secret| {
+                if let secret_ref::SecretSource::File(path) =
+                    secret_ref::SecretSource::parse(&secret.value)
+                {
+                    return modified(&path);
+                }
+                if generated
+                    .iter()
+                    .any(|g| g.key.eq_ignore_ascii_case(&secret.key))
+                {
+                    return None;
+                }
+                bundle.and_then(modified)
+            })
+            .map(|time| time.unwrap_or(now))
+            .collect()
+    }
+
+    /// Show generated values once, or append them to `output`.
+    ///
+    /// This happens before uploading, so the values are never lost if an upload fails.
+    fn reveal_generated(generated: &[prompt::SecretPair], output: Option<&Path>) -> Result<()> {
+        match output {
+            Some(path) => {
+                generate::write_output(path, generated)?;
+                println!(
+                    "{} {} {}",
+                    "Generated values written to".cyan(),
+                    path.display().to_string().bright_cyan(),
+                    "(KEY=value)".bright_black()
+                );
+            }
+            None => {
+                println!(
+                    "{}",
+                    "Generated values (shown once; store them now):".yellow()
+                );
+                for pair in generated {
+                    println!("  {} = {}", pair.key.bright_cyan(), pair.value);
                 }
+                println!();
+            }
+        }
+        Ok(())
+    }
+
+    /// Same as `run_with_deps`, with command-line options applied.
+    pub async fn run_with_options<F, P, RL>(
+        factory: &F,
+        prompt_impl: &P,
+        rate_limiter: &mut RL,
+        token: Arc<String>,
+        config: config::Config,
+        options: &RunOptions,
+    ) -> Result<()>
+    where
+        F: GitHubApiFactory + Sync,
+        P: PromptInterface + Sync,
+        RL: RateLimiterInterface + Send,
+    {
+        let repositories = config.get_repositories();
+        let selected_indices = Self::resolve_selection(prompt_impl, repositories, options)?;
+
+        let mut secrets = match &options.bundle {
+            Some(path) => {
+                let secrets = bundle::load(path, options.identity.as_deref())?;
+                println!(
+                    "{} {} {} {}",
+                    "Loaded".cyan(),
+                    secrets.len().to_string().bright_cyan(),
+                    "secret(s) from".cyan(),
+                    path.display().to_string().bright_cyan()
+                );
+                secrets
             }
+            None if !options.generate.is_empty() => Vec::new(),
+            None => prompt_impl
+                .prompt_secrets()
+                .context("Failed to read secrets from user")?,
+        };
 
-            if prompt_impl.confirm_retry()? {
-                println!("\n{}", "Retrying failed operations...\n".yellow());
+        if !options.generate.is_empty() {
+            let generated = generate::generate_all(&options.generate);
+            Self::reveal_generated(&generated, options.generate_output.as_deref())?;
+            for pair in generated {
+                secrets.retain(|s| !s.key.eq_ignore_ascii_case(&pair.key));
+                secrets.push(pair);
+            }
+        }
 
-                for (repo_index, secret) in &all_failed_secrets {
-                    let repo = &repositories[*repo_index];
-                    let repo_display = repo.display_name();
+        if secrets.is_empty() {
+            println!("{}", "No secrets to update.".yellow());
+            return Ok(());
+        }
+
+        let duplicates = validation::find_duplicate_keys(secrets.iter().map(|s| s.key.as_str()));
+        if !duplicates.is_empty() {
+            anyhow::bail!(
+                "GitHub secret names are case-insensitive, so these are the same secret: {}",
+                duplicates
+                    .iter()
+                    .map(|(key, first)| format!("'{}' and '{}'", first, key))
+                    .collect::<Vec<_>>()
+                    .join(", ")
+            );
+        }
 
-                    let github_client = factory.create(
+        // Literal values are linted up front; references once they are resolved.
+        let mut rejected = Vec::new();
+        for secret in &secrets {
+            if let secret_ref::SecretSource::Literal(value) =
+                secret_ref::SecretSource::parse(&secret.value)
+                && Self::check_lint(&secret.key, &value, options.strict).is_err()
+            {
+                rejected.push(secret.key.clone());
+            }
+        }
+        if !rejected.is_empty() {
+            anyhow::bail!(
+                "Refusing to upload: {} failed lint{}",
+                rejected.join(", "),
+                if options.strict { " (strict mode)" } else { "" }
+            );
+        }
+
+        // `--overwrite` wins over each repository's own setting
+        let overwrite_policies = selected_indices
+            .iter()
+            .map(|&i| match options.overwrite {
+                Some(policy) => Ok(policy),
+                None => repositories[i]
+                    .overwrite_policy()
+                    .map(Option::unwrap_or_default)
+                    .with_context(|| format!("Invalid overwrite for {}", repositories[i].path())),
+            })
+            .collect::<Result<Vec<_>>>()?;
+        let value_times = Self::value_times(
+            &secrets,
+            options.bundle.as_deref(),
+            &options.generate,
+            Utc::now(),
+        );
+
+        let mut progress = Progress::new(
+            secrets.iter().map(|s| s.key.clone()).collect(),
+            selected_indices
+                .iter()
+                .map(|&i| repositories[i].display_name())
+                .collect(),
+        );
+        // Cells whose secret already existed, so a successful retry counts as an update
+        let mut existing: HashSet<(usize, usize)> = HashSet::new();
+        // Cells whose existence check succeeded; the others are checked again on retry
+        let mut checked: HashSet<(usize, usize)> = HashSet::new();
+        // Overwrite answers given for a whole repository, secret or run
+        let mut decisions = OverwriteDecisions::default();
+        // Values are resolved once per pass, on first use, so `cmd:` references run once.
+        let mut resolved_values: HashMap<String, std::result::Result<String, String>> =
+            HashMap::new();
+        let mut clients: HashMap<usize, Box<dyn GitHubApi>> = HashMap::new();
+        let mut cells: Vec<(usize, usize)> = (0..selected_indices.len())
+            .flat_map(|row| (0..secrets.len()).map(move |column| (row, column)))
+            .collect();
+        prompt_impl.show_progress(&progress)?;
+
+        loop {
+            for (row, column) in cells {
+                let selected_repo = &repositories[selected_indices[row]];
+                let secret = &secrets[column];
+                progress.set(row, column, CellStatus::Running);
+                prompt_impl.show_progress(&progress)?;
+
+                let github_client = match clients.entry(row) {
+                    Entry::Occupied(entry) => entry.into_mut(),
+                    Entry::Vacant(entry) => entry.insert(factory.create(
                         token.as_ref().clone(),
-                        repo.owner.clone(),
-                        repo.name.clone(),
-                    )?;
-
-                    match github_client
-                        .update_secret(&secret.key, &secret.value)
-                        .await
-                    {
-                        Ok(()) => {
-                            println!(
-                                "{} {} {} {} {} {}",
-                                "✓".green(),
-                                "Successfully updated secret".green(),
-                                format!("'{}'", secret.key).bright_green(),
-                                "in".green(),
-                                repo_display.bright_green(),
-                                "(retry)".bright_black()
-                            );
-                            if let Some(result) = all_results.iter_mut().find(|r| {
-                                r.secret_name == secret.key && r.repository == repo_display
-                            }) {
-                                result.success = true;
-                                result.error = None;
-                            }
-                        }
+                        selected_repo.owner.clone(),
+                        selected_repo.name.clone(),
+                    )?),
+                };
+
+                if !checked.contains(&(row, column)) {
+                    // Wait for rate limit before making API call
+                    rate_limiter.wait_if_needed().await;
+                    let secret_info = github_client.get_secret_info(&secret.key).await;
+                    rate_limiter.release();
+
+                    let secret_info = match secret_info {
+                        Ok(info) => info,
                         Err(e) => {
-                            let detailed_error = error::format_error_chain(&e);
-
-                            println!(
-                                "{} {} {} {} {} {}",
-                                "✗".red(),
-                                "Failed to update secret".red(),
-                                format!("'{}'", secret.key).bright_red(),
-                                "in".red(),
-                                repo_display.bright_red(),
-                                "(retry)".bright_black()
+                            let e = e.context("Failed to check if secret exists");
+                            let result = UpdateResult::new_failure(
+                                secret.key.clone(),
+                                progress.repositories[row].clone(),
+                                error::format_error_chain(&e),
                             );
-                            println!(
-                                "{} {}",
-                                "  Reason:".bright_red(),
-                                detailed_error.bright_red()
+                            progress.record(row, column, &result, false);
+                            prompt_impl.show_progress(&progress)?;
+                            continue;
+                        }
+                    };
+                    checked.insert((row, column));
+
+                    if let Some(info) = &secret_info {
+                        existing.insert((row, column));
+                        if !info.name.is_empty() && info.name != secret.key {
+                            progress.note(
+                                row,
+                                column,
+                                format!(
+                                    "'{}' is the existing secret '{}' (names are case-insensitive)",
+                                    secret.key, info.name
+                                ),
                             );
                         }
+                        let repository = &progress.repositories[row];
+                        let by_policy = overwrite_policies[row].decide(
+                            info.updated_at.as_deref(),
+                            value_times[column],
+                            Utc::now(),
+                        );
+                        let overwrite =
+                            match by_policy.or(decisions.decided(repository, &secret.key)) {
+                                Some(overwrite) => overwrite,
+                                None => {
+                                    let answer = prompt_impl.confirm_secret_update(
+                                        repository,
+                                        &secret.key,
+                                        info.updated_at.as_deref(),
+                                    )?;
+                                    decisions.record(repository, &secret.key, answer)
+                                }
+                            };
+                        if !overwrite {
+                            progress.set(row, column, CellStatus::Skipped);
+                            prompt_impl.show_progress(&progress)?;
+                            continue;
+                        }
                     }
                 }
 
-                let (final_success, final_failure) = Self::count_results(&all_results);
+                if !resolved_values.contains_key(&secret.key) {
+                    let resolved = match Self::resolve_checked(
+                        &secret.key,
+                        &secret.value,
+                        &config.providers,
+                        options.strict,
+                    ) {
+                        Ok((value, findings)) => {
+                            for finding in findings {
+                                progress.note(row, column, finding.to_string());
+                            }
+                            Ok(value)
+                        }
+                        Err(e) => Err(error::format_error_chain(&e)),
+                    };
+                    resolved_values.insert(secret.key.clone(), resolved);
+                }
 
-                println!("\n{}", "=".repeat(60).bright_black());
-                println!("{}", "Final Summary".bright_cyan().bold());
-                println!("{}", "=".repeat(60).bright_black());
-                println!(
-                    "{} {}",
-                    "Total operations:".cyan(),
-                    all_results.len().to_string().bright_cyan()
-                );
-                println!(
-                    "{} {}",
-                    "Successful:".green(),
-                    final_success.to_string().bright_green()
-                );
-                println!(
-                    "{} {}",
-                    "Failed:".red(),
-                    final_failure.to_string().bright_red()
-                );
+                let update_result = match &resolved_values[&secret.key] {
+                    Ok(value) => {
+                        // Wait for rate limit before making API call
+                        rate_limiter.wait_if_needed().await;
+                        let result = github_client.update_secret(&secret.key, value).await;
+                        rate_limiter.release();
+                        result
+                    }
+                    Err(reason) => Err(anyhow::anyhow!(reason.clone())),
+                };
+
+                let repository = progress.repositories[row].clone();
+                let result = match update_result {
+                    Ok(()) => UpdateResult::new_success(secret.key.clone(), repository),
+                    // Extract detailed error message from error chain
+                    Err(e) => UpdateResult::new_failure(
+                        secret.key.clone(),
+                        repository,
+                        error::format_error_chain(&e),
+                    ),
+                };
+                progress.record(row, column, &result, existing.contains(&(row, column)));
+                prompt_impl.show_progress(&progress)?;
+            }
+
+            // The summary offers to retry for as long as something failed
+            if !prompt_impl.review_progress(&progress)? || progress.failed().is_empty() {
+                break;
             }
+            cells = progress.failed();
+            // Values that resolved are reused; only failed resolutions run again.
+            resolved_values.retain(|_, resolved| resolved.is_ok());
         }
 
         Ok(())
@@ -1,12 +1,18 @@
 use anyhow::Result;
 use async_trait::async_trait;
+use std::sync::{Mutex, OnceLock};
 
-use crate::{config, github, prompt, rate_limit};
+use crate::{config, github, overwrite, progress, prompt, rate_limit};
 
 #[async_trait]
 pub trait GitHubApi: Send + Sync {
     async fn get_secret_info(&self, secret_name: &str) -> Result<Option<github::SecretInfo>>;
     async fn update_secret(&self, secret_name: &str, secret_value: &str) -> Result<()>;
+    async fn list_repositories(
+        &self,
+        kind: github::OwnerKind,
+    ) -> Result<Vec<github::RemoteRepository>>;
+    async fn list_secrets(&self) -> Result<Vec<github::RemoteSecret>>;
 }
 
 pub trait GitHubApiFactory: Send + Sync {
@@ -26,6 +32,17 @@ impl GitHubApi for RealGitHubApi {
     async fn update_secret(&self, secret_name: &str, secret_value: &str) -> Result<()> {
         self.inner.update_secret(secret_name, secret_value).await
     }
+
+    async fn list_repositories(
+        &self,
+        kind: github::OwnerKind,
+    ) -> Result<Vec<github::RemoteRepository>> {
+        self.inner.list_repositories(kind).await
+    }
+
+    async fn list_secrets(&self) -> Result<Vec<github::RemoteSecret>> {
+        self.inner.list_secrets().await
+    }
 }
 
 pub struct RealGitHubApiFactory;
@@ -40,14 +57,42 @@ impl GitHubApiFactory for RealGitHubApiFactory {
 pub trait PromptInterface: Send + Sync {
     fn select_repositories(&self, repositories: &[config::Repository]) -> Result<Vec<usize>>;
     fn prompt_secrets(&self) -> Result<Vec<prompt::SecretPair>>;
-    fn confirm_secret_update(&self, key: &str, last_updated: Option<&str>) -> Result<bool>;
+    /// Ask whether to overwrite the existing secret `key` in `repository`.
+    fn confirm_secret_update(
+        &self,
+        repository: &str,
+        key: &str,
+        last_updated: Option<&str>,
+    ) -> Result<overwrite::OverwriteAnswer>;
     fn confirm_retry(&self) -> Result<bool>;
+    fn confirm_action(&self, question: &str) -> Result<bool>;
 
     fn manage_config(&self, initial: config::Config) -> Result<Option<config::Config>>;
+    fn select_import(&self, candidates: &[config::Repository]) -> Result<Vec<usize>>;
+
+    /// Show the progress of a run; called whenever a status changes.
+    fn show_progress(&self, _progress: &progress::Progress) -> Result<()> {
+        Ok(())
+    }
+
+    /// Show the outcome of a run. Returns whether to retry the failed operations.
+    fn review_progress(&self, progress: &progress::Progress) -> Result<bool> {
+        if progress.counts().failed > 0 {
+            self.confirm_retry()
+        } else {
+            Ok(false)
+        }
+    }
 }
 
 pub struct RealPrompt;
 
+/// The progress dashboard's terminal, kept for the whole run.
+fn progress_screen() -> &'static Mutex<prompt::ProgressScreen> {
+    static SCREEN: OnceLock<Mutex<prompt::ProgressScreen>> = OnceLock::new();
+    SCREEN.get_or_init(Default::default)
+}
+
 impl PromptInterface for RealPrompt {
     fn select_repositories(&self, repositories: &[config::Repository]) -> Result<Vec<usize>> {
         crate::prompt::select_repositories(repositories)
@@ -57,17 +102,41 @@ impl PromptInterface for RealPrompt {
         crate::prompt::prompt_secrets()
     }
 
-    fn confirm_secret_update(&self, name: &str, last_updated: Option<&str>) -> Result<bool> {
-        crate::prompt::confirm_secret_update(name, last_updated)
+    fn confirm_secret_update(
+        &self,
+        repository: &str,
+        name: &str,
+        last_updated: Option<&str>,
+    ) -> Result<overwrite::OverwriteAnswer> {
+        let answer = crate::prompt::confirm_secret_update(repository, name, last_updated);
+        progress_screen().lock().unwrap().invalidate();
+        answer
     }
 
     fn confirm_retry(&self) -> Result<bool> {
         crate::prompt::confirm_retry()
     }
 
+    fn confirm_action(&self, question: &str) -> Result<bool> {
+        crate::prompt::prompt_confirm(question)
+    }
+
     fn manage_config(&self, initial: config::Config) -> Result<Option<config::Config>> {
         crate::prompt::manage_config(initial)
     }
+
+    fn select_import(&self, candidates: &[config::Repository]) -> Result<Vec<usize>> {
+        crate::prompt::select_import(candidates)
+    }
+
+    fn show_progress(&self, progress: &progress::Progress) -> Result<()> {
+        progress_screen().lock().unwrap().show(progress)
+    }
+
+    fn review_progress(&self, progress: &progress::Progress) -> Result<bool> {
+        progress_screen().lock().unwrap().close();
+        crate::prompt::review_progress(progress)
+    }
 }
 
 #[async_trait]
@@ -0,0 +1,356 @@
+//! Encrypted secret bundles (age and SOPS).
+//!
+//! A bundle is a file of `KEY: value` pairs that is either encrypted as a whole
+//! with [age](https://age-encryption.org), or a [SOPS](https://github.com/getsops/sops)
+//! YAML/JSON file whose values are encrypted with an age recipient. Bundles are
+//! decrypted in memory with a local identity file and turned into
+//! [`prompt::SecretPair`]s; nothing decrypted is written to disk.
+//!
+//! SOPS files are decrypted value by value, and the file is only accepted if its
+//! message authentication code (`sops.mac`) matches the decrypted values, so
+//! values can't be edited, swapped or left in plaintext without sops noticing.
+//! Only keys ending in `unencrypted_suffix` may hold plaintext.
+
+use aes_gcm::AesGcm;
+use aes_gcm::aead::consts::U32;
+use aes_gcm::aead::{Aead, KeyInit, Payload};
+use aes_gcm::aes::Aes256;
+use anyhow::{Context, Result};
+use base64::Engine;
+use base64::engine::general_purpose::STANDARD;
+use regex::Regex;
+use serde_yaml::{Mapping, Value};
+use sha2::{Digest, Sha512};
+use std::io::{BufReader, Read};
+use std::path::{Path, PathBuf};
+use std::sync::OnceLock;
+
+use crate::prompt::SecretPair;
+use crate::secret_ref;
+use crate::validation;
+
+/// AES-256-GCM with the 32-byte nonces SOPS uses.
+type SopsCipher = AesGcm<Aes256, U32>;
+
+/// Header of a binary age file.
+const AGE_HEADER: &[u8] = b"age-encryption.org/";
+/// Header of an ASCII-armored age file.
+const AGE_ARMOR_HEADER: &[u8] = b"-----BEGIN AGE ENCRYPTED FILE-----";
+/// Top-level key holding SOPS metadata.
+const SOPS_KEY: &str = "sops";
+/// Default suffix SOPS uses for keys that are left unencrypted.
+const SOPS_UNENCRYPTED_SUFFIX: &str = "_unencrypted";
+/// SOPS settings choosing encrypted keys some other way than `unencrypted_suffix`.
+const UNSUPPORTED_SOPS_SETTINGS: &[&str] = &[
+    "encrypted_suffix",
+    "encrypted_regex",
+    "unencrypted_regex",
+    "encrypted_comment_regex",
+    "unencrypted_comment_regex",
+];
+
+/// Get the identity file used when `--identity` is not given.
+///
+/// Follows SOPS: `SOPS_AGE_KEY_FILE` if set, otherwise `sops/age/keys.txt`
+/// under the user's config directory.
+pub fn default_identity_path() -> Option<PathBuf> {
+    if let Ok(path) = std::env::var("SOPS_AGE_KEY_FILE") {
+        return Some(PathBuf::from(path));
+    }
+    dirs::config_dir().map(|dir| dir.join("sops").join("age").join("keys.txt"))
+}
+
+/// Load age identities from an identity file (as written by `age-keygen`).
+pub fn load_identities(path: &Path) -> Result<Vec<Box<dyn age::Identity>>> {
+    let file = age::IdentityFile::from_file(path.to_string_lossy().into_owned())
+        .with_context(|| format!("Failed to read identity file {}", path.display()))?;
+    let identities = file
+        .into_identities()
+        .with_context(|| format!("Unsupported identity in {}", path.display()))?;
+    if identities.is_empty() {
+        anyhow::bail!("No identities found in {}", path.display());
+    }
+    Ok(identities)
+}
+
+/// Decrypt an age file (binary or ASCII-armored) in memory.
+pub fn age_decrypt(ciphertext: &[u8], identities: &[Box<dyn age::Identity>]) -> Result<Vec<u8>> {
+    let reader = age::armor::ArmoredReader::new(BufReader::new(ciphertext));
+    let decryptor = age::Decryptor::new(reader).context("Not a valid age file")?;
+    let mut stream = decryptor
+        .decrypt(identities.iter().map(|i| i.as_ref() as &dyn age::Identity))
+        .context("Failed to decrypt: no matching identity")?;
+
+    let mut plaintext = Vec::new();
+    stream
+        .read_to_end(&mut plaintext)
+        .context("Failed to decrypt age payload")?;
+    Ok(plaintext)
+}
+
+/// Check whether the data looks like an age file.
+pub fn is_age(data: &[u8]) -> bool {
+    let start = data
+        .iter()
+        .position(|b| !b.is_ascii_whitespace())
+        .unwrap_or(data.len());
+    let trimmed = &data[start..];
+    trimmed.starts_with(AGE_HEADER) || trimmed.starts_with(AGE_ARMOR_HEADER)
+}
+
+/// Parse decrypted bundle text: a YAML/JSON mapping, or `KEY=VALUE` lines.
+pub fn parse_plain(text: &str) -> Result<Vec<SecretPair>> {
+    if let Ok(Value::Mapping(mapping)) = serde_yaml::from_str::<Value>(text) {
+        return mapping_to_pairs(&mapping, |_, value| scalar_to_string(value));
+    }
+
+    let mut pairs = Vec::new();
+    for (idx, line) in text.lines().enumerate() {
+        let line = line.trim();
+        if line.is_empty() || line.starts_with('#') {
+            continue;
+        }
+        let line = line.strip_prefix("export ").unwrap_or(line);
+        let (key, value) = line
+            .split_once('=')
+            .with_context(|| format!("Line {}: expected KEY=VALUE", idx + 1))?;
+        let value = value.trim();
+        let value = value
+            .strip_prefix('"')
+            .and_then(|v| v.strip_suffix('"'))
+            .or_else(|| value.strip_prefix('\'').and_then(|v| v.strip_suffix('\'')))
+            .unwrap_or(value);
+        pairs.push(new_pair(key.trim(), value)?);
+    }
+    Ok(pairs)
+}
+
+/// Decrypt a SOPS document whose data key is encrypted for an age recipient.
+///
+/// # Errors
+///
+/// Returns an error if a value can't be decrypted, a key without the
+/// `unencrypted_suffix` holds plaintext, or the MAC is missing or doesn't
+/// match the values.
+pub fn decrypt_sops(
+    document: &Mapping,
+    identities: &[Box<dyn age::Identity>],
+) -> Result<Vec<SecretPair>> {
+    let metadata = document
+        .get(SOPS_KEY)
+        .and_then(Value::as_mapping)
+        .context("Missing sops metadata")?;
+    if let Some(setting) = UNSUPPORTED_SOPS_SETTINGS
+        .iter()
+        .find(|setting| metadata.contains_key(**setting))
+    {
+        anyhow::bail!(
+            "SOPS files using `{}` are not supported; use `unencrypted_suffix`",
+            setting
+        );
+    }
+
+    let data_key = sops_data_key(metadata, identities)?;
+    let suffix = metadata
+        .get("unencrypted_suffix")
+        .and_then(Value::as_str)
+        .unwrap_or(SOPS_UNENCRYPTED_SUFFIX);
+    let mac_only_encrypted = metadata
+        .get("mac_only_encrypted")
+        .and_then(Value::as_bool)
+        .unwrap_or(false);
+
+    // SOPS hashes every value in document order, after decryption.
+    let mut hash = Sha512::new();
+    let pairs = mapping_to_pairs(document, |key, value| {
+        if key.ends_with(suffix) {
+            if !mac_only_encrypted {
+                hash.update(sops_plaintext_bytes(value)?);
+            }
+            return scalar_to_string(value);
+        }
+        let encrypted = value
+            .as_str()
+            .filter(|text| text.starts_with("ENC["))
+            .with_context(|| {
+                format!(
+                    "'{}' is not encrypted; only keys ending in '{}' may be plaintext",
+                    key, suffix
+                )
+            })?;
+        let text = sops_decrypt_value(encrypted, &data_key, &format!("{}:", key))
+            .with_context(|| format!("Failed to decrypt '{}'", key))?;
+        hash.update(text.as_bytes());
+        Ok(text)
+    })?;
+
+    verify_sops_mac(metadata, &data_key, &hash.finalize())?;
+    Ok(pairs)
+}
+
+/// Read, decrypt and parse a bundle file.
+///
+/// # Arguments
+///
+/// * `path` - An age-encrypted file, or a SOPS YAML/JSON file
+/// * `identity_path` - age identity file; defaults to [`default_identity_path`]
+pub fn load(path: &Path, identity_path: Option<&Path>) -> Result<Vec<SecretPair>> {
+    let data =
+        std::fs::read(path).with_context(|| format!("Failed to read bundle {}", path.display()))?;
+
+    let identity_path = match identity_path {
+        Some(path) => path.to_path_buf(),
+        None => default_identity_path()
+            .context("No identity file given; pass --identity or set SOPS_AGE_KEY_FILE")?,
+    };
+    let identities = load_identities(&identity_path)?;
+
+    if is_age(&data) {
+        let plaintext = age_decrypt(&data, &identities)
+            .with_context(|| format!("Failed to decrypt {}", path.display()))?;
+        let text = String::from_utf8(plaintext).context("Decrypted bundle is not valid UTF-8")?;
+        return parse_plain(&text);
+    }
+
+    let text = std::str::from_utf8(&data).context("Bundle is not valid UTF-8")?;
+    match serde_yaml::from_str::<Value>(text) {
+        Ok(Value::Mapping(document)) if document.contains_key(SOPS_KEY) => {
+            decrypt_sops(&document, &identities)
+                .with_context(|| format!("Failed to decrypt {}", path.display()))
+        }
+        _ => anyhow::bail!(
+            "{} is neither an age-encrypted file nor a SOPS file",
+            path.display()
+        ),
+    }
+}
+
+/// Recover the SOPS data key from the `sops.age` entries.
+fn sops_data_key(metadata: &Mapping, identities: &[Box<dyn age::Identity>]) -> Result<Vec<u8>> {
+    let entries = metadata
+        .get("age")
+        .and_then(Value::as_sequence)
+        .context("SOPS file has no age recipients")?;
+
+    let mut last_error = None;
+    for entry in entries {
+        let Some(enc) = entry.get("enc").and_then(Value::as_str) else {
+            continue;
+        };
+        match age_decrypt(enc.as_bytes(), identities) {
+            Ok(key) if key.len() == 32 => return Ok(key),
+            Ok(key) => last_error = Some(anyhow::anyhow!("Data key has {} bytes", key.len())),
+            Err(e) => last_error = Some(e),
+        }
+    }
+
+    Err(last_error
+        .unwrap_or_else(|| anyhow::anyhow!("SOPS file has no age recipients"))
+        .context("None of the identities can decrypt the SOPS data key"))
+}
+
+/// Check `sops.mac` against the SHA-512 `digest` of the values.
+///
+/// The MAC is encrypted with the data key, using `sops.lastmodified` as
+/// additional data, so it can't be recomputed without the key.
+fn verify_sops_mac(metadata: &Mapping, data_key: &[u8], digest: &[u8]) -> Result<()> {
+    let mac = metadata
+        .get("mac")
+        .and_then(Value::as_str)
+        .context("SOPS file has no MAC; its values can't be trusted")?;
+    let last_modified = metadata
+        .get("lastmodified")
+        .and_then(Value::as_str)
+        .context("SOPS file has no lastmodified time")?;
+    let expected =
+        sops_decrypt_value(mac, data_key, last_modified).context("Failed to decrypt SOPS MAC")?;
+
+    let actual: String = digest.iter().map(|b| format!("{:02X}", b)).collect();
+    if !expected.eq_ignore_ascii_case(&actual) {
+        anyhow::bail!("SOPS MAC mismatch: the file was changed outside sops");
+    }
+    Ok(())
+}
+
+/// Bytes SOPS hashes for a plaintext value (booleans are `True`/`False`).
+fn sops_plaintext_bytes(value: &Value) -> Result<Vec<u8>> {
+    Ok(match value {
+        Value::Bool(true) => b"True".to_vec(),
+        Value::Bool(false) => b"False".to_vec(),
+        _ => scalar_to_string(value)?.into_bytes(),
+    })
+}
+
+/// The pattern of an encrypted SOPS value, compiled once.
+fn sops_value_pattern() -> &'static Regex {
+    static PATTERN: OnceLock<Regex> = OnceLock::new();
+    PATTERN.get_or_init(|| {
+        Regex::new(r"^ENC\[AES256_GCM,data:(.*),iv:(.+),tag:(.+),type:(.+)\]$")
+            .expect("valid SOPS value pattern")
+    })
+}
+
+/// Decrypt a single `ENC[AES256_GCM,data:...,iv:...,tag:...,type:...]` value.
+fn sops_decrypt_value(encrypted: &str, data_key: &[u8], additional_data: &str) -> Result<String> {
+    let captures = sops_value_pattern()
+        .captures(encrypted)
+        .context("Unsupported SOPS value format")?;
+
+    let mut data = STANDARD.decode(&captures[1]).context("Invalid data")?;
+    let iv = STANDARD.decode(&captures[2]).context("Invalid iv")?;
+    let tag = STANDARD.decode(&captures[3]).context("Invalid tag")?;
+    if iv.len() != 32 {
+        anyhow::bail!("Unsupported iv length {}", iv.len());
+    }
+    data.extend_from_slice(&tag);
+
+    let cipher = SopsCipher::new_from_slice(data_key).context("Invalid data key")?;
+    let plaintext = cipher
+        .decrypt(
+            iv.as_slice().into(),
+            Payload {
+                msg: &data,
+                aad: additional_data.as_bytes(),
+            },
+        )
+        .map_err(|_| anyhow::anyhow!("Authentication failed"))?;
+
+    String::from_utf8(plaintext).context("Decrypted value is not valid UTF-8")
+}
+
+/// Convert top-level entries (except SOPS metadata) into secret pairs.
+fn mapping_to_pairs(
+    mapping: &Mapping,
+    mut value_of: impl FnMut(&str, &Value) -> Result<String>,
+) -> Result<Vec<SecretPair>> {
+    let mut pairs = Vec::new();
+    for (key, value) in mapping {
+        let key = key.as_str().context("Bundle keys must be strings")?;
+        if key == SOPS_KEY {
+            continue;
+        }
+        let value = value_of(key, value)?;
+        pairs.push(new_pair(key, &value)?);
+    }
+    Ok(pairs)
+}
+
+fn scalar_to_string(value: &Value) -> Result<String> {
+    match value {
+        Value::String(s) => Ok(s.clone()),
+        Value::Number(n) => Ok(n.to_string()),
+        Value::Bool(b) => Ok(b.to_string()),
+        Value::Null => Ok(String::new()),
+        _ => anyhow::bail!("Nested values are not supported; use top-level KEY: value pairs"),
+    }
+}
+
+fn new_pair(key: &str, value: &str) -> Result<SecretPair> {
+    validation::validate_secret_key(key)
+        .with_context(|| format!("Invalid secret name '{}' in bundle", key))?;
+    Ok(SecretPair {
+        key: key.to_string(),
+        // Decrypted values are literal, even if they look like a reference.
+        value: secret_ref::escape_literal(value),
+    })
+}
@@ -1,14 +1,324 @@
-use clap::{Parser, Subcommand};
+use clap::{Args, Parser, Subcommand};
+use std::path::PathBuf;
+
+use crate::app::{ConfigAction, NewValue};
+use crate::config;
+use crate::generate::GenerateSpec;
+use crate::github::OwnerKind;
+use crate::import::ImportOptions;
+use crate::overwrite::OverwritePolicy;
+use crate::policy::RotationPolicy;
 
 #[derive(Parser)]
 #[command(author, version, about, long_about = None)]
 pub struct Cli {
     #[command(subcommand)]
     pub command: Option<Commands>,
+
+    /// Target repositories in this group, skipping the selection menu (repeatable)
+    #[arg(long = "group", value_name = "GROUP", global = true)]
+    pub groups: Vec<String>,
+
+    /// Target repositories with this tag, skipping the selection menu (repeatable)
+    #[arg(long = "tag", value_name = "TAG", global = true)]
+    pub tags: Vec<String>,
+
+    /// Target repositories by path, alias, glob (`my-org/svc-*`), regex (`re:...`)
+    /// or exclusion (`!pattern`), skipping the selection menu (repeatable)
+    #[arg(
+        long = "repo",
+        value_name = "PATTERN",
+        allow_hyphen_values = true,
+        global = true
+    )]
+    pub repos: Vec<String>,
+
+    /// Target the repositories picked in the selection menu last time, skipping the menu
+    #[arg(long, conflicts_with_all = ["groups", "tags", "repos"], global = true)]
+    pub last: bool,
+
+    /// Read secrets from an age-encrypted or SOPS file instead of prompting
+    #[arg(long, value_name = "FILE")]
+    pub bundle: Option<PathBuf>,
+
+    /// age identity file used to decrypt --bundle
+    /// (default: $SOPS_AGE_KEY_FILE or ~/.config/sops/age/keys.txt)
+    #[arg(long, value_name = "FILE", requires = "bundle")]
+    pub identity: Option<PathBuf>,
+
+    /// Generate a random value instead of prompting, e.g. `WEBHOOK_SECRET:64:hex`
+    /// (charsets: hex, base64, alphanumeric, urlsafe; repeatable)
+    #[arg(long = "generate", value_name = "KEY:LEN[:CHARSET]")]
+    pub generate: Vec<GenerateSpec>,
+
+    /// Append generated values to this file as KEY=value instead of printing them
+    #[arg(long, value_name = "FILE", requires = "generate")]
+    pub generate_output: Option<PathBuf>,
+
+    /// Refuse to upload values with lint warnings (stray whitespace, quotes,
+    /// placeholders...), not just errors
+    #[arg(long, global = true)]
+    pub strict: bool,
+
+    /// How to handle secrets that already exist: prompt, always, never,
+    /// if-older-than=<duration>, if-newer-than=<duration> (units: m, h, d, w) or
+    /// if-newer-than (the value changed after the secret); overrides `overwrite`
+    /// in config (uploads only, so not used by subcommands)
+    #[arg(long, value_name = "POLICY")]
+    pub overwrite: Option<OverwritePolicy>,
 }
 
 #[derive(Subcommand)]
 pub enum Commands {
     /// Configure the application (view or edit settings)
-    Config,
+    Config {
+        #[command(subcommand)]
+        action: Option<ConfigCommands>,
+    },
+    /// Diagnose configuration, token and connectivity problems
+    Doctor,
+    /// Report secret ages and flag those older than their rotation policy
+    Age(AgeArgs),
+    /// Push a new value for a secret to every configured repository that has it
+    Rotate(RotateArgs),
+    /// Manage saved secret sets in the local encrypted vault
+    Vault {
+        #[command(subcommand)]
+        action: VaultCommands,
+    },
+}
+
+#[derive(Args)]
+pub struct AgeArgs {
+    /// Maximum age for secrets matching a name glob, e.g. `*_TOKEN=90d`
+    /// (units: m, h, d, w; repeatable, checked before config policies)
+    #[arg(long = "policy", value_name = "PATTERN=DURATION")]
+    pub policies: Vec<RotationPolicy>,
+
+    /// Print machine-readable JSON
+    #[arg(long)]
+    pub json: bool,
+}
+
+#[derive(Args)]
+pub struct RotateArgs {
+    /// Name of the secret to rotate
+    pub secret: String,
+
+    /// New value or reference (`env:VAR`, `file:path`, `cmd:...`, `provider:...`);
+    /// prompted for without echo when neither this nor --generate is given
+    #[arg(long, value_name = "VALUE", conflicts_with = "generate")]
+    pub value: Option<String>,
+
+    /// Generate the new value, e.g. `64:hex` (charsets: hex, base64, alphanumeric, urlsafe)
+    #[arg(long, value_name = "LEN[:CHARSET]", num_args = 0..=1, default_missing_value = "")]
+    pub generate: Option<String>,
+
+    /// Also create the secret in selected repositories that don't have it
+    #[arg(long)]
+    pub create: bool,
+
+    /// Don't ask for confirmation
+    #[arg(long, short = 'y')]
+    pub yes: bool,
+}
+
+impl RotateArgs {
+    /// Convert the parsed arguments into a new-value source.
+    pub fn new_value(&self) -> anyhow::Result<NewValue> {
+        Ok(match (&self.value, &self.generate) {
+            (Some(value), _) => NewValue::Given(value.clone()),
+            (None, Some(spec)) if spec.is_empty() => {
+                NewValue::Generate(self.secret.parse::<GenerateSpec>()?)
+            }
+            (None, Some(spec)) => {
+                NewValue::Generate(format!("{}:{}", self.secret, spec).parse::<GenerateSpec>()?)
+            }
+            (None, None) => NewValue::Prompt,
+        })
+    }
+}
+
+#[derive(Subcommand)]
+pub enum VaultCommands {
+    /// List saved secret sets and their keys
+    List,
+    /// Rename a secret set
+    Rename {
+        /// Current set name
+        from: String,
+        /// New set name
+        to: String,
+    },
+    /// Remove a secret set
+    Remove {
+        /// Set name
+        name: String,
+    },
+}
+
+#[derive(Subcommand)]
+pub enum ConfigCommands {
+    /// Import repositories from a GitHub organization or user
+    Import(ImportArgs),
+    /// List configured repositories
+    List {
+        /// Print machine-readable JSON
+        #[arg(long)]
+        json: bool,
+    },
+    /// Add a repository
+    Add(AddArgs),
+    /// Remove a repository
+    Remove {
+        /// Repository as owner/name
+        repository: String,
+    },
+    /// Set or clear the display alias of a repository
+    SetAlias {
+        /// Repository as owner/name
+        repository: String,
+        /// New alias; omit to clear it
+        alias: Option<String>,
+    },
+    /// Validate the config file(s) without contacting GitHub
+    Validate,
+    /// Upgrade config files to the current schema version in place
+    Migrate,
+}
+
+#[derive(Args)]
+pub struct AddArgs {
+    /// Repository as owner/name
+    pub repository: String,
+
+    /// Friendly name shown in selection menus
+    #[arg(long)]
+    pub alias: Option<String>,
+
+    /// Group to put the repository in (repeatable)
+    #[arg(long = "group", value_name = "GROUP")]
+    pub groups: Vec<String>,
+
+    /// Tag to attach to the repository (repeatable)
+    #[arg(long = "tag", value_name = "TAG")]
+    pub tags: Vec<String>,
+}
+
+impl AddArgs {
+    /// Convert the parsed arguments into a [`ConfigAction::Add`].
+    pub fn into_action(self) -> anyhow::Result<ConfigAction> {
+        let (owner, name) = config::parse_repository_path(&self.repository)?;
+        Ok(ConfigAction::Add(config::Repository {
+            owner,
+            name,
+            alias: self.alias.filter(|a| !a.trim().is_empty()),
+            groups: self.groups,
+            tags: self.tags,
+            ..Default::default()
+        }))
+    }
+}
+
+#[derive(Args)]
+#[command(group(clap::ArgGroup::new("source").required(true).args(["org", "user"])))]
+pub struct ImportArgs {
+    /// Organization to list repositories for
+    #[arg(long)]
+    pub org: Option<String>,
+
+    /// User account to list repositories for
+    #[arg(long)]
+    pub user: Option<String>,
+
+    /// Only import repositories whose name matches this regular expression
+    #[arg(long, value_name = "REGEX")]
+    pub filter: Option<String>,
+
+    /// Only import repositories with this topic
+    #[arg(long)]
+    pub topic: Option<String>,
+
+    /// Skip archived repositories
+    #[arg(long)]
+    pub exclude_archived: bool,
+}
+
+impl ImportArgs {
+    /// Convert the parsed arguments into [`ImportOptions`], compiling the filter pattern.
+    pub fn into_options(self) -> anyhow::Result<ImportOptions> {
+        let (owner, kind) = match (self.org, self.user) {
+            (Some(org), _) => (org, OwnerKind::Organization),
+            (None, Some(user)) => (user, OwnerKind::User),
+            (None, None) => anyhow::bail!("Either --org or --user is required"),
+        };
+
+        let filter = self
+            .filter
+            .map(|pattern| regex::Regex::new(&pattern))
+            .transpose()
+            .map_err(|e| anyhow::anyhow!("Invalid --filter pattern: {}", e))?;
+
+        Ok(ImportOptions {
+            owner,
+            kind,
+            filter,
+            topic: self.topic,
+            exclude_archived: self.exclude_archived,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use clap::CommandFactory;
+
+    #[test]
+    fn test_cli_is_well_formed() {
+        Cli::command().debug_assert();
+    }
+
+    #[test]
+    fn test_selection_flags_follow_subcommands() {
+        let cli = Cli::try_parse_from([
+            "github-secrets",
+            "rotate",
+            "DB_PASSWORD",
+            "--group",
+            "prod",
+            "--repo",
+            "!my-org/legacy",
+            "--strict",
+        ])
+        .unwrap();
+        assert_eq!(cli.groups, vec!["prod"]);
+        assert_eq!(cli.repos, vec!["!my-org/legacy"]);
+        assert!(cli.strict);
+
+        let cli = Cli::try_parse_from(["github-secrets", "age", "--tag", "payments", "--last"]);
+        assert!(cli.is_err(), "--last conflicts with --tag");
+        let cli = Cli::try_parse_from(["github-secrets", "age", "--last"]).unwrap();
+        assert!(cli.last);
+    }
+
+    #[test]
+    fn test_config_add_keeps_its_own_group_flag() {
+        let cli = Cli::try_parse_from([
+            "github-secrets",
+            "config",
+            "add",
+            "my-org/api",
+            "--group",
+            "prod",
+        ])
+        .unwrap();
+        let Some(Commands::Config {
+            action: Some(ConfigCommands::Add(args)),
+        }) = cli.command
+        else {
+            panic!("expected config add");
+        };
+        assert_eq!(args.groups, vec!["prod"]);
+    }
 }
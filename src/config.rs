@@ -5,21 +5,40 @@
 
 use anyhow::{Context, Result};
 use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
 use std::fs;
+use std::path::{Path, PathBuf};
 
 /// Configuration file structure containing repository definitions.
-#[derive(Debug, Deserialize, Serialize)]
+#[derive(Debug, Deserialize, Serialize, Default)]
+#[serde(deny_unknown_fields)]
 pub struct Config {
+    /// Schema version (see [`crate::schema`]). Files without it are treated as
+    /// the current version, but are the only ones that may still use the
+    /// legacy `[repository]` table.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub version: Option<u32>,
+    /// Other config files whose repositories are merged into this one.
+    /// Relative paths are resolved against the directory of the including file.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub include: Vec<String>,
     /// List of repositories to manage secrets for.
     #[serde(default)]
     pub repositories: Vec<Repository>,
+    /// External secret providers by name (see [`crate::provider`]).
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub providers: crate::provider::Providers,
+    /// Maximum secret ages by name pattern, used by `age` (see [`crate::policy`]).
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub rotation_policy: Vec<crate::policy::RotationPolicy>,
     /// Single repository format (converted to repositories list during parsing).
     #[serde(default)]
     pub repository: Option<Repository>,
 }
 
 /// Repository configuration with owner, name, and optional display alias.
-#[derive(Debug, Deserialize, Clone, Serialize)]
+#[derive(Debug, Deserialize, Clone, Serialize, Default)]
+#[serde(deny_unknown_fields)]
 pub struct Repository {
     /// GitHub username or organization name.
     pub owner: String,
@@ -28,6 +47,135 @@ pub struct Repository {
     /// Optional friendly name for display in selection menus.
     #[serde(default)]
     pub alias: Option<String>,
+    /// Groups the repository belongs to (e.g. "prod").
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub groups: Vec<String>,
+    /// Free-form tags attached to the repository (e.g. "payments").
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub tags: Vec<String>,
+    /// How to handle secrets that already exist, e.g. `if-older-than=7d`
+    /// (see [`crate::overwrite::OverwritePolicy`]); `--overwrite` takes precedence.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub overwrite: Option<String>,
+    /// Canonical path of the config file this entry was loaded from,
+    /// when loaded through [`Config::load_layered`].
+    #[serde(skip)]
+    pub source: Option<PathBuf>,
+}
+
+/// Whether a [`RepositoryGroup`] was built from `groups` or `tags`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum GroupKind {
+    Group,
+    Tag,
+}
+
+/// A named set of repositories sharing a group or tag.
+#[derive(Debug, Clone, PartialEq)]
+pub struct RepositoryGroup {
+    /// Whether this came from `groups` or `tags`.
+    pub kind: GroupKind,
+    /// The group or tag name.
+    pub name: String,
+    /// Indices of member repositories, in config order.
+    pub members: Vec<usize>,
+}
+
+impl RepositoryGroup {
+    /// Get the label shown in selection menus, e.g. "Group: prod (3)".
+    pub fn display_name(&self) -> String {
+        let kind = match self.kind {
+            GroupKind::Group => "Group",
+            GroupKind::Tag => "Tag",
+        };
+        format!("{}: {} ({})", kind, self.name, self.members.len())
+    }
+}
+
+/// Collect all groups and tags used by the given repositories.
+///
+/// Groups come first, then tags, each sorted by name.
+pub fn collect_groups(repositories: &[Repository]) -> Vec<RepositoryGroup> {
+    let mut by_label: BTreeMap<(GroupKind, &str), Vec<usize>> = BTreeMap::new();
+    for (idx, repo) in repositories.iter().enumerate() {
+        for group in &repo.groups {
+            by_label
+                .entry((GroupKind::Group, group.as_str()))
+                .or_default()
+                .push(idx);
+        }
+        for tag in &repo.tags {
+            by_label
+                .entry((GroupKind::Tag, tag.as_str()))
+                .or_default()
+                .push(idx);
+        }
+    }
+
+    by_label
+        .into_iter()
+        .map(|((kind, name), mut members)| {
+            members.dedup();
+            RepositoryGroup {
+                kind,
+                name: name.to_string(),
+                members,
+            }
+        })
+        .collect()
+}
+
+/// Select the indices of repositories that belong to any of `groups` or carry any of `tags`.
+///
+/// # Errors
+///
+/// Returns an error if a requested group or tag matches no repository.
+pub fn select_by_labels(
+    repositories: &[Repository],
+    groups: &[String],
+    tags: &[String],
+) -> Result<Vec<usize>> {
+    for group in groups {
+        if !repositories.iter().any(|r| r.groups.contains(group)) {
+            anyhow::bail!("No repositories in group '{}'", group);
+        }
+    }
+    for tag in tags {
+        if !repositories.iter().any(|r| r.tags.contains(tag)) {
+            anyhow::bail!("No repositories tagged '{}'", tag);
+        }
+    }
+
+    Ok(repositories
+        .iter()
+        .enumerate()
+        .filter(|(_, r)| {
+            r.groups.iter().any(|g| groups.contains(g)) || r.tags.iter().any(|t| tags.contains(t))
+        })
+        .map(|(idx, _)| idx)
+        .collect())
+}
+
+/// Parse an `owner/name` repository argument, validating both parts.
+pub fn parse_repository_path(input: &str) -> Result<(String, String)> {
+    let (owner, name) = input
+        .trim()
+        .split_once('/')
+        .with_context(|| format!("Expected a repository as owner/name, got '{}'", input))?;
+    crate::validation::validate_repo_owner(owner)?;
+    crate::validation::validate_repo_name(name)?;
+    Ok((owner.trim().to_string(), name.trim().to_string()))
+}
+
+/// Parse a comma-separated list of groups or tags, trimming blanks and duplicates.
+pub fn parse_label_list(input: &str) -> Vec<String> {
+    let mut labels: Vec<String> = Vec::new();
+    for label in input.split(',').map(str::trim).filter(|l| !l.is_empty()) {
+        if !labels.iter().any(|l| l == label) {
+            labels.push(label.to_string());
+        }
+    }
+    labels
 }
 
 impl Repository {
@@ -54,15 +202,37 @@ impl Repository {
             self.path()
         }
     }
+
+    /// Parse the repository's `overwrite` policy, if set.
+    pub fn overwrite_policy(&self) -> Result<Option<crate::overwrite::OverwritePolicy>> {
+        self.overwrite.as_deref().map(str::parse).transpose()
+    }
 }
 
 impl Config {
     /// Load configuration from a TOML file.
     /// Converts single repository format to repositories list if needed.
     pub fn from_file(path: &str) -> Result<Self> {
+        let config = Self::load_editable(path)?;
+
+        if config.repositories.is_empty() {
+            anyhow::bail!("No repositories found in config file");
+        }
+
+        Ok(config)
+    }
+
+    /// Load configuration for editing.
+    ///
+    /// Same as [`Config::from_file`], except that a config without any
+    /// repositories is accepted so commands like `config import` can fill it.
+    pub fn load_editable(path: &str) -> Result<Self> {
         let content = fs::read_to_string(path)
             .with_context(|| format!("Failed to read config file: {}", path))?;
         let mut config: Config = toml::from_str(&content).context("Failed to parse config file")?;
+        crate::schema::check_version(config.version)?;
+        crate::schema::check_duplicates(&content)?;
+        crate::schema::check_legacy_table(&content)?;
 
         // Convert single repository to repositories list if present
         if config.repositories.is_empty() {
@@ -71,21 +241,175 @@ impl Config {
             }
         }
 
-        if config.repositories.is_empty() {
-            anyhow::bail!("No repositories found in config file");
-        }
-
         // Validate all repositories
         for (idx, repo) in config.repositories.iter().enumerate() {
             crate::validation::validate_repo_owner(&repo.owner)
                 .with_context(|| format!("Invalid owner in repository #{}", idx + 1))?;
             crate::validation::validate_repo_name(&repo.name)
                 .with_context(|| format!("Invalid repository name in repository #{}", idx + 1))?;
+            for label in repo.groups.iter().chain(&repo.tags) {
+                crate::validation::validate_label(label)
+                    .with_context(|| format!("Invalid group or tag in repository #{}", idx + 1))?;
+            }
+            repo.overwrite_policy()
+                .with_context(|| format!("Invalid overwrite in repository #{}", idx + 1))?;
+        }
+
+        for policy in &config.rotation_policy {
+            policy.compile()?;
         }
 
         Ok(config)
     }
 
+    /// Load and merge several config files, following their `include` lists.
+    ///
+    /// `layers` are given highest priority first. Within a file, its own entries
+    /// come before the entries of the files it includes. When the same
+    /// `owner/name` appears more than once, the first definition wins, so a
+    /// higher-priority layer overrides alias, groups and tags from lower ones.
+    /// Each repository records the file it came from in [`Repository::source`].
+    /// `[providers.<name>]` tables are merged the same way, by provider name;
+    /// those from the current directory's config and its includes are marked
+    /// with [`ProviderConfig::untrusted_source`](crate::provider::ProviderConfig::untrusted_source).
+    /// `[[rotation_policy]]` entries are concatenated in the same order, so
+    /// policies from higher-priority files are checked first.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if any file or include fails to load, or if the merged
+    /// configuration has no repositories.
+    pub fn load_layered(layers: &[PathBuf]) -> Result<Self> {
+        let mut merged = Config::default();
+        let mut visited = Vec::new();
+        let untrusted = crate::paths::untrusted_config_file();
+
+        for layer in layers {
+            let shared = untrusted.is_some() && layer.canonicalize().ok() == untrusted;
+            merged.merge_file(layer, shared, &mut visited)?;
+        }
+
+        if merged.repositories.is_empty() {
+            anyhow::bail!("No repositories found in config file");
+        }
+
+        Ok(merged)
+    }
+
+    /// Merge one file and, recursively, its includes. Files already merged are skipped,
+    /// which also stops include cycles. `untrusted` marks the providers of the
+    /// current directory's config and everything it includes.
+    fn merge_file(
+        &mut self,
+        path: &Path,
+        untrusted: bool,
+        visited: &mut Vec<PathBuf>,
+    ) -> Result<()> {
+        let canonical = path
+            .canonicalize()
+            .with_context(|| format!("Failed to read config file: {}", path.display()))?;
+        if visited.contains(&canonical) {
+            return Ok(());
+        }
+        visited.push(canonical.clone());
+
+        let path_str = path.to_str().context("Config path is not valid UTF-8")?;
+        let mut config = Self::load_editable(path_str)
+            .with_context(|| format!("Failed to load config from {}", path.display()))?;
+
+        for repo in &mut config.repositories {
+            repo.source = Some(canonical.clone());
+        }
+        self.add_repositories(config.repositories);
+        for (name, mut provider) in config.providers {
+            provider.untrusted_source = untrusted.then(|| canonical.clone());
+            self.providers.entry(name).or_insert(provider);
+        }
+        self.rotation_policy.extend(config.rotation_policy);
+
+        let base = path.parent().unwrap_or_else(|| Path::new("."));
+        for include in &config.include {
+            self.merge_file(&base.join(include), untrusted, visited)
+                .with_context(|| {
+                    format!("Failed to include '{}' from {}", include, path.display())
+                })?;
+        }
+
+        Ok(())
+    }
+
+    /// Append repositories, skipping any whose `owner/name` is already configured.
+    ///
+    /// # Returns
+    ///
+    /// Returns the number of repositories actually added.
+    pub fn add_repositories(
+        &mut self,
+        repositories: impl IntoIterator<Item = Repository>,
+    ) -> usize {
+        let mut added = 0;
+        for repo in repositories {
+            if !self.contains(&repo.path()) {
+                self.repositories.push(repo);
+                added += 1;
+            }
+        }
+        added
+    }
+
+    /// Add a single repository after validating it.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the repository is invalid or already configured.
+    pub fn add_repository(&mut self, repository: Repository) -> Result<()> {
+        crate::validation::validate_repo_owner(&repository.owner)?;
+        crate::validation::validate_repo_name(&repository.name)?;
+        for label in repository.groups.iter().chain(&repository.tags) {
+            crate::validation::validate_label(label)?;
+        }
+        if self.contains(&repository.path()) {
+            anyhow::bail!("Repository '{}' is already configured", repository.path());
+        }
+        self.repositories.push(repository);
+        Ok(())
+    }
+
+    /// Remove the repository with the given `owner/name` path.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if no such repository is configured.
+    pub fn remove_repository(&mut self, path: &str) -> Result<Repository> {
+        let idx = self.index_of(path)?;
+        Ok(self.repositories.remove(idx))
+    }
+
+    /// Set or clear (`None`) the alias of the repository with the given `owner/name` path.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if no such repository is configured.
+    pub fn set_alias(&mut self, path: &str, alias: Option<String>) -> Result<()> {
+        let idx = self.index_of(path)?;
+        self.repositories[idx].alias = alias.filter(|a| !a.trim().is_empty());
+        Ok(())
+    }
+
+    fn index_of(&self, path: &str) -> Result<usize> {
+        self.repositories
+            .iter()
+            .position(|r| r.path().eq_ignore_ascii_case(path.trim()))
+            .with_context(|| format!("Repository '{}' is not configured", path.trim()))
+    }
+
+    /// Check whether a repository with the given `owner/name` path is configured.
+    pub fn contains(&self, path: &str) -> bool {
+        self.repositories
+            .iter()
+            .any(|r| r.path().eq_ignore_ascii_case(path))
+    }
+
     /// Get a reference to the list of repositories.
     ///
     /// # Returns
@@ -108,6 +432,7 @@ mod tests {
             owner: "test-owner".to_string(),
             name: "test-repo".to_string(),
             alias: None,
+            ..Default::default()
         };
         assert_eq!(repo.path(), "test-owner/test-repo");
     }
@@ -118,6 +443,7 @@ mod tests {
             owner: "test-owner".to_string(),
             name: "test-repo".to_string(),
             alias: None,
+            ..Default::default()
         };
         assert_eq!(repo.display_name(), "test-owner/test-repo");
     }
@@ -128,6 +454,7 @@ mod tests {
             owner: "test-owner".to_string(),
             name: "test-repo".to_string(),
             alias: Some("My Repo".to_string()),
+            ..Default::default()
         };
         assert_eq!(repo.display_name(), "My Repo (test-owner/test-repo)");
     }
@@ -177,7 +504,7 @@ name = "repo1"
     }
 
     #[test]
-    fn test_config_from_file_single_repository_ignored_when_repositories_exist() {
+    fn test_config_from_file_single_repository_rejected_when_repositories_exist() {
         let temp_dir = TempDir::new().unwrap();
         let config_path = temp_dir.path().join("config.toml");
 
@@ -192,11 +519,9 @@ name = "repo2"
 "#;
         fs::write(&config_path, config_content).unwrap();
 
-        let config = Config::from_file(config_path.to_str().unwrap()).unwrap();
-        // Single repository should be ignored when repositories list exists
-        assert_eq!(config.repositories.len(), 1);
-        assert_eq!(config.repositories[0].owner, "owner2");
-        assert_eq!(config.repositories[0].name, "repo2");
+        let err = Config::from_file(config_path.to_str().unwrap()).unwrap_err();
+        // Single repository would be ignored when repositories list exists
+        assert!(err.to_string().contains("can't be combined"), "{}", err);
     }
 
     #[test]
@@ -226,14 +551,17 @@ name = "repo2"
                     owner: "owner1".to_string(),
                     name: "repo1".to_string(),
                     alias: None,
+                    ..Default::default()
                 },
                 Repository {
                     owner: "owner2".to_string(),
                     name: "repo2".to_string(),
                     alias: None,
+                    ..Default::default()
                 },
             ],
             repository: None,
+            ..Default::default()
         };
 
         let repos = config.get_repositories();
@@ -0,0 +1,295 @@
+//! Format-preserving config file saves.
+//!
+//! Saving a [`Config`] re-renders only the keys this tool owns (`include` and
+//! the repository entries), so comments, ordering, formatting and unknown keys
+//! in a hand-written `config.toml` survive a dashboard session. Files are
+//! written atomically through a temporary file, keeping a `.bak` copy of the
+//! previous version.
+
+use anyhow::{Context, Result};
+use std::fs;
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use toml_edit::{Array, ArrayOfTables, DocumentMut, Item, Table, value};
+
+use crate::config::{Config, Repository};
+use crate::constants;
+
+/// Key of the repositories array of tables.
+const REPOSITORIES_KEY: &str = "repositories";
+/// Key of the legacy single-repository table.
+const LEGACY_REPOSITORY_KEY: &str = "repository";
+/// Key of the include list.
+const INCLUDE_KEY: &str = "include";
+
+/// Render `config` as TOML, applying it on top of `original` when given.
+///
+/// Existing repository tables are matched by `owner/name` and updated in place;
+/// removed repositories are dropped and new ones appended. A legacy
+/// `[repository]` table is kept as-is while the config still holds exactly one
+/// repository, and only converted to `[[repositories]]` once more are added.
+///
+/// # Errors
+///
+/// Returns an error if `original` is not valid TOML.
+pub fn render(original: Option<&str>, config: &Config) -> Result<String> {
+    let Some(original) = original.filter(|s| !s.trim().is_empty()) else {
+        let body = toml::to_string(config).context("Failed to serialize config")?;
+        return Ok(match config.version {
+            Some(_) => body,
+            None => format!(
+                "version = {}\n\n{}",
+                constants::schema::CURRENT_VERSION,
+                body
+            ),
+        });
+    };
+
+    let mut doc: DocumentMut = original
+        .parse()
+        .context("Failed to parse existing config file")?;
+
+    apply_include(&mut doc, &config.include);
+
+    let legacy_only = !doc.contains_key(REPOSITORIES_KEY)
+        && doc
+            .get(LEGACY_REPOSITORY_KEY)
+            .is_some_and(|item| item.is_table());
+
+    if legacy_only && config.repositories.len() == 1 {
+        if let Some(table) = doc
+            .get_mut(LEGACY_REPOSITORY_KEY)
+            .and_then(Item::as_table_mut)
+        {
+            update_table(table, &config.repositories[0]);
+        }
+        return Ok(doc.to_string());
+    }
+
+    let mut old_tables: Vec<Table> = Vec::new();
+    if legacy_only && let Some(Item::Table(table)) = doc.remove(LEGACY_REPOSITORY_KEY) {
+        old_tables.push(table);
+    }
+    if let Some(Item::ArrayOfTables(tables)) = doc.remove(REPOSITORIES_KEY) {
+        old_tables.extend(tables);
+    }
+
+    let header = take_header(&mut old_tables);
+
+    let mut positions: Vec<usize> = old_tables.iter().filter_map(Table::position).collect();
+    positions.sort_unstable();
+    let mut positions = positions.into_iter();
+
+    let mut tables = ArrayOfTables::new();
+    for repo in &config.repositories {
+        let existing = old_tables.iter().position(|table| {
+            table_path(table).is_some_and(|p| p.eq_ignore_ascii_case(&repo.path()))
+        });
+        let mut table = match existing {
+            Some(idx) => old_tables.remove(idx),
+            None => Table::new(),
+        };
+        update_table(&mut table, repo);
+        match positions.next() {
+            Some(pos) => table.set_position(pos),
+            None => table.set_position(usize::MAX),
+        }
+        tables.push(table);
+    }
+
+    if let Some(first) = tables.get_mut(0) {
+        let prefix = first
+            .decor()
+            .prefix()
+            .and_then(|p| p.as_str())
+            .unwrap_or_default()
+            .to_string();
+        first
+            .decor_mut()
+            .set_prefix(format!("{}{}", header, prefix));
+        doc.insert(REPOSITORIES_KEY, Item::ArrayOfTables(tables));
+    }
+
+    Ok(doc.to_string())
+}
+
+/// Write `contents` to `path` atomically, keeping the previous file as `<path>.bak`.
+///
+/// See [`replace_file`] for how the file is written.
+pub fn write_atomic(path: &Path, contents: &str) -> Result<()> {
+    if path.exists() {
+        fs::copy(path, backup_path(path))
+            .with_context(|| format!("Failed to back up {}", path.display()))?;
+    }
+    replace_file(path, contents)
+}
+
+/// Write `contents` to `path` atomically, without a backup.
+///
+/// The data is written to a temporary file in the same directory and renamed
+/// over `path`, so a crash never leaves a half-written file behind. The new
+/// file keeps the permissions of the one it replaces. Meant for state files
+/// the tool rewrites on its own, where a `.bak` copy would only be clutter.
+pub fn replace_file(path: &Path, contents: &str) -> Result<()> {
+    replace(path, contents, false)
+}
+
+/// Same as [`replace_file`], but on Unix the file is only readable by the
+/// current user from the moment it is created.
+pub fn replace_private_file(path: &Path, contents: &str) -> Result<()> {
+    replace(path, contents, true)
+}
+
+fn replace(path: &Path, contents: &str, private: bool) -> Result<()> {
+    let parent = match path.parent() {
+        Some(parent) if !parent.as_os_str().is_empty() => parent.to_path_buf(),
+        _ => PathBuf::from("."),
+    };
+    fs::create_dir_all(&parent)
+        .with_context(|| format!("Failed to create directory {}", parent.display()))?;
+
+    let file_name = path
+        .file_name()
+        .context("Path has no file name")?
+        .to_string_lossy();
+    let temp_path = parent.join(format!(".{}.tmp-{}", file_name, std::process::id()));
+    let permissions = fs::metadata(path).ok().map(|m| m.permissions());
+
+    let result = (|| -> Result<()> {
+        let mut file = create_temp(&temp_path, private)?;
+        if !private && let Some(permissions) = permissions {
+            file.set_permissions(permissions)?;
+        }
+        file.write_all(contents.as_bytes())?;
+        file.sync_all()?;
+        fs::rename(&temp_path, path)?;
+        Ok(())
+    })();
+
+    if result.is_err() {
+        let _ = fs::remove_file(&temp_path);
+    }
+    result.with_context(|| format!("Failed to write {}", path.display()))
+}
+
+/// Create a fresh temporary file, only readable by the current user if `private`.
+fn create_temp(path: &Path, private: bool) -> std::io::Result<fs::File> {
+    // A leftover from a crashed run would keep its old permissions.
+    let _ = fs::remove_file(path);
+    let mut options = fs::OpenOptions::new();
+    options.write(true).create_new(true);
+    #[cfg(unix)]
+    if private {
+        use std::os::unix::fs::OpenOptionsExt;
+        options.mode(0o600);
+    }
+    #[cfg(not(unix))]
+    let _ = private;
+    options.open(path)
+}
+
+/// Get the backup location for a config file (`config.toml` → `config.toml.bak`).
+pub fn backup_path(path: &Path) -> PathBuf {
+    let mut backup = path.as_os_str().to_owned();
+    backup.push(".bak");
+    PathBuf::from(backup)
+}
+
+/// Render `config` over the current contents of `path` and write it atomically.
+pub fn save(path: &Path, config: &Config) -> Result<()> {
+    let original = if path.exists() {
+        Some(
+            fs::read_to_string(path)
+                .with_context(|| format!("Failed to read config file: {}", path.display()))?,
+        )
+    } else {
+        None
+    };
+
+    let contents = render(original.as_deref(), config)?;
+    write_atomic(path, &contents)
+}
+
+fn apply_include(doc: &mut DocumentMut, include: &[String]) {
+    if include.is_empty() {
+        doc.remove(INCLUDE_KEY);
+    } else if string_list(doc.get(INCLUDE_KEY)).as_deref() != Some(include) {
+        doc.insert(
+            INCLUDE_KEY,
+            value(Array::from_iter(include.iter().cloned())),
+        );
+    }
+}
+
+/// Detach the file header from the first repository table.
+///
+/// Comments at the top of the file are stored in front of the first table
+/// header. The part up to the last blank line is treated as a file header, so
+/// it stays at the top even if that repository is removed or moved.
+fn take_header(tables: &mut [Table]) -> String {
+    let Some(first) = tables
+        .iter_mut()
+        .min_by_key(|t| t.position().unwrap_or(usize::MAX))
+    else {
+        return String::new();
+    };
+    let prefix = first
+        .decor()
+        .prefix()
+        .and_then(|p| p.as_str())
+        .unwrap_or_default()
+        .to_string();
+    let Some(split) = prefix.rfind("\n\n") else {
+        return String::new();
+    };
+
+    let (header, rest) = prefix.split_at(split + 2);
+    first.decor_mut().set_prefix(rest);
+    header.to_string()
+}
+
+/// Get the `owner/name` path of a repository table.
+fn table_path(table: &Table) -> Option<String> {
+    let owner = table.get("owner")?.as_str()?;
+    let name = table.get("name")?.as_str()?;
+    Some(format!("{}/{}", owner, name))
+}
+
+/// Read a string array, returning `None` if the item is missing or not an array of strings.
+fn string_list(item: Option<&Item>) -> Option<Vec<String>> {
+    item?
+        .as_array()?
+        .iter()
+        .map(|v| v.as_str().map(str::to_string))
+        .collect()
+}
+
+/// Update a repository table, touching only keys whose value changed.
+fn update_table(table: &mut Table, repo: &Repository) {
+    set_str(table, "owner", Some(&repo.owner));
+    set_str(table, "name", Some(&repo.name));
+    set_str(table, "alias", repo.alias.as_deref());
+    set_list(table, "groups", &repo.groups);
+    set_list(table, "tags", &repo.tags);
+    set_str(table, "overwrite", repo.overwrite.as_deref());
+}
+
+fn set_str(table: &mut Table, key: &str, new: Option<&str>) {
+    match new {
+        Some(new) if table.get(key).and_then(Item::as_str) != Some(new) => {
+            table.insert(key, value(new));
+        }
+        Some(_) => {}
+        None => {
+            table.remove(key);
+        }
+    }
+}
+
+fn set_list(table: &mut Table, key: &str, new: &[String]) {
+    if new.is_empty() {
+        table.remove(key);
+    } else if string_list(table.get(key)).as_deref() != Some(new) {
+        table.insert(key, value(Array::from_iter(new.iter().cloned())));
+    }
+}
@@ -13,6 +13,18 @@ pub mod api {
 
     /// Path template for updating a specific secret.
     pub const SECRET_PATH_TEMPLATE: &str = "/repos/{owner}/{repo}/actions/secrets/{secret_name}";
+
+    /// Path template for listing an organization's repositories.
+    pub const ORG_REPOS_PATH_TEMPLATE: &str = "/orgs/{owner}/repos";
+
+    /// Path template for listing a user's repositories.
+    pub const USER_REPOS_PATH_TEMPLATE: &str = "/users/{owner}/repos";
+
+    /// Path for listing the authenticated user's repositories, private ones included.
+    pub const AUTHENTICATED_USER_REPOS_PATH: &str = "/user/repos";
+
+    /// Page size used for paginated list endpoints (GitHub maximum).
+    pub const PER_PAGE: u32 = 100;
 }
 
 /// Secret validation constants.
@@ -23,9 +35,12 @@ pub mod validation {
     /// Minimum length for a secret key name.
     pub const MIN_SECRET_KEY_LENGTH: usize = 1;
 
-    /// Valid characters for secret key names (alphanumeric, underscore, hyphen).
-    /// GitHub allows: letters, numbers, underscores, and hyphens.
-    pub const VALID_SECRET_KEY_PATTERN: &str = r"^[a-zA-Z0-9_-]+$";
+    /// Valid characters for secret key names.
+    /// GitHub allows letters, numbers and underscores, and names can't start with a number.
+    pub const VALID_SECRET_KEY_PATTERN: &str = r"^[a-zA-Z_][a-zA-Z0-9_]*$";
+
+    /// Prefix reserved by GitHub; secret names can't start with it (case-insensitive).
+    pub const RESERVED_SECRET_KEY_PREFIX: &str = "GITHUB_";
 }
 
 /// Rate limiting constants for GitHub API.
@@ -54,3 +69,18 @@ pub mod repo {
     /// Minimum length for repository name.
     pub const MIN_REPO_NAME_LENGTH: usize = 1;
 }
+
+/// Thresholds used by the `doctor` command.
+pub mod doctor {
+    /// Clock skew (in seconds) above which a warning is reported.
+    pub const CLOCK_SKEW_WARN_SECS: i64 = 30;
+
+    /// Clock skew (in seconds) above which the check fails.
+    pub const CLOCK_SKEW_FAIL_SECS: i64 = 300;
+}
+
+/// Config file schema constants.
+pub mod schema {
+    /// Current config schema version, written as `version = N` in config.toml.
+    pub const CURRENT_VERSION: u32 = 1;
+}
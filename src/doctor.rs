@@ -0,0 +1,462 @@
+//! Setup diagnostics for the `doctor` command.
+//!
+//! Each check produces a [`CheckResult`] with a pass/warn/fail status and,
+//! when something is wrong, a hint describing how to fix it.
+
+use anyhow::Result;
+use chrono::{DateTime, Utc};
+use colored::*;
+use std::env;
+use std::path::{Path, PathBuf};
+
+use crate::config;
+use crate::constants;
+use crate::error;
+use crate::github;
+use crate::paths;
+use crate::validation;
+
+/// Outcome of a single diagnostic check.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CheckStatus {
+    Pass,
+    Warn,
+    Fail,
+}
+
+/// Result of a single diagnostic check.
+#[derive(Debug, Clone)]
+pub struct CheckResult {
+    /// Short name of the check (e.g. "Config file").
+    pub name: String,
+    /// Pass, warn or fail.
+    pub status: CheckStatus,
+    /// One-line summary of what was found.
+    pub summary: String,
+    /// Additional lines shown under the summary.
+    pub details: Vec<String>,
+    /// Suggested fix, shown for warnings and failures.
+    pub hint: Option<String>,
+}
+
+impl CheckResult {
+    pub fn pass(name: impl Into<String>, summary: impl Into<String>) -> Self {
+        Self {
+            name: name.into(),
+            status: CheckStatus::Pass,
+            summary: summary.into(),
+            details: Vec::new(),
+            hint: None,
+        }
+    }
+
+    pub fn warn(
+        name: impl Into<String>,
+        summary: impl Into<String>,
+        hint: impl Into<String>,
+    ) -> Self {
+        Self {
+            name: name.into(),
+            status: CheckStatus::Warn,
+            summary: summary.into(),
+            details: Vec::new(),
+            hint: Some(hint.into()),
+        }
+    }
+
+    pub fn fail(
+        name: impl Into<String>,
+        summary: impl Into<String>,
+        hint: impl Into<String>,
+    ) -> Self {
+        Self {
+            name: name.into(),
+            status: CheckStatus::Fail,
+            summary: summary.into(),
+            details: Vec::new(),
+            hint: Some(hint.into()),
+        }
+    }
+
+    /// Attach detail lines to the result.
+    pub fn with_details(mut self, details: Vec<String>) -> Self {
+        self.details = details;
+        self
+    }
+}
+
+/// Report which config file locations were searched and which ones are merged.
+///
+/// # Arguments
+///
+/// * `candidates` - Locations in priority order, as returned by [`paths::config_layer_candidates`]
+/// * `config_path_override` - Value of `CONFIG_PATH`, if set
+pub fn check_config_paths(
+    candidates: &[PathBuf],
+    config_path_override: Option<&Path>,
+) -> CheckResult {
+    let layers = paths::select_config_layers(candidates, config_path_override);
+
+    let details = candidates
+        .iter()
+        .map(|path| {
+            let state = if layers.contains(path) {
+                "using"
+            } else if path.exists() {
+                "found, not merged"
+            } else {
+                "missing"
+            };
+            format!("{} ({})", path.display(), state)
+        })
+        .collect();
+
+    let result = match (layers.first(), config_path_override) {
+        (_, Some(over)) if !over.exists() => CheckResult::warn(
+            "Config file",
+            format!("CONFIG_PATH points to a missing file: {}", over.display()),
+            "Fix or unset CONFIG_PATH so the intended config file is used",
+        ),
+        (Some(_), _) => CheckResult::pass(
+            "Config file",
+            layers
+                .iter()
+                .map(|p| p.display().to_string())
+                .collect::<Vec<_>>()
+                .join(", "),
+        ),
+        (None, _) => CheckResult::fail(
+            "Config file",
+            "No config file found",
+            format!(
+                "Run `github-secrets config` to create one at {}, or set CONFIG_PATH",
+                paths::get_config_creation_path().display()
+            ),
+        ),
+    };
+
+    result.with_details(details)
+}
+
+/// Report which `.env` files were searched and which were loaded.
+pub fn check_env_files(candidates: &[PathBuf], loaded: &[PathBuf]) -> CheckResult {
+    let details = candidates
+        .iter()
+        .map(|path| {
+            let state = if loaded.contains(path) {
+                "loaded"
+            } else if path.exists() {
+                "found, not loaded"
+            } else {
+                "missing"
+            };
+            format!("{} ({})", path.display(), state)
+        })
+        .collect();
+
+    let result = if loaded.is_empty() {
+        CheckResult::warn(
+            ".env file",
+            "No .env file loaded",
+            "Create ~/.config/github-secrets/.env with GITHUB_TOKEN=..., or export GITHUB_TOKEN",
+        )
+    } else {
+        let names: Vec<String> = loaded.iter().map(|p| p.display().to_string()).collect();
+        CheckResult::pass(".env file", names.join(", "))
+    };
+
+    result.with_details(details)
+}
+
+/// Load, merge and validate the config layers using [`config::Config::load_layered`].
+///
+/// Each repository is listed with the file it came from.
+/// Returns the check result and, if validation passed, the merged config.
+pub fn check_config(layers: &[PathBuf]) -> (CheckResult, Option<config::Config>) {
+    if layers.is_empty() {
+        return (
+            CheckResult::fail(
+                "Config validation",
+                "Skipped: config file does not exist",
+                "Create a config file first (see the Config file check)",
+            ),
+            None,
+        );
+    }
+
+    match config::Config::load_layered(layers) {
+        Ok(cfg) => {
+            let details = cfg
+                .get_repositories()
+                .iter()
+                .map(|r| match &r.source {
+                    Some(source) => format!("{} (from {})", r.display_name(), source.display()),
+                    None => r.display_name(),
+                })
+                .collect();
+            (
+                CheckResult::pass(
+                    "Config validation",
+                    format!(
+                        "{} repository(ies) configured",
+                        cfg.get_repositories().len()
+                    ),
+                )
+                .with_details(details),
+                Some(cfg),
+            )
+        }
+        Err(e) => (
+            CheckResult::fail(
+                "Config validation",
+                error::format_error_chain(&e),
+                "Fix the reported entry or edit it with `github-secrets config`",
+            ),
+            None,
+        ),
+    }
+}
+
+/// Check that `GITHUB_TOKEN` is set and looks like a GitHub token.
+pub fn check_token_format(token: Option<&str>) -> CheckResult {
+    let Some(token) = token else {
+        return CheckResult::fail(
+            "Token format",
+            "GITHUB_TOKEN is not set",
+            "Add GITHUB_TOKEN=... to a .env file or export it in your shell",
+        );
+    };
+
+    if let Err(e) = validation::validate_token(token) {
+        return CheckResult::fail(
+            "Token format",
+            e.to_string(),
+            "Copy the full token from https://github.com/settings/tokens",
+        );
+    }
+
+    let trimmed = token.trim();
+    if trimmed != token {
+        return CheckResult::warn(
+            "Token format",
+            "GITHUB_TOKEN has leading or trailing whitespace",
+            "Remove spaces or quotes around the token in your .env file",
+        );
+    }
+
+    let kind = if trimmed.starts_with("github_pat_") {
+        Some("fine-grained personal access token")
+    } else if trimmed.starts_with("ghp_") {
+        Some("classic personal access token")
+    } else if trimmed.starts_with("gho_") {
+        Some("OAuth token")
+    } else if trimmed.starts_with("ghu_") || trimmed.starts_with("ghs_") {
+        Some("GitHub App token")
+    } else {
+        None
+    };
+
+    match kind {
+        Some(kind) => CheckResult::pass("Token format", format!("Looks like a {}", kind)),
+        None => CheckResult::warn(
+            "Token format",
+            "Unrecognized token prefix",
+            "GitHub tokens usually start with ghp_, github_pat_, gho_, ghu_ or ghs_",
+        ),
+    }
+}
+
+/// Report the identity behind the token and whether it has the scopes needed.
+pub fn check_token_identity(info: &Result<github::TokenInfo>) -> CheckResult {
+    match info {
+        Ok(info) => {
+            let has_repo_scope = info
+                .scopes
+                .iter()
+                .any(|s| s == "repo" || s == "public_repo");
+            if !info.scopes.is_empty() && !has_repo_scope {
+                CheckResult::warn(
+                    "Token identity",
+                    format!(
+                        "Authenticated as {}, but without the repo scope",
+                        info.login
+                    ),
+                    "Grant the `repo` (or `public_repo`) scope to the token",
+                )
+                .with_details(vec![format!("scopes: {}", info.scopes.join(", "))])
+            } else {
+                let details = if info.scopes.is_empty() {
+                    Vec::new()
+                } else {
+                    vec![format!("scopes: {}", info.scopes.join(", "))]
+                };
+                CheckResult::pass("Token identity", format!("Authenticated as {}", info.login))
+                    .with_details(details)
+            }
+        }
+        Err(e) => CheckResult::fail(
+            "Token identity",
+            error::format_error_chain(e),
+            "Check that the token has not expired or been revoked",
+        ),
+    }
+}
+
+/// Report whether the secrets API of a repository is reachable with the token.
+pub fn check_repository_access(
+    repository: &config::Repository,
+    access: &Result<()>,
+) -> CheckResult {
+    let name = format!("Repository {}", repository.display_name());
+    match access {
+        Ok(()) => CheckResult::pass(name, "Secrets API reachable"),
+        Err(e) => CheckResult::fail(
+            name,
+            error::format_error_chain(e),
+            "Make sure the repository exists and the token has write access to its secrets",
+        ),
+    }
+}
+
+/// Compare the local clock against GitHub's server time.
+pub fn check_clock_skew(
+    server_time: Option<DateTime<Utc>>,
+    local_time: DateTime<Utc>,
+) -> CheckResult {
+    let Some(server_time) = server_time else {
+        return CheckResult::warn(
+            "Clock skew",
+            "Could not determine GitHub server time",
+            "Check your network connection and run doctor again",
+        );
+    };
+
+    let skew = local_time
+        .signed_duration_since(server_time)
+        .num_seconds()
+        .abs();
+
+    if skew > constants::doctor::CLOCK_SKEW_FAIL_SECS {
+        CheckResult::fail(
+            "Clock skew",
+            format!("Local clock differs from GitHub by {}s", skew),
+            "Synchronize your system clock (e.g. enable NTP)",
+        )
+    } else if skew > constants::doctor::CLOCK_SKEW_WARN_SECS {
+        CheckResult::warn(
+            "Clock skew",
+            format!("Local clock differs from GitHub by {}s", skew),
+            "Synchronize your system clock (e.g. enable NTP)",
+        )
+    } else {
+        CheckResult::pass("Clock skew", format!("Within {}s of GitHub", skew))
+    }
+}
+
+/// Run every check against the real environment, config files and GitHub API.
+pub async fn run_checks() -> Vec<CheckResult> {
+    let mut results = Vec::new();
+
+    let loaded = paths::load_env_file();
+    results.push(check_env_files(&paths::env_file_candidates(), &loaded));
+
+    let config_override = env::var("CONFIG_PATH").ok().map(PathBuf::from);
+    let candidates = paths::config_layer_candidates();
+    results.push(check_config_paths(&candidates, config_override.as_deref()));
+
+    let layers = paths::select_config_layers(&candidates, config_override.as_deref());
+    let (config_result, config) = check_config(&layers);
+    results.push(config_result);
+
+    let token = env::var("GITHUB_TOKEN").ok();
+    let token_result = check_token_format(token.as_deref());
+    let token_usable = token_result.status != CheckStatus::Fail;
+    results.push(token_result);
+
+    let Some(token) = token.filter(|_| token_usable) else {
+        results.push(CheckResult::warn(
+            "Connectivity",
+            "Skipped: no usable token",
+            "Fix the token problem above and run doctor again",
+        ));
+        return results;
+    };
+
+    // Identity lookup is not repository specific.
+    match github::GitHubClient::new(token.clone(), String::new(), String::new()) {
+        Ok(client) => {
+            let info = client.get_token_info().await;
+            let server_time = info.as_ref().ok().and_then(|i| i.server_time);
+            results.push(check_token_identity(&info));
+            results.push(check_clock_skew(server_time, Utc::now()));
+        }
+        Err(e) => results.push(CheckResult::fail(
+            "Token identity",
+            error::format_error_chain(&e),
+            "Report this as a bug",
+        )),
+    }
+
+    if let Some(config) = config {
+        for repo in config.get_repositories() {
+            let access = match github::GitHubClient::new(
+                token.clone(),
+                repo.owner.clone(),
+                repo.name.clone(),
+            ) {
+                Ok(client) => client.check_secrets_access().await,
+                Err(e) => Err(e),
+            };
+            results.push(check_repository_access(repo, &access));
+        }
+    }
+
+    results
+}
+
+/// Count results by status.
+///
+/// # Returns
+///
+/// Returns `(passed, warnings, failed)`.
+pub fn count_by_status(results: &[CheckResult]) -> (usize, usize, usize) {
+    let count = |status| results.iter().filter(|r| r.status == status).count();
+    (
+        count(CheckStatus::Pass),
+        count(CheckStatus::Warn),
+        count(CheckStatus::Fail),
+    )
+}
+
+/// Print the results as a checklist with fix hints.
+pub fn print_report(results: &[CheckResult]) {
+    println!("{}", "github-secrets doctor".bright_cyan().bold());
+    println!("{}", "=".repeat(60).bright_black());
+
+    for result in results {
+        let (symbol, name) = match result.status {
+            CheckStatus::Pass => ("✓".green(), result.name.green()),
+            CheckStatus::Warn => ("!".yellow(), result.name.yellow()),
+            CheckStatus::Fail => ("✗".red(), result.name.red()),
+        };
+        println!("{} {}: {}", symbol, name, result.summary);
+        for detail in &result.details {
+            println!("    {}", detail.bright_black());
+        }
+        if let Some(hint) = &result.hint {
+            println!("    {} {}", "→".cyan(), hint.cyan());
+        }
+    }
+
+    let (passed, warnings, failed) = count_by_status(results);
+    println!("{}", "=".repeat(60).bright_black());
+    println!(
+        "{} {}, {} {}, {} {}",
+        passed.to_string().bright_green(),
+        "passed".green(),
+        warnings.to_string().bright_yellow(),
+        "warning(s)".yellow(),
+        failed.to_string().bright_red(),
+        "failed".red()
+    );
+}
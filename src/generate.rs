@@ -0,0 +1,168 @@
+//! Random secret value generation.
+//!
+//! Values are drawn uniformly from a character set using the operating
+//! system's CSPRNG, as a replacement for `openssl rand -hex 32` and friends.
+
+use anyhow::{Context, Result};
+use rand::Rng;
+use rand::rngs::OsRng;
+use std::fmt;
+use std::io::Write;
+use std::path::Path;
+use std::str::FromStr;
+
+use crate::prompt::SecretPair;
+use crate::validation;
+
+/// Default length of generated values, in characters.
+pub const DEFAULT_LENGTH: usize = 32;
+
+/// Largest value that can be generated (GitHub's secret size limit).
+pub const MAX_LENGTH: usize = 48 * 1024;
+
+/// Characters a generated value is made of.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Charset {
+    /// `0-9a-f`
+    Hex,
+    /// `A-Za-z0-9+/`
+    Base64,
+    /// `A-Za-z0-9`
+    #[default]
+    Alphanumeric,
+    /// `A-Za-z0-9-_`, safe in URLs and file names
+    UrlSafe,
+}
+
+impl Charset {
+    /// All characters in the set.
+    pub fn alphabet(self) -> &'static [u8] {
+        match self {
+            Self::Hex => b"0123456789abcdef",
+            Self::Base64 => b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789+/",
+            Self::Alphanumeric => b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789",
+            Self::UrlSafe => b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789-_",
+        }
+    }
+}
+
+impl FromStr for Charset {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        match s.to_ascii_lowercase().as_str() {
+            "hex" => Ok(Self::Hex),
+            "base64" => Ok(Self::Base64),
+            "alphanumeric" | "alnum" => Ok(Self::Alphanumeric),
+            "urlsafe" => Ok(Self::UrlSafe),
+            _ => anyhow::bail!(
+                "Unknown charset '{}'; expected hex, base64, alphanumeric or urlsafe",
+                s
+            ),
+        }
+    }
+}
+
+impl fmt::Display for Charset {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            Self::Hex => "hex",
+            Self::Base64 => "base64",
+            Self::Alphanumeric => "alphanumeric",
+            Self::UrlSafe => "urlsafe",
+        })
+    }
+}
+
+/// A `--generate KEY:len[:charset]` request.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct GenerateSpec {
+    /// Secret name.
+    pub key: String,
+    /// Length in characters.
+    pub length: usize,
+    /// Characters to use.
+    pub charset: Charset,
+}
+
+impl FromStr for GenerateSpec {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        let mut parts = s.split(':');
+        let key = parts.next().unwrap_or_default().trim();
+        validation::validate_secret_key(key)
+            .with_context(|| format!("Invalid --generate '{}'", s))?;
+
+        let length = match parts.next() {
+            Some(len) => len
+                .trim()
+                .parse()
+                .with_context(|| format!("Invalid length in --generate '{}'", s))?,
+            None => DEFAULT_LENGTH,
+        };
+        if length == 0 || length > MAX_LENGTH {
+            anyhow::bail!(
+                "Length in --generate '{}' must be between 1 and {}",
+                s,
+                MAX_LENGTH
+            );
+        }
+
+        let charset = match parts.next() {
+            Some(charset) => charset.trim().parse()?,
+            None => Charset::default(),
+        };
+        if parts.next().is_some() {
+            anyhow::bail!("Invalid --generate '{}'; expected KEY:len[:charset]", s);
+        }
+
+        Ok(Self {
+            key: key.to_string(),
+            length,
+            charset,
+        })
+    }
+}
+
+/// Generate a random value of `length` characters from `charset`.
+pub fn generate(length: usize, charset: Charset) -> String {
+    let alphabet = charset.alphabet();
+    let mut rng = OsRng;
+    (0..length)
+        .map(|_| alphabet[rng.gen_range(0..alphabet.len())] as char)
+        .collect()
+}
+
+/// Generate a value for each spec.
+pub fn generate_all(specs: &[GenerateSpec]) -> Vec<SecretPair> {
+    specs
+        .iter()
+        .map(|spec| SecretPair {
+            key: spec.key.clone(),
+            value: generate(spec.length, spec.charset),
+        })
+        .collect()
+}
+
+/// Append generated values to `path` as `KEY=value` lines.
+///
+/// The file is created if needed; on Unix it is only readable by the current user.
+pub fn write_output(path: &Path, secrets: &[SecretPair]) -> Result<()> {
+    let mut options = std::fs::OpenOptions::new();
+    options.create(true).append(true);
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::OpenOptionsExt;
+        options.mode(0o600);
+    }
+
+    let mut file = options
+        .open(path)
+        .with_context(|| format!("Failed to open {}", path.display()))?;
+    for secret in secrets {
+        writeln!(file, "{}={}", secret.key, secret.value)
+            .with_context(|| format!("Failed to write {}", path.display()))?;
+    }
+    Ok(())
+}
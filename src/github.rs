@@ -5,6 +5,7 @@
 
 use anyhow::{Context, Result};
 use base64::{Engine, engine::general_purpose};
+use chrono::{DateTime, Utc};
 use octocrab::Octocrab;
 use serde::{Deserialize, Serialize};
 
@@ -17,14 +18,66 @@ struct PublicKey {
 /// Information about a GitHub repository secret.
 #[derive(Debug, Deserialize)]
 pub struct SecretInfo {
-    #[serde(skip)]
-    #[allow(dead_code)]
-    name: String,
+    /// Secret name as stored by GitHub (upper case).
+    #[serde(default)]
+    pub name: String,
     /// ISO 8601 timestamp of when the secret was last updated.
     #[serde(rename = "updated_at")]
     pub updated_at: Option<String>,
 }
 
+/// A secret as returned by GitHub's secrets listing endpoint (values are never returned).
+#[derive(Debug, Clone, Deserialize)]
+pub struct RemoteSecret {
+    /// Secret name.
+    pub name: String,
+    /// ISO 8601 timestamp of when the secret was created.
+    #[serde(default)]
+    pub created_at: Option<String>,
+    /// ISO 8601 timestamp of when the secret was last updated.
+    #[serde(default)]
+    pub updated_at: Option<String>,
+}
+
+/// Information about the token used to authenticate, as reported by `GET /user`.
+#[derive(Debug)]
+pub struct TokenInfo {
+    /// Login of the account that owns the token.
+    pub login: String,
+    /// OAuth scopes granted to a classic token (empty for fine-grained tokens).
+    pub scopes: Vec<String>,
+    /// Server time taken from the response `Date` header.
+    pub server_time: Option<DateTime<Utc>>,
+}
+
+/// Whether a repository owner is an organization or a user account.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum OwnerKind {
+    Organization,
+    User,
+}
+
+/// Owner of a repository returned by a listing endpoint.
+#[derive(Debug, Clone, Deserialize)]
+pub struct RemoteOwner {
+    pub login: String,
+}
+
+/// A repository as returned by GitHub's repository listing endpoints.
+#[derive(Debug, Clone, Deserialize)]
+pub struct RemoteRepository {
+    /// Repository name.
+    pub name: String,
+    /// Account that owns the repository.
+    pub owner: RemoteOwner,
+    /// Whether the repository is archived (read-only).
+    #[serde(default)]
+    pub archived: bool,
+    /// Repository topics.
+    #[serde(default)]
+    pub topics: Vec<String>,
+}
+
 /// Client for interacting with GitHub's Actions Secrets API.
 pub struct GitHubClient {
     octocrab: Octocrab,
@@ -150,6 +203,210 @@ impl GitHubClient {
         }
     }
 
+    /// Look up the account that owns the token, along with its scopes and the server time.
+    ///
+    /// # Returns
+    ///
+    /// Returns a [`TokenInfo`] describing the authenticated user.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the token is rejected or the API cannot be reached.
+    pub async fn get_token_info(&self) -> Result<TokenInfo> {
+        #[derive(Deserialize)]
+        struct User {
+            login: String,
+        }
+
+        let response = self
+            .octocrab
+            ._get("/user")
+            .await
+            .context("Failed to reach GitHub API")?;
+        let response = octocrab::map_github_error(response)
+            .await
+            .map_err(|e| anyhow::anyhow!("{}", crate::errors::GitHubError::from(e)))
+            .context("Failed to authenticate with GitHub")?;
+
+        let headers = response.headers();
+        let scopes = headers
+            .get("x-oauth-scopes")
+            .and_then(|v| v.to_str().ok())
+            .map(|v| {
+                v.split(',')
+                    .map(|s| s.trim().to_string())
+                    .filter(|s| !s.is_empty())
+                    .collect()
+            })
+            .unwrap_or_default();
+        let server_time = headers
+            .get("date")
+            .and_then(|v| v.to_str().ok())
+            .and_then(|v| DateTime::parse_from_rfc2822(v).ok())
+            .map(|dt| dt.with_timezone(&Utc));
+
+        let body = self
+            .octocrab
+            .body_to_string(response)
+            .await
+            .context("Failed to read GitHub API response")?;
+        let user: User = serde_json::from_str(&body).context("Failed to parse user response")?;
+
+        Ok(TokenInfo {
+            login: user.login,
+            scopes,
+            server_time,
+        })
+    }
+
+    /// List every repository belonging to this client's owner, following pagination.
+    ///
+    /// GitHub only lists a user's public repositories by name, so when the owner is
+    /// the authenticated user their own listing is used instead, private ones included.
+    ///
+    /// # Arguments
+    ///
+    /// * `kind` - Whether the owner is an organization or a user account
+    ///
+    /// # Returns
+    ///
+    /// Returns all repositories across all pages.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if any page cannot be fetched.
+    pub async fn list_repositories(&self, kind: OwnerKind) -> Result<Vec<RemoteRepository>> {
+        #[derive(Serialize)]
+        struct ListParams<'a> {
+            per_page: u32,
+            page: u32,
+            // GitHub rejects `type` together with `affiliation`
+            #[serde(rename = "type", skip_serializing_if = "Option::is_none")]
+            repo_type: Option<&'a str>,
+            #[serde(skip_serializing_if = "Option::is_none")]
+            affiliation: Option<&'a str>,
+        }
+
+        let (path, repo_type, affiliation) = match kind {
+            OwnerKind::Organization => (
+                crate::constants::api::ORG_REPOS_PATH_TEMPLATE.replace("{owner}", &self.owner),
+                Some("all"),
+                None,
+            ),
+            OwnerKind::User if self.is_authenticated_user().await? => (
+                crate::constants::api::AUTHENTICATED_USER_REPOS_PATH.to_string(),
+                None,
+                Some("owner"),
+            ),
+            OwnerKind::User => (
+                crate::constants::api::USER_REPOS_PATH_TEMPLATE.replace("{owner}", &self.owner),
+                Some("owner"),
+                None,
+            ),
+        };
+
+        let mut repositories = Vec::new();
+        let mut page = 1;
+        loop {
+            let params = ListParams {
+                per_page: crate::constants::api::PER_PAGE,
+                page,
+                repo_type,
+                affiliation,
+            };
+            let batch: Vec<RemoteRepository> = self
+                .octocrab
+                .get(&path, Some(&params))
+                .await
+                .map_err(|e| anyhow::anyhow!("{}", crate::errors::GitHubError::from(e)))
+                .with_context(|| format!("Failed to list repositories for {}", self.owner))?;
+
+            let is_last_page = batch.len() < crate::constants::api::PER_PAGE as usize;
+            repositories.extend(batch);
+            if is_last_page {
+                break;
+            }
+            page += 1;
+        }
+
+        Ok(repositories)
+    }
+
+    /// Check whether this client's owner is the user the token belongs to.
+    async fn is_authenticated_user(&self) -> Result<bool> {
+        #[derive(Deserialize)]
+        struct User {
+            login: String,
+        }
+
+        let user: User = self
+            .octocrab
+            .get("/user", None::<&()>)
+            .await
+            .map_err(|e| anyhow::anyhow!("{}", crate::errors::GitHubError::from(e)))
+            .context("Failed to look up the authenticated user")?;
+        Ok(user.login.eq_ignore_ascii_case(&self.owner))
+    }
+
+    /// List the repository's Actions secrets (names and timestamps), following pagination.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if any page cannot be fetched.
+    pub async fn list_secrets(&self) -> Result<Vec<RemoteSecret>> {
+        #[derive(Serialize)]
+        struct ListParams {
+            per_page: u32,
+            page: u32,
+        }
+
+        #[derive(Deserialize)]
+        struct SecretList {
+            total_count: usize,
+            secrets: Vec<RemoteSecret>,
+        }
+
+        let path = crate::constants::api::SECRETS_BASE_PATH
+            .replace("{owner}", &self.owner)
+            .replace("{repo}", &self.repo);
+
+        let mut secrets = Vec::new();
+        let mut page = 1;
+        loop {
+            let params = ListParams {
+                per_page: crate::constants::api::PER_PAGE,
+                page,
+            };
+            let batch: SecretList = self
+                .octocrab
+                .get(&path, Some(&params))
+                .await
+                .map_err(|e| anyhow::anyhow!("{}", crate::errors::GitHubError::from(e)))
+                .with_context(|| {
+                    format!("Failed to list secrets for {}/{}", self.owner, self.repo)
+                })?;
+
+            let is_last_page = batch.secrets.is_empty()
+                || secrets.len() + batch.secrets.len() >= batch.total_count;
+            secrets.extend(batch.secrets);
+            if is_last_page {
+                break;
+            }
+            page += 1;
+        }
+
+        Ok(secrets)
+    }
+
+    /// Check that the token can read the repository's Actions secrets public key.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the repository does not exist or the token lacks access.
+    pub async fn check_secrets_access(&self) -> Result<()> {
+        self.get_public_key().await.map(|_| ())
+    }
+
     pub async fn update_secret(&self, secret_name: &str, secret_value: &str) -> Result<()> {
         let public_key = self.get_public_key().await?;
         let encrypted_value = self
@@ -0,0 +1,94 @@
+//! Importing repositories from a GitHub organization or user into the config.
+//!
+//! This module filters the repositories returned by GitHub's listing endpoints
+//! and turns them into validated [`config::Repository`] entries.
+
+use regex::Regex;
+
+use crate::config;
+use crate::github::{OwnerKind, RemoteRepository};
+use crate::validation;
+
+/// Options for `config import`.
+#[derive(Debug, Clone)]
+pub struct ImportOptions {
+    /// Organization or user to list repositories for.
+    pub owner: String,
+    /// Whether `owner` is an organization or a user account.
+    pub kind: OwnerKind,
+    /// Only keep repositories whose name matches this pattern.
+    pub filter: Option<Regex>,
+    /// Only keep repositories carrying this topic.
+    pub topic: Option<String>,
+    /// Drop archived repositories.
+    pub exclude_archived: bool,
+}
+
+/// Outcome of filtering a repository listing.
+#[derive(Debug, Default)]
+pub struct ImportCandidates {
+    /// Repositories eligible for import, in listing order.
+    pub repositories: Vec<config::Repository>,
+    /// Number of repositories skipped because they are already configured.
+    pub already_configured: usize,
+    /// Repositories skipped because they failed validation, with the reason.
+    pub invalid: Vec<(String, String)>,
+}
+
+/// Check whether a listed repository passes the name, topic and archive filters.
+pub fn matches(repository: &RemoteRepository, options: &ImportOptions) -> bool {
+    if options.exclude_archived && repository.archived {
+        return false;
+    }
+    if let Some(filter) = &options.filter
+        && !filter.is_match(&repository.name)
+    {
+        return false;
+    }
+    if let Some(topic) = &options.topic
+        && !repository
+            .topics
+            .iter()
+            .any(|t| t.eq_ignore_ascii_case(topic))
+    {
+        return false;
+    }
+    true
+}
+
+/// Filter a repository listing into import candidates.
+///
+/// Repositories are validated with [`validation::validate_repo_owner`] and
+/// [`validation::validate_repo_name`], and entries already present in `existing`
+/// are left out.
+pub fn filter_candidates(
+    listing: &[RemoteRepository],
+    options: &ImportOptions,
+    existing: &config::Config,
+) -> ImportCandidates {
+    let mut candidates = ImportCandidates::default();
+
+    for remote in listing.iter().filter(|r| matches(r, options)) {
+        let path = format!("{}/{}", remote.owner.login, remote.name);
+
+        if let Err(e) = validation::validate_repo_owner(&remote.owner.login)
+            .and_then(|_| validation::validate_repo_name(&remote.name))
+        {
+            candidates.invalid.push((path, e.to_string()));
+            continue;
+        }
+
+        if existing.contains(&path) {
+            candidates.already_configured += 1;
+            continue;
+        }
+
+        candidates.repositories.push(config::Repository {
+            owner: remote.owner.login.clone(),
+            name: remote.name.clone(),
+            ..Default::default()
+        });
+    }
+
+    candidates
+}
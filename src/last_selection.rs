@@ -0,0 +1,75 @@
+//! Last repository selection.
+//!
+//! Repositories picked in the selection menu are saved by their `owner/name`
+//! path under the XDG state directory (see
+//! [`crate::paths::get_last_selection_path`]). The next run pre-checks them in
+//! the menu, and `--last` reuses them without showing it.
+
+use anyhow::{Context, Result};
+use chrono::Utc;
+use serde::{Deserialize, Serialize};
+use std::path::Path;
+
+use crate::config::Repository;
+use crate::config_edit;
+
+/// The saved selection.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct LastSelection {
+    /// RFC 3339 timestamp of the selection.
+    pub saved_at: String,
+    /// Selected repositories as `owner/name` paths.
+    pub repositories: Vec<String>,
+}
+
+/// Read the saved selection at `path`.
+///
+/// A missing file is treated as an empty selection.
+pub fn load(path: &Path) -> Result<LastSelection> {
+    if !path.exists() {
+        return Ok(LastSelection::default());
+    }
+    let content = std::fs::read_to_string(path)
+        .with_context(|| format!("Failed to read last selection {}", path.display()))?;
+    serde_json::from_str(&content)
+        .with_context(|| format!("Last selection {} is corrupted", path.display()))
+}
+
+/// Save the repositories at `indices` as the last selection.
+pub fn save(path: &Path, repositories: &[Repository], indices: &[usize]) -> Result<()> {
+    let selection = LastSelection {
+        saved_at: Utc::now().to_rfc3339(),
+        repositories: indices.iter().map(|&i| repositories[i].path()).collect(),
+    };
+    config_edit::replace_file(path, &serde_json::to_string_pretty(&selection)?)?;
+    Ok(())
+}
+
+impl LastSelection {
+    /// Indices of the saved repositories that are still configured, in config order.
+    ///
+    /// Paths are compared case-insensitively, like GitHub does.
+    pub fn indices(&self, repositories: &[Repository]) -> Vec<usize> {
+        (0..repositories.len())
+            .filter(|&i| {
+                let path = repositories[i].path();
+                self.repositories
+                    .iter()
+                    .any(|saved| saved.eq_ignore_ascii_case(&path))
+            })
+            .collect()
+    }
+
+    /// Saved paths that no longer match a configured repository.
+    pub fn missing(&self, repositories: &[Repository]) -> Vec<String> {
+        self.repositories
+            .iter()
+            .filter(|saved| {
+                !repositories
+                    .iter()
+                    .any(|r| r.path().eq_ignore_ascii_case(saved))
+            })
+            .cloned()
+            .collect()
+    }
+}
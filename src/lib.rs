@@ -11,22 +11,54 @@
 //! ## Modules
 //!
 //! - [`config`] - Configuration file parsing and validation
+//! - [`config_edit`] - Format-preserving, atomic config file saves
 //! - [`github`] - GitHub API client for secrets management
+//! - [`import`] - Importing repositories from an organization or user
+//! - [`last_selection`] - Last repository selection, reused with `--last`
+//! - [`lint`] - Secret value linting before upload
 //! - [`prompt`] - Interactive terminal user interface
+//! - [`provider`] - External secret provider protocol
 //! - [`validation`] - Input validation utilities
 //! - [`paths`] - XDG-compliant path resolution
 //! - [`error`] - Error formatting utilities
 //! - [`errors`] - Structured error types
 //! - [`constants`] - Application constants
+//! - [`doctor`] - Setup diagnostics
+//! - [`schema`] - Config schema versioning, strict checks and migration
+//! - [`secret_ref`] - `env:`, `file:` and `cmd:` secret value references
+//! - [`selector`] - Repository selection by name pattern
+//! - [`bundle`] - age/SOPS encrypted secret bundles
+//! - [`vault`] - Passphrase-encrypted local vault of reusable secret sets
+//! - [`generate`] - Random secret value generation
+//! - [`rotation`] - Log of secret rotations
+//! - [`policy`] - Rotation policies and durations
+//! - [`progress`] - Per-repository, per-secret progress of a run
+//! - [`overwrite`] - Overwrite answers remembered for the rest of a run
 
 pub mod app;
 pub mod app_deps;
+pub mod bundle;
 pub mod config;
+pub mod config_edit;
 pub mod constants;
+pub mod doctor;
 pub mod error;
 pub mod errors;
+pub mod generate;
 pub mod github;
+pub mod import;
+pub mod last_selection;
+pub mod lint;
+pub mod overwrite;
 pub mod paths;
+pub mod policy;
+pub mod progress;
 pub mod prompt;
+pub mod provider;
 pub mod rate_limit;
+pub mod rotation;
+pub mod schema;
+pub mod secret_ref;
+pub mod selector;
 pub mod validation;
+pub mod vault;
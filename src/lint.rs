@@ -0,0 +1,149 @@
+//! Secret value linting.
+//!
+//! Catches values that are almost certainly mistakes before they are uploaded:
+//! stray whitespace or newlines, quotes copied from a `.env` file, placeholders
+//! such as `changeme`, and values GitHub would reject outright.
+//! Errors always stop an upload; warnings only do in strict mode.
+
+use std::fmt;
+
+/// GitHub's limit on the size of a secret value, in bytes.
+pub const MAX_VALUE_BYTES: usize = 48 * 1024;
+
+/// Values that are obviously not real secrets, compared case-insensitively.
+const PLACEHOLDERS: &[&str] = &[
+    "changeme",
+    "change_me",
+    "change-me",
+    "replaceme",
+    "replace_me",
+    "placeholder",
+    "todo",
+    "tbd",
+    "fixme",
+    "null",
+    "nil",
+    "none",
+    "undefined",
+];
+
+/// How serious a lint finding is.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Severity {
+    /// Probably a mistake; uploaded anyway unless in strict mode.
+    Warning,
+    /// Never uploaded.
+    Error,
+}
+
+/// One problem found in a value.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LintIssue {
+    pub severity: Severity,
+    pub message: String,
+}
+
+impl LintIssue {
+    fn warning(message: &str) -> Self {
+        Self {
+            severity: Severity::Warning,
+            message: message.to_string(),
+        }
+    }
+
+    fn error(message: impl Into<String>) -> Self {
+        Self {
+            severity: Severity::Error,
+            message: message.into(),
+        }
+    }
+}
+
+impl fmt::Display for LintIssue {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.severity {
+            Severity::Warning => write!(f, "warning: {}", self.message),
+            Severity::Error => write!(f, "error: {}", self.message),
+        }
+    }
+}
+
+/// Check the value of secret `key` for likely mistakes. Never includes the value in messages.
+pub fn lint_value(key: &str, value: &str) -> Vec<LintIssue> {
+    let mut issues = Vec::new();
+    let trimmed = value.trim();
+
+    if trimmed.is_empty() {
+        issues.push(LintIssue::error("value is empty or only whitespace"));
+        return issues;
+    }
+    if value.len() > MAX_VALUE_BYTES {
+        issues.push(LintIssue::error(format!(
+            "value is {} bytes; GitHub's limit is {} bytes",
+            value.len(),
+            MAX_VALUE_BYTES
+        )));
+    }
+    if value.contains('\u{FFFD}') || value.contains('\0') {
+        issues.push(LintIssue::error(
+            "value contains non-UTF-8 or binary content",
+        ));
+    }
+    if trimmed.eq_ignore_ascii_case(key.trim()) {
+        issues.push(LintIssue::error("value is the secret's own name"));
+    }
+
+    if value.ends_with('\n') || value.ends_with('\r') {
+        issues.push(LintIssue::warning("value ends with a newline"));
+    }
+    let inner = value.trim_end_matches(['\n', '\r']);
+    if inner.starts_with(char::is_whitespace) {
+        issues.push(LintIssue::warning("value starts with whitespace"));
+    }
+    if inner.ends_with(|c: char| c.is_whitespace()) {
+        issues.push(LintIssue::warning("value ends with whitespace"));
+    }
+
+    let quoted = trimmed.len() >= 2
+        && ((trimmed.starts_with('"') && trimmed.ends_with('"'))
+            || (trimmed.starts_with('\'') && trimmed.ends_with('\'')));
+    if quoted && trimmed.len() == 2 {
+        issues.push(LintIssue::error("value is an empty quoted string"));
+    } else if quoted {
+        issues.push(LintIssue::warning("value is wrapped in quotes"));
+    }
+
+    let unquoted = if quoted {
+        &trimmed[1..trimmed.len() - 1]
+    } else {
+        trimmed
+    };
+    let is_placeholder = PLACEHOLDERS
+        .iter()
+        .any(|p| unquoted.eq_ignore_ascii_case(p))
+        || (unquoted.len() >= 3 && unquoted.chars().all(|c| c == 'x' || c == 'X'))
+        || (unquoted.starts_with('<') && unquoted.ends_with('>'))
+        || (unquoted.starts_with("${") && unquoted.ends_with('}'))
+        || (unquoted.to_ascii_lowercase().starts_with("your") && unquoted.ends_with("here"));
+    if is_placeholder {
+        issues.push(LintIssue::warning("value looks like a placeholder"));
+    }
+
+    issues
+}
+
+/// Whether `issues` should stop an upload.
+pub fn blocks_upload(issues: &[LintIssue], strict: bool) -> bool {
+    issues
+        .iter()
+        .any(|issue| strict || issue.severity == Severity::Error)
+}
+
+/// Join issue messages for a single status line.
+pub fn summarize(issues: &[LintIssue]) -> String {
+    issues
+        .iter()
+        .map(|issue| issue.message.as_str())
+        .collect::<Vec<_>>()
+        .join("; ")
+}
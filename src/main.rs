@@ -1,15 +1,31 @@
 pub mod app;
 pub mod app_deps;
+pub mod bundle;
 pub mod cli;
 pub mod config;
+pub mod config_edit;
 pub mod constants;
+pub mod doctor;
 pub mod error;
 pub mod errors;
+pub mod generate;
 pub mod github;
+pub mod import;
+pub mod last_selection;
+pub mod lint;
+pub mod overwrite;
 pub mod paths;
+pub mod policy;
+pub mod progress;
 pub mod prompt;
+pub mod provider;
 pub mod rate_limit;
+pub mod rotation;
+pub mod schema;
+pub mod secret_ref;
+pub mod selector;
 pub mod validation;
+pub mod vault;
 
 use anyhow::Result;
 use clap::Parser;
@@ -19,7 +35,78 @@ async fn main() -> Result<()> {
     let cli = cli::Cli::parse();
 
     match cli.command {
-        Some(cli::Commands::Config) => app::App::config().await,
-        None => app::App::run().await,
+        Some(cli::Commands::Config { action }) => match action {
+            None => app::App::config().await,
+            Some(cli::ConfigCommands::Import(args)) => {
+                app::App::config_import(args.into_options()?).await
+            }
+            Some(cli::ConfigCommands::List { json }) => app::App::config_list(json),
+            Some(cli::ConfigCommands::Add(args)) => app::App::config_edit(args.into_action()?),
+            Some(cli::ConfigCommands::Remove { repository }) => {
+                app::App::config_edit(app::ConfigAction::Remove(repository))
+            }
+            Some(cli::ConfigCommands::SetAlias { repository, alias }) => {
+                app::App::config_edit(app::ConfigAction::SetAlias { repository, alias })
+            }
+            Some(cli::ConfigCommands::Validate) => app::App::config_validate(),
+            Some(cli::ConfigCommands::Migrate) => app::App::config_migrate(),
+        },
+        Some(cli::Commands::Doctor) => app::App::doctor().await,
+        Some(cli::Commands::Age(args)) => {
+            app::App::age(app::AgeOptions {
+                policies: args.policies,
+                json: args.json,
+                selection: app::RunOptions {
+                    groups: cli.groups,
+                    tags: cli.tags,
+                    repos: cli.repos,
+                    last: cli.last,
+                    ..Default::default()
+                },
+            })
+            .await
+        }
+        Some(cli::Commands::Rotate(args)) => {
+            let value = args.new_value()?;
+            app::App::rotate(app::RotateOptions {
+                secret: args.secret,
+                value,
+                create: args.create,
+                yes: args.yes,
+                selection: app::RunOptions {
+                    groups: cli.groups,
+                    tags: cli.tags,
+                    repos: cli.repos,
+                    last: cli.last,
+                    strict: cli.strict,
+                    ..Default::default()
+                },
+            })
+            .await
+        }
+        Some(cli::Commands::Vault { action }) => match action {
+            cli::VaultCommands::List => app::App::vault_list(),
+            cli::VaultCommands::Rename { from, to } => {
+                app::App::vault_edit(app::VaultAction::Rename { from, to })
+            }
+            cli::VaultCommands::Remove { name } => {
+                app::App::vault_edit(app::VaultAction::Remove(name))
+            }
+        },
+        None => {
+            app::App::run(app::RunOptions {
+                groups: cli.groups,
+                tags: cli.tags,
+                repos: cli.repos,
+                last: cli.last,
+                bundle: cli.bundle,
+                identity: cli.identity,
+                generate: cli.generate,
+                generate_output: cli.generate_output,
+                strict: cli.strict,
+                overwrite: cli.overwrite,
+            })
+            .await
+        }
     }
 }
@@ -0,0 +1,237 @@
+//! Overwriting secrets that already exist.
+//!
+//! An [`OverwritePolicy`], from `--overwrite` or a repository's `overwrite` key
+//! in config, can decide without asking, e.g. only replacing secrets that
+//! weren't updated in the last week:
+//!
+//! ```toml
+//! [[repositories]]
+//! owner = "my-org"
+//! name = "api"
+//! overwrite = "if-older-than=7d"
+//! ```
+//!
+//! A bare `if-newer-than` compares the other way round: a secret is only
+//! replaced when the value being pushed changed after it, e.g. a `file:`
+//! reference or bundle edited since the last upload.
+//!
+//! Otherwise the overwrite question can be answered for just that secret or
+//! for a wider scope: the rest of the repository, the same secret in every
+//! repository, or everything. [`OverwriteDecisions`] remembers the wider
+//! answers for the rest of the run so they aren't asked again.
+
+use anyhow::{Context, Result};
+use chrono::{DateTime, Duration, Utc};
+use std::collections::HashMap;
+use std::fmt;
+use std::str::FromStr;
+
+use crate::policy;
+
+/// How to handle secrets that already exist.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum OverwritePolicy {
+    /// Ask for each secret (the default).
+    #[default]
+    Prompt,
+    /// Overwrite without asking.
+    Always,
+    /// Keep existing secrets without asking.
+    Never,
+    /// Overwrite secrets last updated longer ago than the duration.
+    IfOlderThan(Duration),
+    /// Overwrite secrets last updated within the duration.
+    IfNewerThan(Duration),
+    /// Overwrite secrets last updated before the new value was last changed.
+    IfNewer,
+}
+
+impl FromStr for OverwritePolicy {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        let text = s.trim();
+        let (name, duration) = match text.split_once('=') {
+            Some((name, duration)) => (name.trim(), Some(duration)),
+            None => (text, None),
+        };
+        let age = || -> Result<Duration> {
+            let duration = duration.with_context(|| {
+                format!(
+                    "Overwrite policy '{}' needs a duration, e.g. {}=7d",
+                    s, name
+                )
+            })?;
+            policy::parse_duration(duration)
+                .with_context(|| format!("Invalid overwrite policy '{}'", s))
+        };
+        let policy = match name.to_ascii_lowercase().as_str() {
+            "prompt" => Self::Prompt,
+            "always" => Self::Always,
+            "never" => Self::Never,
+            "if-older-than" => return Ok(Self::IfOlderThan(age()?)),
+            "if-newer-than" if duration.is_none() => Self::IfNewer,
+            "if-newer-than" => return Ok(Self::IfNewerThan(age()?)),
+            _ => anyhow::bail!(
+                "Unknown overwrite policy '{}'; expected prompt, always, never, \
+                 if-older-than=<duration>, if-newer-than or if-newer-than=<duration>",
+                s
+            ),
+        };
+        if duration.is_some() {
+            anyhow::bail!("Overwrite policy '{}' doesn't take a duration", name);
+        }
+        Ok(policy)
+    }
+}
+
+impl fmt::Display for OverwritePolicy {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Prompt => f.write_str("prompt"),
+            Self::Always => f.write_str("always"),
+            Self::Never => f.write_str("never"),
+            Self::IfOlderThan(age) => write!(f, "if-older-than={}", format_duration(*age)),
+            Self::IfNewerThan(age) => write!(f, "if-newer-than={}", format_duration(*age)),
+            Self::IfNewer => f.write_str("if-newer-than"),
+        }
+    }
+}
+
+/// Format a duration in the largest whole unit [`policy::parse_duration`] accepts.
+fn format_duration(duration: Duration) -> String {
+    let minutes = duration.num_minutes();
+    [(7 * 24 * 60, "w"), (24 * 60, "d"), (60, "h")]
+        .iter()
+        .find(|(size, _)| minutes != 0 && minutes % size == 0)
+        .map(|(size, unit)| format!("{}{}", minutes / size, unit))
+        .unwrap_or_else(|| format!("{}m", minutes))
+}
+
+impl OverwritePolicy {
+    /// Decide whether to overwrite a secret last updated at `last_updated`
+    /// (an RFC 3339 timestamp) with a value last changed at `value_modified`,
+    /// or `None` to ask. A value made during the run has `value_modified` of `now`.
+    ///
+    /// The age-based policies keep secrets whose last update time is unknown.
+    pub fn decide(
+        &self,
+        last_updated: Option<&str>,
+        value_modified: DateTime<Utc>,
+        now: DateTime<Utc>,
+    ) -> Option<bool> {
+        let updated = || {
+            last_updated
+                .and_then(|d| DateTime::parse_from_rfc3339(d).ok())
+                .map(|d| d.with_timezone(&Utc))
+        };
+        let age = || updated().map(|d| now.signed_duration_since(d));
+        match self {
+            Self::Prompt => None,
+            Self::Always => Some(true),
+            Self::Never => Some(false),
+            Self::IfOlderThan(max) => Some(age().is_some_and(|age| age > *max)),
+            Self::IfNewerThan(max) => Some(age().is_some_and(|age| age <= *max)),
+            Self::IfNewer => Some(updated().is_some_and(|d| value_modified > d)),
+        }
+    }
+}
+
+/// What an overwrite answer applies to.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AnswerScope {
+    /// Only the secret being asked about.
+    Once,
+    /// Every remaining secret in the same repository.
+    Repository,
+    /// The same secret in every remaining repository.
+    Secret,
+    /// Everything left in the run.
+    All,
+}
+
+/// Answer to an overwrite question.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct OverwriteAnswer {
+    /// Whether to overwrite.
+    pub overwrite: bool,
+    /// What the answer applies to.
+    pub scope: AnswerScope,
+}
+
+impl OverwriteAnswer {
+    /// Overwrite this secret only.
+    pub const YES: Self = Self {
+        overwrite: true,
+        scope: AnswerScope::Once,
+    };
+    /// Keep this secret only.
+    pub const NO: Self = Self {
+        overwrite: false,
+        scope: AnswerScope::Once,
+    };
+
+    /// Answer for `overwrite` in `scope`.
+    pub fn new(overwrite: bool, scope: AnswerScope) -> Self {
+        Self { overwrite, scope }
+    }
+
+    /// Answer for the keys pressed at the overwrite prompt.
+    ///
+    /// `y` overwrites this secret and anything else keeps it. `r`, `s` and `a`
+    /// pick the rest of the repository, this secret everywhere or everything,
+    /// and `confirm` then reads a second key for that scope: `y` to overwrite,
+    /// anything else to keep. Case doesn't matter, so Caps Lock can't turn a
+    /// yes into a no.
+    pub fn from_keys(key: char, confirm: impl FnOnce(AnswerScope) -> Result<char>) -> Result<Self> {
+        let scope = match key.to_ascii_lowercase() {
+            'r' => AnswerScope::Repository,
+            's' => AnswerScope::Secret,
+            'a' => AnswerScope::All,
+            _ => return Ok(Self::new(is_yes(key), AnswerScope::Once)),
+        };
+        Ok(Self::new(is_yes(confirm(scope)?), scope))
+    }
+}
+
+fn is_yes(key: char) -> bool {
+    key.eq_ignore_ascii_case(&'y')
+}
+
+/// Wider-scoped overwrite answers given so far in a run.
+#[derive(Debug, Clone, Default)]
+pub struct OverwriteDecisions {
+    all: Option<bool>,
+    repositories: HashMap<String, bool>,
+    secrets: HashMap<String, bool>,
+}
+
+impl OverwriteDecisions {
+    /// The remembered decision for `secret` in `repository`, if any.
+    ///
+    /// Secret names are compared case-insensitively, like GitHub does. A
+    /// repository-wide answer wins over a secret-wide one, as it can only have
+    /// been given later.
+    pub fn decided(&self, repository: &str, secret: &str) -> Option<bool> {
+        self.all
+            .or_else(|| self.repositories.get(repository).copied())
+            .or_else(|| self.secrets.get(&secret.to_uppercase()).copied())
+    }
+
+    /// Remember `answer` given for `secret` in `repository`, and return whether
+    /// to overwrite that secret.
+    pub fn record(&mut self, repository: &str, secret: &str, answer: OverwriteAnswer) -> bool {
+        match answer.scope {
+            AnswerScope::Once => {}
+            AnswerScope::Repository => {
+                self.repositories
+                    .insert(repository.to_string(), answer.overwrite);
+            }
+            AnswerScope::Secret => {
+                self.secrets.insert(secret.to_uppercase(), answer.overwrite);
+            }
+            AnswerScope::All => self.all = Some(answer.overwrite),
+        }
+        answer.overwrite
+    }
+}
@@ -5,54 +5,131 @@
 
 use anyhow::Result;
 use std::env;
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 
-/// Find the config.toml file.
-/// Priority:
-/// 1. CONFIG_PATH from environment (if set)
-/// 2. Current directory/config.toml
-/// 3. XDG_CONFIG_HOME/github-secrets/config.toml (if XDG_CONFIG_HOME is set)
-/// 4. ~/.config/github-secrets/config.toml (default XDG location)
-pub fn find_config_file() -> Result<PathBuf> {
-    // Check if CONFIG_PATH is explicitly set (highest priority)
+/// List every config.toml location that is looked at: `CONFIG_PATH`, the current
+/// directory and the XDG locations, in that order.
+///
+/// Candidates are returned whether or not they exist, so callers such as the
+/// `doctor` command can report every location that was considered.
+pub fn config_file_candidates() -> Vec<PathBuf> {
+    let mut candidates = Vec::new();
+
+    // CONFIG_PATH is explicitly set (highest priority)
     if let Ok(config_path) = env::var("CONFIG_PATH") {
-        let path = PathBuf::from(&config_path);
-        if path.exists() {
-            return Ok(path);
-        }
+        candidates.push(PathBuf::from(config_path));
     }
 
-    // 1. Try current directory first (use absolute path to avoid race conditions)
+    // Current directory (use absolute path to avoid race conditions)
     if let Ok(current_dir) = env::current_dir() {
-        let current_dir_config = current_dir.join("config.toml");
-        if current_dir_config.exists() {
-            return Ok(current_dir_config);
-        }
+        candidates.push(current_dir.join("config.toml"));
     }
 
-    // 2. Try XDG_CONFIG_HOME/github-secrets/config.toml (if XDG_CONFIG_HOME is set)
+    // XDG_CONFIG_HOME/github-secrets/config.toml (if XDG_CONFIG_HOME is set)
     if let Ok(xdg_config_home) = env::var("XDG_CONFIG_HOME") {
-        let xdg_config_path = PathBuf::from(xdg_config_home)
-            .join("github-secrets")
-            .join("config.toml");
-        if xdg_config_path.exists() {
-            return Ok(xdg_config_path);
-        }
+        candidates.push(
+            PathBuf::from(xdg_config_home)
+                .join("github-secrets")
+                .join("config.toml"),
+        );
     }
 
-    // 3. Try default XDG location (~/.config/github-secrets/config.toml)
+    // Default XDG location (~/.config/github-secrets/config.toml)
     if let Some(home) = dirs::home_dir() {
-        let default_xdg_config = home
-            .join(".config")
-            .join("github-secrets")
-            .join("config.toml");
-        if default_xdg_config.exists() {
-            return Ok(default_xdg_config);
-        }
+        candidates.push(
+            home.join(".config")
+                .join("github-secrets")
+                .join("config.toml"),
+        );
     }
 
+    candidates
+}
+
+/// Find the config.toml file, which config commands edit.
+///
+/// This is the highest-priority existing layer, in [`config_layer_candidates`]
+/// order, so edits land in the file whose settings win when merging:
+/// 1. CONFIG_PATH from environment (if set)
+/// 2. XDG_CONFIG_HOME/github-secrets/config.toml (if XDG_CONFIG_HOME is set)
+/// 3. ~/.config/github-secrets/config.toml (default XDG location)
+/// 4. Current directory/config.toml
+pub fn find_config_file() -> Result<PathBuf> {
     // If none exists, return default XDG path (will show error when trying to read)
-    Ok(get_config_creation_path())
+    Ok(config_layer_candidates()
+        .into_iter()
+        .find(|path| path.exists())
+        .unwrap_or_else(get_config_creation_path))
+}
+
+/// List the config.toml locations merged by [`find_config_layers`], highest priority first.
+///
+/// Personal files in the XDG locations come before `./config.toml`, so personal
+/// overrides win over a team-shared config checked into the current directory.
+/// Like [`config_file_candidates`], missing files are included.
+pub fn config_layer_candidates() -> Vec<PathBuf> {
+    let current = env::current_dir().ok().map(|dir| dir.join("config.toml"));
+    let (mut shared, mut layers): (Vec<PathBuf>, Vec<PathBuf>) = config_file_candidates()
+        .into_iter()
+        .partition(|path| Some(path) == current.as_ref());
+    layers.append(&mut shared);
+    layers
+}
+
+/// The current directory's `config.toml`, canonicalized, when it is merged as
+/// a shared layer rather than chosen by the user.
+///
+/// That file comes with whatever directory the tool runs in, so provider
+/// commands defined in it are not run (see [`crate::provider::fetch`]). It is
+/// trusted when `CONFIG_PATH` is set or when it is also a personal config.
+pub fn untrusted_config_file() -> Option<PathBuf> {
+    if env::var_os("CONFIG_PATH").is_some() {
+        return None;
+    }
+    let current = env::current_dir().ok()?.join("config.toml");
+    let canonical = current.canonicalize().ok()?;
+    let personal = config_file_candidates()
+        .into_iter()
+        .filter(|path| *path != current)
+        .any(|path| path.canonicalize().ok().as_ref() == Some(&canonical));
+    (!personal).then_some(canonical)
+}
+
+/// Pick the config files that are merged into the effective configuration.
+///
+/// When `CONFIG_PATH` is set, only that file is used. Otherwise every existing
+/// candidate is a layer, in priority order, with duplicate locations removed
+/// (e.g. when `XDG_CONFIG_HOME` points at `~/.config`).
+pub fn select_config_layers(
+    candidates: &[PathBuf],
+    config_path_override: Option<&Path>,
+) -> Vec<PathBuf> {
+    if let Some(over) = config_path_override {
+        return if over.exists() {
+            vec![over.to_path_buf()]
+        } else {
+            Vec::new()
+        };
+    }
+
+    let mut layers: Vec<PathBuf> = Vec::new();
+    let mut seen: Vec<PathBuf> = Vec::new();
+    for path in candidates.iter().filter(|path| path.exists()) {
+        let canonical = path.canonicalize().unwrap_or_else(|_| path.clone());
+        if !seen.contains(&canonical) {
+            seen.push(canonical);
+            layers.push(path.clone());
+        }
+    }
+    layers
+}
+
+/// Find all config files to merge, highest priority first.
+///
+/// See [`select_config_layers`] and [`config_layer_candidates`].
+pub fn find_config_layers() -> Vec<PathBuf> {
+    let config_path_override = env::var("CONFIG_PATH").ok().map(PathBuf::from);
+    select_config_layers(&config_layer_candidates(), config_path_override.as_deref())
 }
 
 /// Get the path where a new config file should be created.
@@ -80,45 +157,127 @@ pub fn get_config_creation_path() -> PathBuf {
     PathBuf::from("config.toml")
 }
 
+/// Get the application data directory.
+/// Priority:
+/// 1. XDG_DATA_HOME/github-secrets (if XDG_DATA_HOME is set)
+/// 2. ~/.local/share/github-secrets (default XDG location)
+/// 3. Current directory (fallback)
+pub fn get_data_dir() -> PathBuf {
+    if let Ok(xdg_data_home) = env::var("XDG_DATA_HOME") {
+        return PathBuf::from(xdg_data_home).join("github-secrets");
+    }
+
+    if let Some(home) = dirs::home_dir() {
+        return home.join(".local").join("share").join("github-secrets");
+    }
+
+    PathBuf::from(".")
+}
+
+/// Get the path of the encrypted secret vault (see [`crate::vault`]).
+pub fn get_vault_path() -> PathBuf {
+    get_data_dir().join("vault.json")
+}
+
+/// Get the application state directory.
+/// Priority:
+/// 1. XDG_STATE_HOME/github-secrets (if XDG_STATE_HOME is set)
+/// 2. ~/.local/state/github-secrets (default XDG location)
+/// 3. Current directory (fallback)
+pub fn get_state_dir() -> PathBuf {
+    if let Ok(xdg_state_home) = env::var("XDG_STATE_HOME") {
+        return PathBuf::from(xdg_state_home).join("github-secrets");
+    }
+
+    if let Some(home) = dirs::home_dir() {
+        return home.join(".local").join("state").join("github-secrets");
+    }
+
+    PathBuf::from(".")
+}
+
+/// Get the path of the secret rotation log (see [`crate::rotation`]).
+pub fn get_rotation_log_path() -> PathBuf {
+    get_state_dir().join("rotations.json")
+}
+
+/// Get the path of the last repository selection (see [`crate::last_selection`]).
+pub fn get_last_selection_path() -> PathBuf {
+    get_state_dir().join("last-selection.json")
+}
+
+/// List the .env locations searched by [`load_env_file`], in priority order.
+pub fn env_file_candidates() -> Vec<PathBuf> {
+    let mut candidates = vec![PathBuf::from(".env")];
+
+    if let Some(home) = dirs::home_dir() {
+        candidates.push(home.join(".config").join("github-secrets").join(".env"));
+    }
+
+    if let Ok(xdg_config_home) = env::var("XDG_CONFIG_HOME") {
+        candidates.push(
+            PathBuf::from(xdg_config_home)
+                .join("github-secrets")
+                .join(".env"),
+        );
+    }
+
+    candidates
+}
+
 /// Find and load .env file.
 /// Priority:
 /// 1. Current directory/.env
 /// 2. ~/.config/github-secrets/.env (default XDG location)
 /// 3. XDG_CONFIG_HOME/github-secrets/.env (if XDG_CONFIG_HOME is set)
-pub fn load_env_file() {
+///
+/// Returns the files that were actually loaded, in load order.
+pub fn load_env_file() -> Vec<PathBuf> {
+    let mut loaded = Vec::new();
+
     // 1. Try current directory first
     let current_dir_env = PathBuf::from(".env");
     if current_dir_env.exists() {
-        let _ = dotenv::from_path(&current_dir_env);
+        if dotenv::from_path(&current_dir_env).is_ok() {
+            loaded.push(current_dir_env.clone());
+        }
         // After loading, check if XDG_CONFIG_HOME was set in .env
         // and reload from that location if it exists
         if let Ok(xdg_config_home) = env::var("XDG_CONFIG_HOME") {
             let xdg_env_path = PathBuf::from(xdg_config_home)
                 .join("github-secrets")
                 .join(".env");
-            if xdg_env_path.exists() && xdg_env_path != current_dir_env {
-                let _ = dotenv::from_path(&xdg_env_path);
+            if xdg_env_path.exists()
+                && xdg_env_path != current_dir_env
+                && dotenv::from_path(&xdg_env_path).is_ok()
+            {
+                loaded.push(xdg_env_path);
             }
         }
-        return;
+        return loaded;
     }
 
     // 2. Try default XDG location (~/.config/github-secrets/.env)
     if let Some(home) = dirs::home_dir() {
         let default_xdg_env = home.join(".config").join("github-secrets").join(".env");
         if default_xdg_env.exists() {
-            let _ = dotenv::from_path(&default_xdg_env);
+            if dotenv::from_path(&default_xdg_env).is_ok() {
+                loaded.push(default_xdg_env.clone());
+            }
 
             // If XDG_CONFIG_HOME was set in the .env file, reload from the new location
             if let Ok(new_xdg_config_home) = env::var("XDG_CONFIG_HOME") {
                 let new_xdg_env_path = PathBuf::from(new_xdg_config_home)
                     .join("github-secrets")
                     .join(".env");
-                if new_xdg_env_path.exists() && new_xdg_env_path != default_xdg_env {
-                    let _ = dotenv::from_path(&new_xdg_env_path);
+                if new_xdg_env_path.exists()
+                    && new_xdg_env_path != default_xdg_env
+                    && dotenv::from_path(&new_xdg_env_path).is_ok()
+                {
+                    loaded.push(new_xdg_env_path);
                 }
             }
-            return;
+            return loaded;
         }
     }
 
@@ -128,13 +287,18 @@ pub fn load_env_file() {
             .join("github-secrets")
             .join(".env");
         if xdg_env_path.exists() {
-            let _ = dotenv::from_path(&xdg_env_path);
-            return;
+            if dotenv::from_path(&xdg_env_path).is_ok() {
+                loaded.push(xdg_env_path);
+            }
+            return loaded;
         }
     }
 
     // Fallback: try current directory again (dotenv default behavior)
-    let _ = dotenv::dotenv();
+    if let Ok(path) = dotenv::dotenv() {
+        loaded.push(path);
+    }
+    loaded
 }
 
 #[cfg(test)]
@@ -0,0 +1,121 @@
+//! Secret rotation policies and durations.
+//!
+//! A policy gives the maximum age of secrets whose name matches a glob, e.g.
+//!
+//! ```toml
+//! [[rotation_policy]]
+//! pattern = "*_TOKEN"
+//! max_age = "90d"
+//! ```
+//!
+//! Patterns are case-insensitive and the first matching policy wins.
+
+use anyhow::{Context, Result};
+use chrono::Duration;
+use regex::{Regex, RegexBuilder};
+use serde::{Deserialize, Serialize};
+use std::str::FromStr;
+
+use crate::selector;
+
+/// A `[[rotation_policy]]` entry in config.toml, or a `--policy PATTERN=DURATION` flag.
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(deny_unknown_fields)]
+pub struct RotationPolicy {
+    /// Glob matched against secret names (`*` and `?` wildcards).
+    pub pattern: String,
+    /// Maximum age, e.g. `90d` (see [`parse_duration`]).
+    pub max_age: String,
+}
+
+impl FromStr for RotationPolicy {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        let (pattern, max_age) = s
+            .split_once('=')
+            .with_context(|| format!("Invalid policy '{}'; expected PATTERN=DURATION", s))?;
+        let policy = Self {
+            pattern: pattern.trim().to_string(),
+            max_age: max_age.trim().to_string(),
+        };
+        policy.compile()?;
+        Ok(policy)
+    }
+}
+
+impl RotationPolicy {
+    /// Check the pattern and duration, producing a matcher.
+    pub fn compile(&self) -> Result<CompiledPolicy> {
+        if self.pattern.is_empty() {
+            anyhow::bail!("Rotation policy pattern is empty");
+        }
+        let matcher = RegexBuilder::new(&selector::glob_to_regex(&self.pattern))
+            .case_insensitive(true)
+            .build()
+            .with_context(|| format!("Invalid rotation policy pattern '{}'", self.pattern))?;
+        let max_age = parse_duration(&self.max_age)
+            .with_context(|| format!("Invalid max_age for pattern '{}'", self.pattern))?;
+        Ok(CompiledPolicy {
+            pattern: self.pattern.clone(),
+            max_age_text: self.max_age.clone(),
+            max_age,
+            matcher,
+        })
+    }
+}
+
+/// A [`RotationPolicy`] ready for matching.
+#[derive(Debug, Clone)]
+pub struct CompiledPolicy {
+    /// The original glob.
+    pub pattern: String,
+    /// The maximum age as written, for display.
+    pub max_age_text: String,
+    /// The maximum age.
+    pub max_age: Duration,
+    matcher: Regex,
+}
+
+impl CompiledPolicy {
+    /// Check whether the policy applies to a secret name.
+    pub fn matches(&self, secret: &str) -> bool {
+        self.matcher.is_match(secret)
+    }
+}
+
+/// Compile policies in order, failing on the first invalid one.
+pub fn compile_all(policies: &[RotationPolicy]) -> Result<Vec<CompiledPolicy>> {
+    policies.iter().map(RotationPolicy::compile).collect()
+}
+
+/// Get the first policy that applies to `secret`.
+pub fn find<'a>(policies: &'a [CompiledPolicy], secret: &str) -> Option<&'a CompiledPolicy> {
+    policies.iter().find(|p| p.matches(secret))
+}
+
+/// Parse a duration such as `90d`, `12w`, `36h` or `30m`.
+///
+/// Units are `m` (minutes), `h` (hours), `d` (days) and `w` (weeks).
+pub fn parse_duration(input: &str) -> Result<Duration> {
+    let text = input.trim();
+    let split = text
+        .find(|c: char| !c.is_ascii_digit())
+        .with_context(|| format!("Duration '{}' has no unit; use m, h, d or w", input))?;
+    let (number, unit) = text.split_at(split);
+    let amount: i64 = number
+        .parse()
+        .with_context(|| format!("Invalid duration '{}'", input))?;
+    let duration = match unit {
+        "m" => Duration::try_minutes(amount),
+        "h" => Duration::try_hours(amount),
+        "d" => Duration::try_days(amount),
+        "w" => Duration::try_weeks(amount),
+        _ => anyhow::bail!(
+            "Unknown unit '{}' in duration '{}'; use m, h, d or w",
+            unit,
+            input
+        ),
+    };
+    duration.with_context(|| format!("Duration '{}' is too large", input))
+}
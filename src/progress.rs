@@ -0,0 +1,211 @@
+//! Progress of a run across repositories and secrets.
+//!
+//! [`Progress`] holds one status cell per repository and secret, plus the error
+//! details shown next to them. The run updates it as it goes and hands it to
+//! [`crate::app_deps::PromptInterface::show_progress`], which draws the dashboard.
+//! Each finished upload is an [`UpdateResult`], recorded with [`Progress::record`].
+
+use crate::app::UpdateResult;
+
+/// Status of one secret in one repository.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CellStatus {
+    /// Not processed yet.
+    Pending,
+    /// Being checked or uploaded.
+    Running,
+    /// Uploaded; the secret didn't exist before.
+    Created,
+    /// Uploaded over an existing secret.
+    Updated,
+    /// Not uploaded because overwriting was declined.
+    Skipped,
+    /// Upload or value resolution failed.
+    Failed,
+}
+
+impl CellStatus {
+    /// Short label for the status.
+    pub fn label(self) -> &'static str {
+        match self {
+            CellStatus::Pending => "pending",
+            CellStatus::Running => "running",
+            CellStatus::Created => "created",
+            CellStatus::Updated => "updated",
+            CellStatus::Skipped => "skipped",
+            CellStatus::Failed => "failed",
+        }
+    }
+}
+
+/// Something worth reading about one cell: an error, or a note such as a name
+/// matching an existing secret in another case.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Detail {
+    pub repository: String,
+    pub secret: String,
+    pub message: String,
+    /// Errors are shown in red and removed when a retry succeeds.
+    pub is_error: bool,
+}
+
+/// Number of cells in each status.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct Counts {
+    pub pending: usize,
+    pub running: usize,
+    pub created: usize,
+    pub updated: usize,
+    pub skipped: usize,
+    pub failed: usize,
+}
+
+impl Counts {
+    /// Cells that reached a final status.
+    pub fn done(&self) -> usize {
+        self.created + self.updated + self.skipped + self.failed
+    }
+
+    /// All cells.
+    pub fn total(&self) -> usize {
+        self.pending + self.running + self.done()
+    }
+}
+
+/// Status of every secret in every repository of a run.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct Progress {
+    /// Secret names, one column each.
+    pub secrets: Vec<String>,
+    /// Repository display names, one row each.
+    pub repositories: Vec<String>,
+    /// `cells[repository][secret]`.
+    pub cells: Vec<Vec<CellStatus>>,
+    /// Errors and notes, oldest first.
+    pub details: Vec<Detail>,
+}
+
+impl Progress {
+    /// Start a run with every cell pending.
+    pub fn new(secrets: Vec<String>, repositories: Vec<String>) -> Self {
+        let cells = vec![vec![CellStatus::Pending; secrets.len()]; repositories.len()];
+        Self {
+            secrets,
+            repositories,
+            cells,
+            details: Vec::new(),
+        }
+    }
+
+    /// Status of `secret` in `repository` (both indices).
+    pub fn status(&self, repository: usize, secret: usize) -> CellStatus {
+        self.cells[repository][secret]
+    }
+
+    /// Set the status of a cell; a success clears its earlier error.
+    pub fn set(&mut self, repository: usize, secret: usize, status: CellStatus) {
+        self.cells[repository][secret] = status;
+        if matches!(status, CellStatus::Created | CellStatus::Updated) {
+            let (repo_name, secret_name) = self.names(repository, secret);
+            self.details
+                .retain(|d| !(d.is_error && d.repository == repo_name && d.secret == secret_name));
+        }
+    }
+
+    /// Mark a cell failed with `message`, replacing its earlier error.
+    pub fn fail(&mut self, repository: usize, secret: usize, message: String) {
+        self.cells[repository][secret] = CellStatus::Failed;
+        let (repo_name, secret_name) = self.names(repository, secret);
+        self.details
+            .retain(|d| !(d.is_error && d.repository == repo_name && d.secret == secret_name));
+        self.details.push(Detail {
+            repository: repo_name,
+            secret: secret_name,
+            message,
+            is_error: true,
+        });
+    }
+
+    /// Set a cell from the outcome of its upload: created, or updated if the
+    /// secret `existed`, on success, failed with the result's error otherwise.
+    pub fn record(
+        &mut self,
+        repository: usize,
+        secret: usize,
+        result: &UpdateResult,
+        existed: bool,
+    ) {
+        if result.is_success() {
+            let status = if existed {
+                CellStatus::Updated
+            } else {
+                CellStatus::Created
+            };
+            self.set(repository, secret, status);
+        } else {
+            let error = result.error.clone();
+            self.fail(
+                repository,
+                secret,
+                error.unwrap_or_else(|| "Unknown error".to_string()),
+            );
+        }
+    }
+
+    /// Add a note about a cell.
+    pub fn note(&mut self, repository: usize, secret: usize, message: String) {
+        let (repository, secret) = self.names(repository, secret);
+        self.details.push(Detail {
+            repository,
+            secret,
+            message,
+            is_error: false,
+        });
+    }
+
+    /// Cells that failed, as `(repository, secret)` indices.
+    pub fn failed(&self) -> Vec<(usize, usize)> {
+        self.cells
+            .iter()
+            .enumerate()
+            .flat_map(|(r, row)| {
+                row.iter()
+                    .enumerate()
+                    .filter(|(_, status)| **status == CellStatus::Failed)
+                    .map(move |(s, _)| (r, s))
+            })
+            .collect()
+    }
+
+    /// Count cells by status, overall.
+    pub fn counts(&self) -> Counts {
+        Self::count(self.cells.iter().flatten())
+    }
+
+    /// Count cells by status for one repository.
+    pub fn repository_counts(&self, repository: usize) -> Counts {
+        Self::count(self.cells[repository].iter())
+    }
+
+    fn count<'a>(cells: impl Iterator<Item = &'a CellStatus>) -> Counts {
+        let mut counts = Counts::default();
+        for status in cells {
+            match status {
+                CellStatus::Pending => counts.pending += 1,
+                CellStatus::Running => counts.running += 1,
+                CellStatus::Created => counts.created += 1,
+                CellStatus::Updated => counts.updated += 1,
+                CellStatus::Skipped => counts.skipped += 1,
+                CellStatus::Failed => counts.failed += 1,
+            }
+        }
+        counts
+    }
+
+    fn names(&self, repository: usize, secret: usize) -> (String, String) {
+        (
+            self.repositories[repository].clone(),
+            self.secrets[secret].clone(),
+        )
+    }
+}
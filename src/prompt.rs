@@ -2,9 +2,15 @@
 //!
 //! This module provides a ratatui-based TUI for:
 //! - Entering secret key-value pairs interactively
+//! - Loading and saving secret sets from the local [`crate::vault`]
+//! - Showing the progress and summary of a run (see [`crate::progress`])
 use chrono::{DateTime, Utc};
 use colored::*;
-use crossterm::event::{self, Event, KeyCode, KeyEvent, KeyEventKind, KeyModifiers};
+use crossterm::cursor::{MoveTo, Show};
+use crossterm::event::{
+    self, DisableBracketedPaste, EnableBracketedPaste, Event, KeyCode, KeyEvent, KeyEventKind,
+    KeyModifiers,
+};
 use crossterm::execute;
 use crossterm::terminal::{self, Clear, ClearType};
 use ratatui::{
@@ -13,9 +19,17 @@ use ratatui::{
     layout::{Alignment, Constraint, Layout},
     style::{Color, Modifier, Style},
     text::{Line, Span},
-    widgets::{Block, Borders, List, ListItem, ListState, Paragraph},
+    widgets::{
+        Block, Borders, Cell, Gauge, List, ListItem, ListState, Paragraph, Row, Table, TableState,
+        Wrap,
+    },
 };
-use std::io::{self, Write};
+use std::collections::HashSet;
+use std::io::{self, IsTerminal, Write};
+
+use crate::overwrite::{AnswerScope, OverwriteAnswer};
+use crate::progress::{CellStatus, Progress};
+use crate::vault::VaultSession;
 
 /// A key-value pair representing a GitHub secret.
 #[derive(Clone)]
@@ -37,16 +51,19 @@ pub fn prompt_secrets() -> anyhow::Result<Vec<SecretPair>> {
     // Setup terminal
     terminal::enable_raw_mode()?;
     let mut stdout = io::stdout();
-    // Clear the terminal before showing TUI
-    execute!(stdout, Clear(ClearType::All))?;
+    // Clear the terminal before showing TUI; bracketed paste delivers pasted
+    // text as one event instead of a key press per character
+    execute!(stdout, EnableBracketedPaste, Clear(ClearType::All))?;
     let backend = CrosstermBackend::new(stdout);
     let mut terminal = Terminal::new(backend)?;
 
     let mut event_src = CrosstermEventSource;
-    let res = prompt_secrets_with(&mut terminal, &mut event_src);
+    let mut vault = VaultSession::default_location();
+    let res = prompt_secrets_with_vault(&mut terminal, &mut event_src, Some(&mut vault));
 
     // Restore terminal in all cases
     terminal::disable_raw_mode()?;
+    execute!(io::stdout(), DisableBracketedPaste)?;
     drop(terminal);
 
     res
@@ -62,14 +79,34 @@ pub fn prompt_secrets_with<B: Backend, E: EventSource>(
     terminal: &mut Terminal<B>,
     events: &mut E,
 ) -> anyhow::Result<Vec<SecretPair>> {
-    let mut secrets = Vec::new();
+    prompt_secrets_with_vault(terminal, events, None)
+}
+
+/// Prompt for secret key-value pairs, with Ctrl+L/Ctrl+S loading and saving
+/// secret sets from `vault` when one is given.
+pub fn prompt_secrets_with_vault<B: Backend, E: EventSource>(
+    terminal: &mut Terminal<B>,
+    events: &mut E,
+    mut vault: Option<&mut VaultSession>,
+) -> anyhow::Result<Vec<SecretPair>> {
+    let mut secrets: Vec<SecretPair> = Vec::new();
     let mut current_key = String::new();
     let mut current_value = String::new();
     let mut input_mode = InputMode::Key; // Start with key input
     let mut message = String::new();
     let mut message_color = Color::Yellow;
+    let mut dialog: Option<VaultDialog> = None;
+    // Set once lint warnings for the current value were shown; Enter again adds it anyway.
+    let mut lint_warned = false;
+    let mut display = ValueDisplay::default();
+    let mut list = SecretList::default();
+    // Entry being edited from the list; Enter/ESC return to the list instead of adding
+    let mut editing: Option<usize> = None;
+    // Last deleted entry, its position and whether it was toggled off, for undo
+    let mut deleted: Option<(usize, SecretPair, bool)> = None;
 
     loop {
+        let new_vault = vault.as_ref().is_some_and(|v| !v.exists());
         terminal.draw(|frame| {
             render_secret_input_ui(
                 frame,
@@ -79,23 +116,115 @@ pub fn prompt_secrets_with<B: Backend, E: EventSource>(
                 &input_mode,
                 &message,
                 message_color,
+                display,
+                &list,
             );
+            if let Some(dialog) = &dialog {
+                render_vault_dialog(frame, dialog, new_vault);
+            }
         })?;
 
         // Handle input
-        if let Event::Key(key) = events.read_event()? {
+        let event = events.read_event()?;
+        // A paste arrives as one event, so line breaks in it don't submit the value
+        if let Event::Paste(text) = &event {
+            if dialog.is_none() {
+                match input_mode {
+                    InputMode::Key => {
+                        current_key.extend(text.chars().filter(|c| !c.is_whitespace()));
+                        message.clear();
+                    }
+                    InputMode::Value => {
+                        let text = text.replace("\r\n", "\n").replace('\r', "\n");
+                        if text.contains('\n') && !display.multiline {
+                            display.multiline = true;
+                            message = "Multi-line mode: pasted text has line breaks (Ctrl+D: add)"
+                                .to_string();
+                            message_color = Color::Cyan;
+                        } else {
+                            message.clear();
+                        }
+                        current_value.push_str(&text);
+                        display.scroll = 0;
+                        lint_warned = false;
+                    }
+                    InputMode::List => {}
+                }
+            }
+            continue;
+        }
+        if let Event::Key(key) = event {
             if key.kind != KeyEventKind::Press {
                 continue;
             }
             // Check for Ctrl+C - exit immediately
             if key.code == KeyCode::Char('c') && key.modifiers == KeyModifiers::CONTROL {
                 terminal::disable_raw_mode()?;
+                let _ = execute!(io::stdout(), DisableBracketedPaste);
                 std::process::exit(0);
             }
 
+            if let (Some(current), Some(session)) = (dialog.take(), vault.as_deref_mut()) {
+                let (next, outcome) = handle_vault_key(current, key, session, &mut secrets);
+                dialog = next;
+                if let Some((text, color)) = outcome {
+                    message = text;
+                    message_color = color;
+                }
+                continue;
+            }
+
+            if key.code == KeyCode::Char('n') && key.modifiers == KeyModifiers::CONTROL {
+                display.show_length = !display.show_length;
+                continue;
+            }
+
             match input_mode {
                 InputMode::Key => {
                     match key.code {
+                        KeyCode::Char('l') | KeyCode::Char('s')
+                            if key.modifiers == KeyModifiers::CONTROL =>
+                        {
+                            let action = if key.code == KeyCode::Char('l') {
+                                VaultAction::Load
+                            } else {
+                                VaultAction::Save
+                            };
+                            match vault.as_deref() {
+                                Some(session) => {
+                                    match start_vault_action(action, session, &secrets) {
+                                        Ok(next) => {
+                                            dialog = Some(next);
+                                            message.clear();
+                                        }
+                                        Err(text) => {
+                                            message = text;
+                                            message_color = Color::Yellow;
+                                        }
+                                    }
+                                }
+                                None => {
+                                    message = "⚠️  Secret vault is not available".to_string();
+                                    message_color = Color::Yellow;
+                                }
+                            }
+                        }
+                        KeyCode::Enter if editing.is_some() => {
+                            let index = editing.unwrap_or_default();
+                            match rename_secret(&mut secrets, &mut list, index, &current_key) {
+                                Ok(text) => {
+                                    message = text;
+                                    message_color = Color::Green;
+                                    current_key.clear();
+                                    editing = None;
+                                    input_mode = InputMode::List;
+                                }
+                                Err(text) => {
+                                    message = text;
+                                    message_color = Color::Yellow;
+                                }
+                            }
+                        }
                         KeyCode::Enter => {
                             match crate::validation::validate_secret_key(&current_key) {
                                 Ok(()) => {
@@ -103,11 +232,32 @@ pub fn prompt_secrets_with<B: Backend, E: EventSource>(
                                     message.clear();
                                 }
                                 Err(e) => {
-                                    message = format!("⚠️  {}", e);
+                                    message = invalid_key_message(&current_key, &e);
                                     message_color = Color::Yellow;
                                 }
                             }
                         }
+                        KeyCode::Up if editing.is_none() && !secrets.is_empty() => {
+                            input_mode = InputMode::List;
+                            list.selected = secrets.len() - 1;
+                            message.clear();
+                        }
+                        KeyCode::Tab => {
+                            // Normalize the name the way GitHub stores it
+                            if let Some(suggestion) =
+                                crate::validation::suggest_secret_key(&current_key)
+                            {
+                                current_key = suggestion;
+                                message.clear();
+                            }
+                        }
+                        KeyCode::Esc if editing.is_some() => {
+                            // Cancel renaming and go back to the list
+                            current_key.clear();
+                            editing = None;
+                            input_mode = InputMode::List;
+                            message.clear();
+                        }
                         KeyCode::Esc => {
                             if !current_key.is_empty() || !secrets.is_empty() {
                                 // Ask for confirmation
@@ -118,6 +268,10 @@ pub fn prompt_secrets_with<B: Backend, E: EventSource>(
                                 break;
                             }
                         }
+                        // Value-field shortcuts such as Ctrl+G aren't typed into the name
+                        KeyCode::Char(_)
+                            if key.modifiers.contains(KeyModifiers::CONTROL)
+                                && !key.modifiers.contains(KeyModifiers::ALT) => {}
                         KeyCode::Char(c) => {
                             current_key.push(c);
                             message.clear();
@@ -130,27 +284,59 @@ pub fn prompt_secrets_with<B: Backend, E: EventSource>(
                     }
                 }
                 InputMode::Value => {
+                    // In multi-line mode Enter adds a line break and Ctrl+D adds the secret
+                    let submit = if display.multiline {
+                        key.code == KeyCode::Char('d') && key.modifiers == KeyModifiers::CONTROL
+                    } else {
+                        key.code == KeyCode::Enter
+                    };
                     match key.code {
-                        KeyCode::Enter => {
+                        _ if submit => {
                             if current_value.trim().is_empty() {
                                 message = "⚠️  Value cannot be empty".to_string();
                                 message_color = Color::Yellow;
+                            } else if let Err(e) =
+                                crate::secret_ref::SecretSource::parse(&current_value).validate()
+                            {
+                                message = format!("⚠️  {}", e);
+                                message_color = Color::Yellow;
+                            } else if let Some((text, color)) =
+                                lint_message(&current_key, &current_value, lint_warned)
+                            {
+                                message = text;
+                                message_color = color;
+                                lint_warned = true;
                             } else {
-                                // Check for duplicate key
+                                // GitHub names are case-insensitive, so `db_url` replaces `DB_URL`
                                 let key_to_add = current_key.clone();
-                                let was_duplicate = secrets.iter().any(|s| s.key == key_to_add);
-
-                                // Remove existing entry with same key (if any)
-                                secrets.retain(|s| s.key != key_to_add);
-
-                                // Add new secret pair
-                                secrets.push(SecretPair {
+                                let pair = SecretPair {
                                     key: current_key.clone(),
                                     value: current_value.clone(),
-                                });
+                                };
+                                let position = secrets
+                                    .iter()
+                                    .position(|s| s.key.eq_ignore_ascii_case(&key_to_add));
+                                let replaced = position.map(|i| secrets[i].key.clone());
+
+                                // Replace an existing entry in place, keeping the list order
+                                match position {
+                                    Some(i) => secrets[i] = pair,
+                                    None => secrets.push(pair),
+                                }
+                                // Entering a key again turns it back on; editing keeps its toggle
+                                if editing.is_none() {
+                                    list.disabled.remove(&key_to_add.to_ascii_lowercase());
+                                }
 
                                 // Set appropriate message
-                                if was_duplicate {
+                                if let Some(previous) =
+                                    replaced.as_ref().filter(|p| **p != key_to_add)
+                                {
+                                    message = format!(
+                                        "✓ Secret '{}' replaces '{}' (names are case-insensitive)",
+                                        key_to_add, previous
+                                    );
+                                } else if replaced.is_some() {
                                     message = format!("✓ Secret '{}' updated", key_to_add);
                                 } else {
                                     message = format!("✓ Secret '{}' added", key_to_add);
@@ -158,22 +344,168 @@ pub fn prompt_secrets_with<B: Backend, E: EventSource>(
                                 message_color = Color::Green;
                                 current_key.clear();
                                 current_value.clear();
-                                input_mode = InputMode::Key;
+                                input_mode = if editing.take().is_some() {
+                                    InputMode::List
+                                } else {
+                                    InputMode::Key
+                                };
+                                lint_warned = false;
+                                display = ValueDisplay {
+                                    show_length: display.show_length,
+                                    ..Default::default()
+                                };
+                            }
+                        }
+                        KeyCode::Enter => {
+                            current_value.push('\n');
+                            display.scroll = 0;
+                            message.clear();
+                            lint_warned = false;
+                        }
+                        KeyCode::Up if display.multiline => {
+                            let lines = current_value.split('\n').count();
+                            if usize::from(display.scroll) + 1 < lines {
+                                display.scroll += 1;
                             }
                         }
+                        KeyCode::Down if display.multiline => {
+                            display.scroll = display.scroll.saturating_sub(1);
+                        }
+                        KeyCode::Char('t') if key.modifiers == KeyModifiers::CONTROL => {
+                            display.multiline = !display.multiline;
+                            display.scroll = 0;
+                        }
+                        KeyCode::Char('r') if key.modifiers == KeyModifiers::CONTROL => {
+                            display.reveal = !display.reveal;
+                        }
+                        KeyCode::Char('g') if key.modifiers == KeyModifiers::CONTROL => {
+                            current_value = crate::generate::generate(
+                                crate::generate::DEFAULT_LENGTH,
+                                crate::generate::Charset::default(),
+                            );
+                            // Only shown in clear when reveal is on; the next key press
+                            // clears the message
+                            message = if display.reveal {
+                                format!("Generated (store it now): {}", current_value)
+                            } else {
+                                format!(
+                                    "Generated a {}-character value (Ctrl+R: reveal it to store it)",
+                                    current_value.chars().count()
+                                )
+                            };
+                            message_color = Color::Magenta;
+                            lint_warned = false;
+                        }
                         KeyCode::Esc => {
-                            // Go back to key input
+                            // Go back to key input, or to the list when editing an entry
                             current_value.clear();
-                            input_mode = InputMode::Key;
+                            if editing.take().is_some() {
+                                current_key.clear();
+                                input_mode = InputMode::List;
+                            } else {
+                                input_mode = InputMode::Key;
+                            }
                             message.clear();
+                            lint_warned = false;
+                            display = ValueDisplay {
+                                show_length: display.show_length,
+                                ..Default::default()
+                            };
                         }
+                        // Unbound Ctrl shortcuts (e.g. Ctrl+D outside multi-line mode) aren't
+                        // typed; AltGr characters arrive as Ctrl+Alt and are.
+                        KeyCode::Char(_)
+                            if key.modifiers.contains(KeyModifiers::CONTROL)
+                                && !key.modifiers.contains(KeyModifiers::ALT) => {}
                         KeyCode::Char(c) => {
                             current_value.push(c);
                             message.clear();
+                            lint_warned = false;
                         }
                         KeyCode::Backspace => {
                             current_value.pop();
                             message.clear();
+                            lint_warned = false;
+                        }
+                        _ => {}
+                    }
+                }
+                InputMode::List => {
+                    message.clear();
+                    let selected = list.selected;
+                    match key.code {
+                        KeyCode::Up if key.modifiers == KeyModifiers::SHIFT && selected > 0 => {
+                            secrets.swap(selected, selected - 1);
+                            list.selected -= 1;
+                        }
+                        KeyCode::Down
+                            if key.modifiers == KeyModifiers::SHIFT
+                                && selected + 1 < secrets.len() =>
+                        {
+                            secrets.swap(selected, selected + 1);
+                            list.selected += 1;
+                        }
+                        KeyCode::Up => {
+                            list.selected = selected.saturating_sub(1);
+                        }
+                        KeyCode::Down if selected + 1 < secrets.len() => {
+                            list.selected += 1;
+                        }
+                        KeyCode::Char('k') if selected < secrets.len() => {
+                            current_key = secrets[selected].key.clone();
+                            editing = Some(selected);
+                            input_mode = InputMode::Key;
+                        }
+                        KeyCode::Enter | KeyCode::Char('v') if selected < secrets.len() => {
+                            current_key = secrets[selected].key.clone();
+                            current_value = secrets[selected].value.clone();
+                            display.multiline = current_value.contains('\n');
+                            editing = Some(selected);
+                            input_mode = InputMode::Value;
+                        }
+                        KeyCode::Char(' ') if selected < secrets.len() => {
+                            let key = secrets[selected].key.to_ascii_lowercase();
+                            if !list.disabled.remove(&key) {
+                                list.disabled.insert(key);
+                            }
+                        }
+                        KeyCode::Char('d') | KeyCode::Delete | KeyCode::Backspace
+                            if selected < secrets.len() =>
+                        {
+                            let pair = secrets.remove(selected);
+                            let was_disabled = list.disabled.remove(&pair.key.to_ascii_lowercase());
+                            message = format!("✓ Secret '{}' deleted (u: undo)", pair.key);
+                            message_color = Color::Green;
+                            deleted = Some((selected, pair, was_disabled));
+                            list.selected = selected.min(secrets.len().saturating_sub(1));
+                        }
+                        KeyCode::Char('u') => match deleted.take() {
+                            Some((_, pair, _))
+                                if secrets
+                                    .iter()
+                                    .any(|s| s.key.eq_ignore_ascii_case(&pair.key)) =>
+                            {
+                                message =
+                                    format!("⚠️  Cannot undo: '{}' was added again", pair.key);
+                                message_color = Color::Yellow;
+                            }
+                            Some((index, pair, was_disabled)) => {
+                                let index = index.min(secrets.len());
+                                if was_disabled {
+                                    list.disabled.insert(pair.key.to_ascii_lowercase());
+                                }
+                                message = format!("✓ Secret '{}' restored", pair.key);
+                                message_color = Color::Green;
+                                secrets.insert(index, pair);
+                                list.selected = index;
+                            }
+                            None => {
+                                message = "Nothing to undo".to_string();
+                                message_color = Color::Yellow;
+                            }
+                        },
+                        KeyCode::Esc | KeyCode::Tab => {
+                            input_mode = InputMode::Key;
                         }
                         _ => {}
                     }
@@ -182,16 +514,131 @@ pub fn prompt_secrets_with<B: Backend, E: EventSource>(
         }
     }
 
-    Ok(secrets)
+    // Entries toggled off stay in the list but are not submitted
+    Ok(secrets
+        .into_iter()
+        .filter(|pair| list.is_enabled(pair))
+        .collect())
+}
+
+/// Error message for an invalid key, with the normalized name to apply with Tab.
+fn invalid_key_message(key: &str, error: &anyhow::Error) -> String {
+    match crate::validation::suggest_secret_key(key) {
+        Some(suggestion) => format!("⚠️  {} (Tab: use '{}')", error, suggestion),
+        None => format!("⚠️  {}", error),
+    }
+}
+
+/// Rename the entry at `index` to `new_key`, keeping its value, position and toggle.
+fn rename_secret(
+    secrets: &mut [SecretPair],
+    list: &mut SecretList,
+    index: usize,
+    new_key: &str,
+) -> Result<String, String> {
+    crate::validation::validate_secret_key(new_key)
+        .map_err(|e| invalid_key_message(new_key, &e))?;
+    if let Some(other) = secrets
+        .iter()
+        .enumerate()
+        .find(|(i, s)| *i != index && s.key.eq_ignore_ascii_case(new_key))
+    {
+        return Err(format!(
+            "⚠️  '{}' is already in the list (names are case-insensitive)",
+            other.1.key
+        ));
+    }
+    let old_key = std::mem::replace(&mut secrets[index].key, new_key.to_string());
+    if list.disabled.remove(&old_key.to_ascii_lowercase()) {
+        list.disabled.insert(new_key.to_ascii_lowercase());
+    }
+    Ok(format!("✓ Secret '{}' renamed to '{}'", old_key, new_key))
+}
+
+/// Message to show instead of adding a literal value, if lint finds problems.
+///
+/// Errors always block; warnings block until they have been shown once (`warned`).
+/// References are checked when they are resolved instead.
+fn lint_message(key: &str, value: &str, warned: bool) -> Option<(String, Color)> {
+    let source = crate::secret_ref::SecretSource::parse(value);
+    let crate::secret_ref::SecretSource::Literal(literal) = source else {
+        return None;
+    };
+    let issues = crate::lint::lint_value(key, &literal);
+    let summary = crate::lint::summarize(&issues);
+    if crate::lint::blocks_upload(&issues, false) {
+        Some((format!("✗ {}", summary), Color::Red))
+    } else if !issues.is_empty() && !warned {
+        Some((
+            format!("⚠️  {} (add again to confirm)", summary),
+            Color::Yellow,
+        ))
+    } else {
+        None
+    }
 }
 
 #[derive(Debug, Clone, PartialEq)]
 pub enum InputMode {
     Key,
     Value,
+    /// Focus is on the "Added Secrets" list.
+    List,
+}
+
+/// State of the "Added Secrets" list on the input screen.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct SecretList {
+    /// Entry highlighted while the list has focus.
+    pub selected: usize,
+    /// Lowercased keys of entries toggled off; they are kept but not submitted.
+    pub disabled: HashSet<String>,
+}
+
+impl SecretList {
+    /// Whether `pair` will be submitted.
+    pub fn is_enabled(&self, pair: &SecretPair) -> bool {
+        !self.disabled.contains(&pair.key.to_ascii_lowercase())
+    }
+}
+
+/// Fixed-width placeholder for hidden values, so their length isn't visible.
+const MASK: &str = "••••••••";
+
+/// How secret values are shown on the input screen.
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct ValueDisplay {
+    /// Show the value being typed in plain text (toggled with Ctrl+R).
+    pub reveal: bool,
+    /// Show an "N chars" hint next to masked values (toggled with Ctrl+N).
+    pub show_length: bool,
+    /// Enter inserts a line break and Ctrl+D adds the value (toggled with Ctrl+T).
+    pub multiline: bool,
+    /// Lines scrolled up from the end of a multi-line value.
+    pub scroll: u16,
+}
+
+/// Height of the value field in multi-line mode, borders included.
+const MULTILINE_FIELD_HEIGHT: u16 = 8;
+
+/// Mask a value without revealing its length, unless `show_length` asks for a hint.
+fn masked(value: &str, show_length: bool) -> String {
+    let lines = value.split('\n').count();
+    match (value.is_empty(), show_length) {
+        (true, _) => String::new(),
+        (false, true) if lines > 1 => format!(
+            "{} ({} chars, {} lines)",
+            MASK,
+            value.chars().count(),
+            lines
+        ),
+        (false, true) => format!("{} ({} chars)", MASK, value.chars().count()),
+        (false, false) => MASK.to_string(),
+    }
 }
 
 /// Render the secret input UI using ratatui.
+#[allow(clippy::too_many_arguments)]
 pub fn render_secret_input_ui(
     f: &mut Frame,
     secrets: &[SecretPair],
@@ -200,14 +647,27 @@ pub fn render_secret_input_ui(
     input_mode: &InputMode,
     message: &str,
     message_color: Color,
+    display: ValueDisplay,
+    list: &SecretList,
 ) {
     let size = f.size();
 
     // Calculate minimum required height for input field (must always be visible)
     // Single input field (3 lines) + message (1 line) = 4 lines minimum
-    let min_input_height = 4;
     let header_height = if size.height < 8 { 1 } else { 3 };
     let instructions_height = if size.height < 8 { 1 } else { 3 };
+    // A multi-line value gets a taller field when the terminal has room for it
+    let field_height = if *input_mode == InputMode::Value && display.multiline {
+        MULTILINE_FIELD_HEIGHT
+            .min(
+                size.height
+                    .saturating_sub(header_height + instructions_height + 1),
+            )
+            .max(3)
+    } else {
+        3
+    };
+    let min_input_height = field_height + 1;
     let fixed_height = header_height + min_input_height + instructions_height;
 
     // Calculate available space for secrets list (only if terminal is tall enough)
@@ -250,8 +710,14 @@ pub fn render_secret_input_ui(
         f.render_widget(header, chunks[0]);
     } else {
         let secret_count = secrets.len();
+        let off_count = secrets.iter().filter(|s| !list.is_enabled(s)).count();
         let header_text = if secret_count == 0 {
             "Enter secret key-value pairs. Press ESC to finish.".to_string()
+        } else if off_count > 0 {
+            format!(
+                "Enter secret key-value pairs ({} added, {} off). Press ESC to finish.",
+                secret_count, off_count
+            )
         } else {
             format!(
                 "Enter secret key-value pairs ({} added). Press ESC to finish.",
@@ -278,16 +744,22 @@ pub fn render_secret_input_ui(
     if available_for_list > 0 {
         let mut items = Vec::new();
         for (idx, secret) in secrets.iter().enumerate() {
-            let item_text = format!(
-                "{}. {} = {}",
-                idx + 1,
-                secret.key,
-                "•".repeat(secret.value.len())
-            );
-            items.push(ListItem::new(Span::styled(
-                item_text,
-                Style::default().fg(Color::Green),
-            )));
+            let source = crate::secret_ref::SecretSource::parse(&secret.value);
+            let shown_value = if source.is_reference() {
+                format!("→ {}", source)
+            } else {
+                masked(&secret.value, display.show_length)
+            };
+            let item_text = format!("{}. {} = {}", idx + 1, secret.key, shown_value);
+            let item = if list.is_enabled(secret) {
+                Span::styled(item_text, Style::default().fg(Color::Green))
+            } else {
+                Span::styled(
+                    format!("{} (off)", item_text),
+                    Style::default().fg(Color::DarkGray),
+                )
+            };
+            items.push(ListItem::new(item));
         }
         if items.is_empty() {
             items.push(ListItem::new(Span::styled(
@@ -301,10 +773,24 @@ pub fn render_secret_input_ui(
         } else {
             format!("Added Secrets ({})", secrets.len())
         };
-        let list = List::new(items)
-            .block(Block::default().borders(Borders::ALL).title(list_title))
-            .style(Style::default().fg(Color::White));
-        f.render_widget(list, chunks[list_chunk_idx]);
+        let focused = *input_mode == InputMode::List;
+        let border_color = if focused { Color::Cyan } else { Color::White };
+        let items = List::new(items)
+            .block(
+                Block::default()
+                    .borders(Borders::ALL)
+                    .border_style(Style::default().fg(border_color))
+                    .title(list_title),
+            )
+            .style(Style::default().fg(Color::White))
+            .highlight_style(Style::default().add_modifier(Modifier::REVERSED))
+            .highlight_symbol("▶ ");
+        // The highlight (and scrolling to it) only applies while the list has focus
+        let mut state = ListState::default();
+        if focused && !secrets.is_empty() {
+            state.select(Some(list.selected));
+        }
+        f.render_stateful_widget(items, chunks[list_chunk_idx], &mut state);
     }
 
     // Input area - single field that switches between key and value
@@ -312,13 +798,33 @@ pub fn render_secret_input_ui(
     let input_area_chunk_idx = if available_for_list > 0 { 2 } else { 1 };
     let input_chunks = Layout::default()
         .constraints([
-            Constraint::Length(3), // Input field (always 3 lines - guaranteed visible)
-            Constraint::Length(1), // Message (always 1 line)
+            Constraint::Length(field_height), // Input field (3 lines, taller for multi-line values)
+            Constraint::Length(1),            // Message (always 1 line)
         ])
         .split(chunks[input_area_chunk_idx]);
 
     // Show either key or value input based on mode
     match input_mode {
+        InputMode::List => {
+            let text = if secrets.is_empty() {
+                "The list is empty (u: undo delete, ESC: back)".to_string()
+            } else {
+                format!(
+                    "Editing entry {} of {}",
+                    list.selected.min(secrets.len() - 1) + 1,
+                    secrets.len()
+                )
+            };
+            f.render_widget(
+                Paragraph::new(text).block(
+                    Block::default()
+                        .borders(Borders::ALL)
+                        .border_style(Style::default().fg(Color::Cyan))
+                        .title("Added Secrets"),
+                ),
+                input_chunks[0],
+            );
+        }
         InputMode::Key => {
             // Key input with border and cursor
             let key_label = Span::styled(
@@ -350,21 +856,63 @@ pub fn render_secret_input_ui(
                     .fg(Color::Cyan)
                     .add_modifier(Modifier::BOLD),
             );
-            let masked_value = "•".repeat(current_value.len());
             let value_cursor = "│";
-            let value_block = Block::default()
-                .borders(Borders::ALL)
-                .border_style(Style::default().fg(Color::Cyan))
-                .title("Enter Secret Value");
-            f.render_widget(
-                Paragraph::new(Line::from(vec![
-                    value_label,
-                    Span::raw(masked_value),
-                    Span::raw(value_cursor),
-                ]))
-                .block(value_block),
-                input_chunks[0],
-            );
+            if display.multiline {
+                let value_block = Block::default()
+                    .borders(Borders::ALL)
+                    .border_style(Style::default().fg(Color::Cyan));
+                let paragraph = if display.reveal {
+                    // Show the window of lines ending `scroll` lines above the last one
+                    let mut lines: Vec<Line> = current_value.split('\n').map(Line::raw).collect();
+                    if let Some(last) = lines.last_mut() {
+                        last.spans.push(Span::raw(value_cursor));
+                    }
+                    let total = lines.len() as u16;
+                    let visible = field_height.saturating_sub(2).max(1);
+                    let bottom = total.saturating_sub(visible);
+                    let top = bottom.saturating_sub(display.scroll);
+                    let title = format!(
+                        "Enter Secret Value (multi-line, revealed) lines {}-{} of {}",
+                        top + 1,
+                        (top + visible).min(total),
+                        total
+                    );
+                    Paragraph::new(lines)
+                        .block(value_block.title(title))
+                        .scroll((top, 0))
+                } else {
+                    Paragraph::new(Line::from(vec![
+                        value_label,
+                        Span::raw(masked(current_value, display.show_length)),
+                        Span::raw(value_cursor),
+                    ]))
+                    .block(value_block.title("Enter Secret Value (multi-line)"))
+                };
+                f.render_widget(paragraph, input_chunks[0]);
+            } else {
+                let shown_value = if display.reveal {
+                    current_value.to_string()
+                } else {
+                    masked(current_value, display.show_length)
+                };
+                let value_block = Block::default()
+                    .borders(Borders::ALL)
+                    .border_style(Style::default().fg(Color::Cyan))
+                    .title(if display.reveal {
+                        "Enter Secret Value (revealed)"
+                    } else {
+                        "Enter Secret Value"
+                    });
+                f.render_widget(
+                    Paragraph::new(Line::from(vec![
+                        value_label,
+                        Span::raw(shown_value),
+                        Span::raw(value_cursor),
+                    ]))
+                    .block(value_block),
+                    input_chunks[0],
+                );
+            }
         }
     }
 
@@ -381,12 +929,24 @@ pub fn render_secret_input_ui(
     let instruction_text = if is_small_terminal {
         match input_mode {
             InputMode::Key => "Enter: next → value | ESC: finish",
+            InputMode::List => "Space: on/off | d: delete | ESC: back",
+            InputMode::Value if display.multiline => "Ctrl+D: add secret | ESC: back to key",
             InputMode::Value => "Enter: add secret | ESC: back to key",
         }
     } else {
         match input_mode {
-            InputMode::Key => "Enter: confirm key → value input | ESC: finish/cancel",
-            InputMode::Value => "Enter: add secret | ESC: back to key | Backspace: delete",
+            InputMode::Key => {
+                "Enter: confirm key → value input | ↑: edit list | Tab: normalize name | Ctrl+L/Ctrl+S: load/save set | ESC: finish/cancel"
+            }
+            InputMode::List => {
+                "↑/↓: move | Shift+↑/↓: reorder | Enter: edit value | k: edit key | Space: on/off | d: delete | u: undo | ESC: back"
+            }
+            InputMode::Value if display.multiline => {
+                "Enter: new line | Ctrl+D: add secret | ↑/↓: scroll | Ctrl+R: reveal | Ctrl+T: single-line | ESC: back to key"
+            }
+            InputMode::Value => {
+                "Enter: add secret | Ctrl+T: multi-line | Ctrl+R: reveal | Ctrl+N: length | Ctrl+G: generate | ESC: back to key"
+            }
         }
     };
     let instructions = if is_small_terminal {
@@ -402,6 +962,268 @@ pub fn render_secret_input_ui(
     f.render_widget(instructions, chunks[instructions_chunk_idx]);
 }
 
+/// What the user wants to do with the vault.
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum VaultAction {
+    Load,
+    Save,
+}
+
+/// Vault dialog shown on top of the secret input.
+enum VaultDialog {
+    /// Entering the passphrase before carrying out `then`. For a new vault,
+    /// `first` holds the passphrase being confirmed.
+    Passphrase {
+        then: VaultAction,
+        input: String,
+        first: Option<String>,
+    },
+    /// Choosing a set to load.
+    Pick { names: Vec<String>, selected: usize },
+    /// Naming the set to save.
+    SaveName { input: String },
+}
+
+/// Open the dialog for `action`, or explain why it can't be done.
+fn start_vault_action(
+    action: VaultAction,
+    session: &VaultSession,
+    secrets: &[SecretPair],
+) -> Result<VaultDialog, String> {
+    if action == VaultAction::Save && secrets.is_empty() {
+        return Err("⚠️  Add secrets before saving them as a set".to_string());
+    }
+    if !session.is_unlocked() {
+        if action == VaultAction::Load && !session.exists() {
+            return Err("⚠️  No saved secret sets yet (Ctrl+S saves one)".to_string());
+        }
+        return Ok(VaultDialog::Passphrase {
+            then: action,
+            input: String::new(),
+            first: None,
+        });
+    }
+    match action {
+        VaultAction::Load => {
+            let names = session
+                .vault()
+                .map(|v| v.set_names())
+                .map_err(|e| format!("⚠️  {}", e))?;
+            if names.is_empty() {
+                return Err("⚠️  No saved secret sets yet (Ctrl+S saves one)".to_string());
+            }
+            Ok(VaultDialog::Pick { names, selected: 0 })
+        }
+        VaultAction::Save => Ok(VaultDialog::SaveName {
+            input: String::new(),
+        }),
+    }
+}
+
+/// Handle a key press in a vault dialog.
+///
+/// Returns the dialog to show next (if any) and a status message.
+fn handle_vault_key(
+    dialog: VaultDialog,
+    key: KeyEvent,
+    session: &mut VaultSession,
+    secrets: &mut Vec<SecretPair>,
+) -> (Option<VaultDialog>, Option<(String, Color)>) {
+    if key.code == KeyCode::Esc {
+        return (None, None);
+    }
+
+    match dialog {
+        VaultDialog::Passphrase {
+            then,
+            mut input,
+            first,
+        } => match key.code {
+            KeyCode::Enter => {
+                // A new vault gets its passphrase here, so ask for it twice.
+                if !session.exists() {
+                    match first {
+                        None if input.is_empty() => {
+                            return (
+                                Some(VaultDialog::Passphrase { then, input, first }),
+                                Some(("⚠️  The passphrase can't be empty".to_string(), Color::Red)),
+                            );
+                        }
+                        None => {
+                            return (
+                                Some(VaultDialog::Passphrase {
+                                    then,
+                                    input: String::new(),
+                                    first: Some(input),
+                                }),
+                                None,
+                            );
+                        }
+                        Some(first) if first != input => {
+                            return (
+                                Some(VaultDialog::Passphrase {
+                                    then,
+                                    input: String::new(),
+                                    first: None,
+                                }),
+                                Some((
+                                    "⚠️  Passphrases don't match, try again".to_string(),
+                                    Color::Red,
+                                )),
+                            );
+                        }
+                        Some(_) => {}
+                    }
+                }
+                if let Err(e) = session.unlock(&input) {
+                    return (None, Some((format!("⚠️  {}", e), Color::Red)));
+                }
+                match start_vault_action(then, session, secrets) {
+                    Ok(next) => (Some(next), None),
+                    Err(text) => (None, Some((text, Color::Yellow))),
+                }
+            }
+            KeyCode::Char(c) => {
+                input.push(c);
+                (Some(VaultDialog::Passphrase { then, input, first }), None)
+            }
+            KeyCode::Backspace => {
+                input.pop();
+                (Some(VaultDialog::Passphrase { then, input, first }), None)
+            }
+            _ => (Some(VaultDialog::Passphrase { then, input, first }), None),
+        },
+        VaultDialog::Pick {
+            names,
+            mut selected,
+        } => match key.code {
+            KeyCode::Up => {
+                selected = selected.saturating_sub(1);
+                (Some(VaultDialog::Pick { names, selected }), None)
+            }
+            KeyCode::Down => {
+                selected = (selected + 1).min(names.len().saturating_sub(1));
+                (Some(VaultDialog::Pick { names, selected }), None)
+            }
+            KeyCode::Enter => {
+                let name = &names[selected];
+                let loaded = session
+                    .vault()
+                    .ok()
+                    .and_then(|v| v.get(name))
+                    .unwrap_or_default();
+                for pair in &loaded {
+                    secrets.retain(|s| !s.key.eq_ignore_ascii_case(&pair.key));
+                }
+                let count = loaded.len();
+                secrets.extend(loaded);
+                (
+                    None,
+                    Some((
+                        format!("✓ Loaded {} secret(s) from set '{}'", count, name),
+                        Color::Green,
+                    )),
+                )
+            }
+            _ => (Some(VaultDialog::Pick { names, selected }), None),
+        },
+        VaultDialog::SaveName { mut input } => match key.code {
+            KeyCode::Enter => match session.save_set(&input, secrets) {
+                Ok(replaced) => {
+                    let verb = if replaced { "updated" } else { "saved" };
+                    (
+                        None,
+                        Some((format!("✓ Secret set '{}' {}", input, verb), Color::Green)),
+                    )
+                }
+                Err(e) => (
+                    Some(VaultDialog::SaveName { input }),
+                    Some((format!("⚠️  {}", e), Color::Red)),
+                ),
+            },
+            KeyCode::Char(c) => {
+                input.push(c);
+                (Some(VaultDialog::SaveName { input }), None)
+            }
+            KeyCode::Backspace => {
+                input.pop();
+                (Some(VaultDialog::SaveName { input }), None)
+            }
+            _ => (Some(VaultDialog::SaveName { input }), None),
+        },
+    }
+}
+
+/// Render a vault dialog as a popup over the secret input.
+fn render_vault_dialog(f: &mut Frame, dialog: &VaultDialog, new_vault: bool) {
+    let area = centered_rect(60, 50, f.size());
+    f.render_widget(ratatui::widgets::Clear, area);
+
+    let style = Style::default().fg(Color::Cyan);
+    match dialog {
+        VaultDialog::Passphrase { input, first, .. } => {
+            let title = match (new_vault, first) {
+                (true, Some(_)) => "Confirm New Vault Passphrase",
+                (true, None) => "New Vault Passphrase",
+                (false, _) => "Vault Passphrase",
+            };
+            // Don't reveal the passphrase length
+            let text = vec![
+                Line::from(masked(input, false)),
+                Line::from(""),
+                Line::from(Span::styled(
+                    "Enter: unlock | ESC: cancel",
+                    Style::default().fg(Color::DarkGray),
+                )),
+            ];
+            f.render_widget(
+                Paragraph::new(text)
+                    .block(Block::default().borders(Borders::ALL).title(title))
+                    .style(style),
+                area,
+            );
+        }
+        VaultDialog::Pick { names, selected } => {
+            let items: Vec<ListItem> = names
+                .iter()
+                .map(|name| ListItem::new(name.as_str()))
+                .collect();
+            let mut state = ListState::default();
+            state.select(Some(*selected));
+            let list = List::new(items)
+                .block(
+                    Block::default()
+                        .borders(Borders::ALL)
+                        .title("Load Secret Set (Enter: load | ESC: cancel)"),
+                )
+                .style(style)
+                .highlight_style(Style::default().add_modifier(Modifier::REVERSED))
+                .highlight_symbol("> ");
+            f.render_stateful_widget(list, area, &mut state);
+        }
+        VaultDialog::SaveName { input } => {
+            let text = vec![
+                Line::from(vec![Span::raw(input.as_str()), Span::raw("│")]),
+                Line::from(""),
+                Line::from(Span::styled(
+                    "Enter: save | ESC: cancel",
+                    Style::default().fg(Color::DarkGray),
+                )),
+            ];
+            f.render_widget(
+                Paragraph::new(text)
+                    .block(
+                        Block::default()
+                            .borders(Borders::ALL)
+                            .title("Save As Secret Set"),
+                    )
+                    .style(style),
+                area,
+            );
+        }
+    }
+}
+
 /// Confirm exit using ratatui.
 // `confirm_exit_ratatui` removed; tests and interactive flows use the injected
 // `confirm_exit_ratatui_with` variant which accepts an `EventSource`.
@@ -460,10 +1282,8 @@ pub fn confirm_exit_ratatui_with<B: Backend, E: EventSource>(
                 KeyCode::Left | KeyCode::Up => {
                     cursor_pos = cursor_pos.saturating_sub(1);
                 }
-                KeyCode::Right | KeyCode::Down => {
-                    if cursor_pos < 1 {
-                        cursor_pos += 1;
-                    }
+                KeyCode::Right | KeyCode::Down if cursor_pos < 1 => {
+                    cursor_pos += 1;
                 }
                 KeyCode::Char('y') | KeyCode::Char('Y') | KeyCode::Enter if cursor_pos == 0 => {
                     return Ok(true);
@@ -540,27 +1360,33 @@ pub fn format_date(date_str: &str) -> String {
 
 /// Confirm whether to update an existing secret.
 ///
-/// Displays a confirmation prompt showing the secret name and when it was last updated.
+/// Displays a confirmation prompt showing the secret name, the repository and
+/// when the secret was last updated. Besides `y`/`n` for this secret, `r`
+/// answers for the rest of the repository, `s` for this secret in every
+/// repository and `a` for everything, each followed by a second `y`/`n`.
 ///
 /// # Arguments
 ///
+/// * `repository` - The repository holding the secret
 /// * `secret_name` - The name of the secret to update
 /// * `last_updated` - Optional ISO 8601 timestamp of when the secret was last updated
 ///
 /// # Returns
 ///
-/// Returns `true` if the user confirms the update, `false` otherwise.
+/// Returns the answer and what it applies to; see [`OverwriteAnswer::from_keys`].
 ///
 /// # Errors
 ///
 /// Returns an error if terminal operations fail.
 pub fn confirm_secret_update(
+    repository: &str,
     secret_name: &str,
     last_updated: Option<&str>,
-) -> anyhow::Result<bool> {
+) -> anyhow::Result<OverwriteAnswer> {
     print!("\n{}", "⚠️  Secret '".yellow());
     print!("{}", secret_name.bright_yellow());
-    print!("{}", "' already exists".yellow());
+    print!("{}", "' already exists in ".yellow());
+    print!("{}", repository.bright_yellow());
     if let Some(date) = last_updated {
         let friendly_date = format_date(date);
         print!(
@@ -570,13 +1396,29 @@ pub fn confirm_secret_update(
         );
         print!("{}", ")".yellow());
     }
-    print!("{}", ". Overwrite? (y/N): ".yellow());
+    println!("{}", ".".yellow());
+    print!(
+        "{}",
+        "   r: rest of this repo | s: this secret in all repos | a: everything".dimmed()
+    );
+    print!("\n{}", "Overwrite? (y/N/r/s/a): ".yellow());
     io::stdout().flush()?;
 
     let response = read_single_char()?;
     println!(); // New line after input
 
-    Ok(response == 'y' || response == 'Y')
+    OverwriteAnswer::from_keys(response, |scope| {
+        let target = match scope {
+            AnswerScope::Repository => format!("every remaining existing secret in {}", repository),
+            AnswerScope::Secret => format!("'{}' in every remaining repository", secret_name),
+            AnswerScope::All | AnswerScope::Once => "every remaining existing secret".to_string(),
+        };
+        print!("{}", format!("Overwrite {}? (y/N): ", target).yellow());
+        io::stdout().flush()?;
+        let response = read_single_char()?;
+        println!();
+        Ok(response)
+    })
 }
 
 pub fn confirm_retry() -> anyhow::Result<bool> {
@@ -592,12 +1434,369 @@ pub fn confirm_retry() -> anyhow::Result<bool> {
     Ok(response == 'y' || response == 'Y')
 }
 
+/// The progress dashboard of a run.
+///
+/// One terminal is kept for the whole run, so each update only redraws the
+/// cells that changed instead of clearing the screen.
+#[derive(Default)]
+pub struct ProgressScreen {
+    terminal: Option<Terminal<CrosstermBackend<io::Stdout>>>,
+    /// Something else wrote to the screen since the last draw.
+    stale: bool,
+}
+
+impl ProgressScreen {
+    /// Draw the dashboard; does nothing when stdout isn't a terminal.
+    ///
+    /// Overwrite questions asked during the run are printed over the last line
+    /// and cleared by the next redraw (see [`ProgressScreen::invalidate`]).
+    pub fn show(&mut self, progress: &Progress) -> anyhow::Result<()> {
+        if !io::stdout().is_terminal() {
+            return Ok(());
+        }
+        let terminal = match &mut self.terminal {
+            Some(terminal) => terminal,
+            None => {
+                self.stale = true;
+                self.terminal
+                    .insert(Terminal::new(CrosstermBackend::new(io::stdout()))?)
+            }
+        };
+        if std::mem::take(&mut self.stale) {
+            terminal.clear()?;
+        }
+        terminal.draw(|f| render_progress_ui(f, progress, false, 0))?;
+        let height = terminal.size()?.height;
+        execute!(io::stdout(), MoveTo(0, height.saturating_sub(4)), Show)?;
+        Ok(())
+    }
+
+    /// Redraw the whole dashboard next time, after something was printed over it.
+    pub fn invalidate(&mut self) {
+        self.stale = true;
+    }
+
+    /// Let go of the terminal, e.g. when the summary screen takes over.
+    pub fn close(&mut self) {
+        self.terminal = None;
+    }
+}
+
+/// Show the summary screen of a run. Returns whether to retry the failed operations.
+///
+/// Without a terminal the summary is printed as text instead and nothing is retried.
+pub fn review_progress(progress: &Progress) -> anyhow::Result<bool> {
+    if !io::stdout().is_terminal() || !io::stdin().is_terminal() {
+        print_progress_summary(progress);
+        return Ok(false);
+    }
+
+    struct CrosstermEventSource;
+    impl EventSource for CrosstermEventSource {
+        fn read_event(&mut self) -> anyhow::Result<Event> {
+            Ok(event::read()?)
+        }
+    }
+
+    terminal::enable_raw_mode()?;
+    let mut stdout = io::stdout();
+    execute!(stdout, Clear(ClearType::All))?;
+    let backend = CrosstermBackend::new(stdout);
+    let mut terminal = Terminal::new(backend)?;
+
+    let mut event_src = CrosstermEventSource;
+    let res = review_progress_with(&mut terminal, &mut event_src, progress);
+
+    // Restore terminal in all cases
+    terminal::disable_raw_mode()?;
+    terminal.clear()?;
+    drop(terminal);
+
+    // Leave the outcome in the scrollback once the screen is gone
+    if let Ok(false) = res {
+        print_progress_summary(progress);
+    }
+    res
+}
+
+/// Summary screen with dependency injection for testing.
+///
+/// `r` retries when something failed, ↑/↓ scroll the details, Enter/ESC/`q` exit.
+pub fn review_progress_with<B: Backend, E: EventSource>(
+    terminal: &mut Terminal<B>,
+    events: &mut E,
+    progress: &Progress,
+) -> anyhow::Result<bool> {
+    let mut scroll: u16 = 0;
+    let can_retry = progress.counts().failed > 0;
+
+    loop {
+        terminal.draw(|f| render_progress_ui(f, progress, true, scroll))?;
+
+        if let Event::Key(key) = events.read_event()? {
+            if key.kind != KeyEventKind::Press {
+                continue;
+            }
+            // Check for Ctrl+C - exit immediately
+            if key.code == KeyCode::Char('c') && key.modifiers == KeyModifiers::CONTROL {
+                terminal::disable_raw_mode()?;
+                std::process::exit(0);
+            }
+
+            match key.code {
+                KeyCode::Char('r') | KeyCode::Char('R') if can_retry => return Ok(true),
+                KeyCode::Enter | KeyCode::Esc | KeyCode::Char('q') => return Ok(false),
+                KeyCode::Up => scroll = scroll.saturating_sub(1),
+                KeyCode::Down if usize::from(scroll) + 1 < progress.details.len() => {
+                    scroll += 1;
+                }
+                _ => {}
+            }
+        }
+    }
+}
+
+/// Color of a status cell.
+fn status_color(status: CellStatus) -> Color {
+    match status {
+        CellStatus::Pending => Color::DarkGray,
+        CellStatus::Running => Color::Yellow,
+        CellStatus::Created => Color::LightGreen,
+        CellStatus::Updated => Color::Green,
+        CellStatus::Skipped => Color::Blue,
+        CellStatus::Failed => Color::Red,
+    }
+}
+
+/// Render the progress dashboard: overall counts, one row per repository with a
+/// status cell per secret, and the error details. `finished` turns it into the
+/// summary screen, with the details scrolled by `detail_scroll` lines.
+pub fn render_progress_ui(f: &mut Frame, progress: &Progress, finished: bool, detail_scroll: u16) {
+    let size = f.size();
+    let counts = progress.counts();
+
+    // Details get up to 8 lines, and at least room for their borders
+    let details_height = (progress.details.len() as u16 + 2).clamp(3, 8);
+    let chunks = Layout::default()
+        .constraints([
+            Constraint::Length(3),              // Overall counts
+            Constraint::Min(3),                 // Repositories
+            Constraint::Length(details_height), // Error details
+            Constraint::Length(3),              // Instructions
+        ])
+        .split(size);
+
+    // Overall counts
+    let ratio = if counts.total() == 0 {
+        1.0
+    } else {
+        counts.done() as f64 / counts.total() as f64
+    };
+    let label = format!(
+        "{}/{} done | {} created | {} updated | {} skipped | {} failed",
+        counts.done(),
+        counts.total(),
+        counts.created,
+        counts.updated,
+        counts.skipped,
+        counts.failed
+    );
+    let gauge_color = if counts.failed > 0 {
+        Color::Red
+    } else {
+        Color::Green
+    };
+    f.render_widget(
+        Gauge::default()
+            .block(Block::default().borders(Borders::ALL).title(if finished {
+                "Summary"
+            } else {
+                "Progress"
+            }))
+            .gauge_style(Style::default().fg(gauge_color))
+            .ratio(ratio)
+            .label(label),
+        chunks[0],
+    );
+
+    // One row per repository, one column per secret
+    let name_width = progress
+        .repositories
+        .iter()
+        .map(|r| r.chars().count())
+        .max()
+        .unwrap_or(0)
+        .max("Repository".len()) as u16;
+    let mut widths = vec![Constraint::Length(name_width)];
+    widths.extend(progress.secrets.iter().map(|s| {
+        Constraint::Length(s.chars().count().max(CellStatus::Pending.label().len()) as u16)
+    }));
+    let header = Row::new(
+        std::iter::once(Cell::from("Repository"))
+            .chain(progress.secrets.iter().map(|s| Cell::from(s.as_str())))
+            .collect::<Vec<_>>(),
+    )
+    .style(
+        Style::default()
+            .fg(Color::Cyan)
+            .add_modifier(Modifier::BOLD),
+    );
+    let rows: Vec<Row> = progress
+        .repositories
+        .iter()
+        .enumerate()
+        .map(|(r, repository)| {
+            let cells = progress.cells[r].iter().map(|status| {
+                Cell::from(status.label()).style(Style::default().fg(status_color(*status)))
+            });
+            Row::new(std::iter::once(Cell::from(repository.as_str())).chain(cells))
+        })
+        .collect();
+    // Keep the repository being worked on in view
+    let mut state = TableState::default();
+    state.select(
+        progress
+            .cells
+            .iter()
+            .position(|row| row.contains(&CellStatus::Running)),
+    );
+    f.render_stateful_widget(
+        Table::new(rows, widths).header(header).block(
+            Block::default()
+                .borders(Borders::ALL)
+                .title(format!("Repositories ({})", progress.repositories.len())),
+        ),
+        chunks[1],
+        &mut state,
+    );
+
+    // Error details
+    let lines: Vec<Line> = if progress.details.is_empty() {
+        vec![Line::styled(
+            "No errors",
+            Style::default().fg(Color::DarkGray),
+        )]
+    } else {
+        progress
+            .details
+            .iter()
+            .map(|d| {
+                let (symbol, color) = if d.is_error {
+                    ("✗", Color::Red)
+                } else {
+                    ("ℹ", Color::Cyan)
+                };
+                Line::styled(
+                    format!("{} {} / {}: {}", symbol, d.repository, d.secret, d.message),
+                    Style::default().fg(color),
+                )
+            })
+            .collect()
+    };
+    f.render_widget(
+        Paragraph::new(lines)
+            .block(Block::default().borders(Borders::ALL).title("Details"))
+            .wrap(Wrap { trim: false })
+            .scroll((detail_scroll, 0)),
+        chunks[2],
+    );
+
+    // Instructions
+    let instruction_text = if !finished {
+        "Working... overwrite questions appear at the bottom".to_string()
+    } else if counts.failed > 0 {
+        format!(
+            "r: retry {} failed | ↑/↓: scroll details | Enter/q: exit",
+            counts.failed
+        )
+    } else {
+        "↑/↓: scroll details | Enter/q: exit".to_string()
+    };
+    f.render_widget(
+        Paragraph::new(instruction_text)
+            .block(Block::default().borders(Borders::ALL).title("Instructions"))
+            .style(Style::default().fg(Color::DarkGray))
+            .alignment(Alignment::Center),
+        chunks[3],
+    );
+}
+
+/// Print the outcome of a run as text.
+fn print_progress_summary(progress: &Progress) {
+    let counts = progress.counts();
+
+    println!("\n{}", "=".repeat(60).bright_black());
+    println!("{}", "Overall Summary".bright_cyan().bold());
+    println!("{}", "=".repeat(60).bright_black());
+    println!(
+        "{} {}",
+        "Total operations:".cyan(),
+        counts.total().to_string().bright_cyan()
+    );
+    println!(
+        "{} {} {}, {} {}",
+        "Successful:".green(),
+        counts.created.to_string().bright_green(),
+        "created".green(),
+        counts.updated.to_string().bright_green(),
+        "updated".green()
+    );
+    println!(
+        "{} {}",
+        "Skipped:".yellow(),
+        counts.skipped.to_string().bright_yellow()
+    );
+    println!(
+        "{} {}",
+        "Failed:".red(),
+        counts.failed.to_string().bright_red()
+    );
+
+    println!("\n{}", "Per-repository breakdown:".cyan());
+    for (r, repository) in progress.repositories.iter().enumerate() {
+        let repo_counts = progress.repository_counts(r);
+        println!(
+            "  {}: {} {}, {} {}, {} {}",
+            repository.bright_cyan(),
+            (repo_counts.created + repo_counts.updated)
+                .to_string()
+                .bright_green(),
+            "successful".green(),
+            repo_counts.skipped.to_string().bright_yellow(),
+            "skipped".yellow(),
+            repo_counts.failed.to_string().bright_red(),
+            "failed".red()
+        );
+    }
+
+    if counts.failed > 0 {
+        println!("\n{}", "Failed operations:".red().bold());
+        for detail in progress.details.iter().filter(|d| d.is_error) {
+            println!(
+                "  {} {} {} {}",
+                "✗".red(),
+                detail.secret.bright_red(),
+                format!("in {}", detail.repository).red(),
+                format!("→ {}", detail.message).bright_red()
+            );
+        }
+    }
+    for detail in progress.details.iter().filter(|d| !d.is_error) {
+        println!(
+            "{} {}",
+            "ℹ".cyan(),
+            format!("{} in {}", detail.message, detail.repository).cyan()
+        );
+    }
+}
+
 /// Present interactive menu for selecting one or more repositories.
 /// Returns vector of selected repository indices.
 /// Select repositories from a list using an interactive TUI.
 ///
 /// Displays a TUI where users can select multiple repositories using spacebar
-/// to toggle selection and Enter to confirm. Includes a "Select All" option.
+/// to toggle selection and Enter to confirm. Includes a "Select All" option and
+/// one entry per group or tag defined in the config.
 ///
 /// # Arguments
 ///
@@ -641,30 +1840,141 @@ pub fn select_repositories(
     let backend = CrosstermBackend::new(stdout);
     let mut terminal = Terminal::new(backend)?;
 
+    // Pre-check the repositories picked last time; a broken state file only loses that
+    let last_path = crate::paths::get_last_selection_path();
+    let last = crate::last_selection::load(&last_path)
+        .map(|saved| saved.indices(repositories))
+        .unwrap_or_default();
+
     let mut event_src = CrosstermEventSource;
-    let res = select_repositories_with(&mut terminal, &mut event_src, repositories);
+    let res = select_repositories_with_last(&mut terminal, &mut event_src, repositories, &last);
 
     // Restore terminal in all cases
     terminal::disable_raw_mode()?;
     drop(terminal);
 
+    if let Ok(indices) = &res
+        && let Err(e) = crate::last_selection::save(&last_path, repositories, indices)
+    {
+        eprintln!("{} {:#}", "⚠ Could not save the selection:".yellow(), e);
+    }
+
     res
 }
 
+/// Preview repositories found by `config import` and choose which ones to add.
+///
+/// Unlike [`select_repositories`], the checklist is always shown, even for a
+/// single candidate, so nothing is imported without confirmation.
+pub fn select_import(candidates: &[crate::config::Repository]) -> anyhow::Result<Vec<usize>> {
+    struct CrosstermEventSource;
+    impl EventSource for CrosstermEventSource {
+        fn read_event(&mut self) -> anyhow::Result<Event> {
+            Ok(event::read()?)
+        }
+    }
+
+    terminal::enable_raw_mode()?;
+    let mut stdout = io::stdout();
+    execute!(stdout, Clear(ClearType::All))?;
+    let backend = CrosstermBackend::new(stdout);
+    let mut terminal = Terminal::new(backend)?;
+
+    let mut event_src = CrosstermEventSource;
+    let res = select_repositories_with(&mut terminal, &mut event_src, candidates);
+
+    terminal::disable_raw_mode()?;
+    drop(terminal);
+
+    res
+}
+
+/// Filter typed after `/` in the repository selection.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct RepoFilter {
+    /// Fuzzy query matched against each repository's display name.
+    pub query: String,
+    /// Whether typed characters currently go to the query.
+    pub editing: bool,
+}
+
+impl RepoFilter {
+    /// Whether the list is currently narrowed down.
+    pub fn is_active(&self) -> bool {
+        !self.query.is_empty()
+    }
+
+    /// Whether `repository` is shown with this filter.
+    pub fn matches(&self, repository: &crate::config::Repository) -> bool {
+        crate::selector::fuzzy_matches(&self.query, &repository.display_name())
+    }
+}
+
+/// One row of the selection list.
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum SelectionRow {
+    /// "Select All", or "Select All Visible" while filtering.
+    All,
+    /// A group or tag, by index into `collect_groups`.
+    Group(usize),
+    /// A repository, by index into the repository list.
+    Repo(usize),
+}
+
+/// Rows shown for `filter`; groups and tags are hidden while filtering.
+fn selection_rows(
+    repositories: &[crate::config::Repository],
+    group_count: usize,
+    filter: &RepoFilter,
+) -> Vec<SelectionRow> {
+    let mut rows = vec![SelectionRow::All];
+    if filter.is_active() {
+        rows.extend(
+            (0..repositories.len())
+                .filter(|&i| filter.matches(&repositories[i]))
+                .map(SelectionRow::Repo),
+        );
+    } else {
+        rows.extend((0..group_count).map(SelectionRow::Group));
+        rows.extend((0..repositories.len()).map(SelectionRow::Repo));
+    }
+    rows
+}
+
 /// Select repositories with dependency injection for testing.
+///
+/// `/` filters the list; selections of repositories hidden by the filter are kept.
 pub fn select_repositories_with<B: Backend, E: EventSource>(
     terminal: &mut Terminal<B>,
     events: &mut E,
     repositories: &[crate::config::Repository],
+) -> anyhow::Result<Vec<usize>> {
+    select_repositories_with_last(terminal, events, repositories, &[])
+}
+
+/// Select repositories with the indices in `last` pre-checked; `l` restores them.
+pub fn select_repositories_with_last<B: Backend, E: EventSource>(
+    terminal: &mut Terminal<B>,
+    events: &mut E,
+    repositories: &[crate::config::Repository],
+    last: &[usize],
 ) -> anyhow::Result<Vec<usize>> {
     // State: index 0 is "Select All", indices 1.. are repositories
     let mut selected = vec![false; repositories.len() + 1];
+    for &i in last {
+        selected[i + 1] = true;
+    }
+    selected[0] = !repositories.is_empty() && selected[1..].iter().all(|&s| s);
     let mut list_state = ListState::default();
     list_state.select(Some(0)); // Start with "Select All" selected
+    let mut filter = RepoFilter::default();
+
+    // Rows: "Select All", then one row per group/tag, then the repositories
+    let groups = crate::config::collect_groups(repositories);
 
     loop {
         terminal.draw(|frame| {
-            render_selection_ui(frame, repositories, &selected, &mut list_state);
+            render_selection_ui(frame, repositories, &selected, &mut list_state, &filter);
         })?;
 
         // Handle input
@@ -678,6 +1988,17 @@ pub fn select_repositories_with<B: Backend, E: EventSource>(
                 std::process::exit(0);
             }
 
+            let rows = selection_rows(repositories, groups.len(), &filter);
+            let visible: Vec<usize> = rows
+                .iter()
+                .filter_map(|row| match row {
+                    SelectionRow::Repo(i) => Some(*i),
+                    _ => None,
+                })
+                .collect();
+            let last_row = rows.len() - 1;
+            let query_before = filter.query.clone();
+
             match key.code {
                 KeyCode::Up => {
                     if let Some(selected_idx) = list_state.selected()
@@ -687,29 +2008,63 @@ pub fn select_repositories_with<B: Backend, E: EventSource>(
                     }
                 }
                 KeyCode::Down => {
-                    if let Some(selected_idx) = list_state.selected() {
-                        if selected_idx < repositories.len() {
-                            list_state.select(Some(selected_idx + 1));
-                        }
+                    if let Some(selected_idx) = list_state.selected()
+                        && selected_idx < last_row
+                    {
+                        list_state.select(Some(selected_idx + 1));
+                    }
+                }
+                // While filtering, typed characters edit the query; Space still toggles
+                KeyCode::Char(c) if filter.editing && c != ' ' => filter.query.push(c),
+                KeyCode::Backspace if filter.editing => {
+                    filter.query.pop();
+                }
+                KeyCode::Enter if filter.editing => filter.editing = false,
+                KeyCode::Esc if filter.editing || filter.is_active() => {
+                    filter = RepoFilter::default();
+                }
+                KeyCode::Char('/') => filter.editing = true,
+                KeyCode::Char('a') => {
+                    // Select every visible repository
+                    for &i in &visible {
+                        selected[i + 1] = true;
+                    }
+                }
+                KeyCode::Char('i') => {
+                    // Invert the selection of the visible repositories
+                    for &i in &visible {
+                        selected[i + 1] = !selected[i + 1];
+                    }
+                }
+                KeyCode::Char('l') if !last.is_empty() => {
+                    // Use the last selection again
+                    selected.iter_mut().for_each(|s| *s = false);
+                    for &i in last {
+                        selected[i + 1] = true;
                     }
                 }
                 KeyCode::Char(' ') => {
                     if let Some(cursor_pos) = list_state.selected() {
-                        if cursor_pos == 0 {
-                            // Toggle "Select All"
-                            let all_selected = selected[1..].iter().all(|&s| s);
-                            let new_state = !all_selected;
-                            // Set all repository selections to match "Select All"
-                            for selected_item in selected.iter_mut().skip(1) {
-                                *selected_item = new_state;
+                        match rows[cursor_pos.min(last_row)] {
+                            SelectionRow::All => {
+                                // Toggle "Select All" (only the visible repositories while filtering)
+                                let all_selected = visible.iter().all(|&i| selected[i + 1]);
+                                for &i in &visible {
+                                    selected[i + 1] = !all_selected;
+                                }
+                            }
+                            SelectionRow::Group(group) => {
+                                // Toggle every member of the group or tag
+                                let members = &groups[group].members;
+                                let all_selected = members.iter().all(|&i| selected[i + 1]);
+                                for &i in members {
+                                    selected[i + 1] = !all_selected;
+                                }
+                            }
+                            SelectionRow::Repo(i) => {
+                                // Toggle individual repository
+                                selected[i + 1] = !selected[i + 1];
                             }
-                            selected[0] = new_state;
-                        } else {
-                            // Toggle individual repository
-                            selected[cursor_pos] = !selected[cursor_pos];
-                            // Update "Select All" state based on all repositories
-                            let all_selected = selected[1..].iter().all(|&s| s);
-                            selected[0] = all_selected;
                         }
                     }
                 }
@@ -721,6 +2076,13 @@ pub fn select_repositories_with<B: Backend, E: EventSource>(
                 }
                 _ => {}
             }
+
+            // Update "Select All" state based on all repositories
+            selected[0] = selected[1..].iter().all(|&s| s);
+            // A new query changes the rows, so start again from the top
+            if filter.query != query_before {
+                list_state.select(Some(0));
+            }
         }
     }
 
@@ -738,11 +2100,17 @@ pub fn select_repositories_with<B: Backend, E: EventSource>(
 }
 
 /// Render the selection UI using ratatui.
+///
+/// `selected` holds the "Select All" state at index 0 followed by one entry per
+/// repository. Group and tag rows are rendered between "Select All" and the
+/// repositories and are checked when all of their members are selected. While
+/// `filter` is active only matching repositories are listed.
 pub fn render_selection_ui(
     f: &mut Frame,
     repositories: &[crate::config::Repository],
     selected: &[bool],
     list_state: &mut ListState,
+    filter: &RepoFilter,
 ) {
     let size = f.size();
 
@@ -758,26 +2126,50 @@ pub fn render_selection_ui(
         ])
         .split(size);
 
+    let groups = crate::config::collect_groups(repositories);
+    let rows = selection_rows(repositories, groups.len(), filter);
+    let visible_count = rows.len() - 1;
+
     // Build list items
-    let mut items = Vec::new();
-
-    // "Select All" item
-    let checkbox = if selected[0] { "[x]" } else { "[ ]" };
-    let select_all_text = format!("{} Select All", checkbox);
-    items.push(ListItem::new(select_all_text));
-
-    // Repository items
-    for (i, repo) in repositories.iter().enumerate() {
-        let idx = i + 1;
-        let checkbox = if selected[idx] { "[x]" } else { "[ ]" };
-        let repo_text = format!("{} {}", checkbox, repo.display_name());
-        items.push(ListItem::new(repo_text));
-    }
+    let items: Vec<ListItem> = rows
+        .iter()
+        .map(|row| match *row {
+            SelectionRow::All if filter.is_active() => {
+                let all_selected = rows.iter().all(|row| match row {
+                    SelectionRow::Repo(i) => selected[i + 1],
+                    _ => true,
+                });
+                let checkbox = if all_selected && visible_count > 0 {
+                    "[x]"
+                } else {
+                    "[ ]"
+                };
+                ListItem::new(format!("{} Select All Visible", checkbox))
+            }
+            SelectionRow::All => {
+                let checkbox = if selected[0] { "[x]" } else { "[ ]" };
+                ListItem::new(format!("{} Select All", checkbox))
+            }
+            SelectionRow::Group(group) => {
+                let group = &groups[group];
+                let all_selected = group.members.iter().all(|&i| selected[i + 1]);
+                let checkbox = if all_selected { "[x]" } else { "[ ]" };
+                ListItem::new(Span::styled(
+                    format!("{} {}", checkbox, group.display_name()),
+                    Style::default().fg(Color::Magenta),
+                ))
+            }
+            SelectionRow::Repo(i) => {
+                let checkbox = if selected[i + 1] { "[x]" } else { "[ ]" };
+                ListItem::new(format!("{} {}", checkbox, repositories[i].display_name()))
+            }
+        })
+        .collect();
 
-    // Count selected repositories
+    // Count selected repositories, including any hidden by the filter
     let selected_count: usize = selected[1..].iter().map(|&s| s as usize).sum();
     let total_count = repositories.len();
-    let list_title = if selected_count == total_count && selected[0] {
+    let mut list_title = if selected_count == total_count && selected[0] {
         format!("Repositories (All {} selected)", total_count)
     } else if selected_count > 0 {
         format!(
@@ -787,6 +2179,13 @@ pub fn render_selection_ui(
     } else {
         "Repositories".to_string()
     };
+    if filter.is_active() || filter.editing {
+        let cursor = if filter.editing { "│" } else { "" };
+        list_title = format!(
+            "{} - filter: /{}{} ({} of {} shown)",
+            list_title, filter.query, cursor, visible_count, total_count
+        );
+    }
 
     // Create and render list
     let list = List::new(items)
@@ -801,13 +2200,19 @@ pub fn render_selection_ui(
     f.render_stateful_widget(list, chunks[0], &mut *list_state);
 
     // Instructions with selection status
-    let instruction_text = if selected_count > 0 {
+    let instruction_text = if filter.editing {
+        format!(
+            "Type to filter | ↑/↓: navigate | Space: toggle | Enter: done | ESC: clear filter ({} selected)",
+            selected_count
+        )
+    } else if selected_count > 0 {
         format!(
-            "↑/↓: navigate | Space: toggle | Enter: confirm ({} selected)",
+            "↑/↓: navigate | Space: toggle | /: filter | a: all visible | i: invert | l: last selection | Enter: confirm ({} selected)",
             selected_count
         )
     } else {
-        "↑/↓: navigate | Space: toggle | Enter: confirm".to_string()
+        "↑/↓: navigate | Space: toggle | /: filter | a: all visible | i: invert | l: last selection | Enter: confirm"
+            .to_string()
     };
     let instructions = Paragraph::new(instruction_text)
         .block(Block::default().borders(Borders::ALL).title("Instructions"))
@@ -904,10 +2309,8 @@ pub fn prompt_confirm_with<B: Backend, E: EventSource>(
                 KeyCode::Left | KeyCode::Up => {
                     cursor_pos = cursor_pos.saturating_sub(1);
                 }
-                KeyCode::Right | KeyCode::Down => {
-                    if cursor_pos < 1 {
-                        cursor_pos += 1;
-                    }
+                KeyCode::Right | KeyCode::Down if cursor_pos < 1 => {
+                    cursor_pos += 1;
                 }
                 KeyCode::Char('y') | KeyCode::Char('Y') | KeyCode::Enter if cursor_pos == 0 => {
                     return Ok(true);
@@ -965,12 +2368,16 @@ enum InputField {
     Owner,
     Name,
     Alias,
+    Groups,
+    Tags,
 }
 
 struct InputState {
     owner: String,
     name: String,
     alias: String,
+    groups: String,
+    tags: String,
     active_field: InputField,
     is_edit: bool,
     edit_index: Option<usize>,
@@ -983,6 +2390,8 @@ impl InputState {
             owner: String::new(),
             name: String::new(),
             alias: String::new(),
+            groups: String::new(),
+            tags: String::new(),
             active_field: InputField::Owner,
             is_edit: false,
             edit_index: None,
@@ -995,6 +2404,8 @@ impl InputState {
             owner: repo.owner.clone(),
             name: repo.name.clone(),
             alias: repo.alias.clone().unwrap_or_default(),
+            groups: repo.groups.join(", "),
+            tags: repo.tags.join(", "),
             active_field: InputField::Owner,
             is_edit: true,
             edit_index: Some(index),
@@ -1008,6 +2419,9 @@ impl InputState {
                 c.is_ascii_alphanumeric() || c == '_' || c == '-'
             }
             InputField::Alias => true,
+            InputField::Groups | InputField::Tags => {
+                c.is_ascii_alphanumeric() || c == '_' || c == '-' || c == ',' || c == ' '
+            }
         }
     }
 }
@@ -1070,11 +2484,17 @@ pub fn manage_config_with<B: Backend, E: EventSource>(
                     .repositories
                     .iter()
                     .map(|r| {
-                        let text = if let Some(alias) = &r.alias {
+                        let mut text = if let Some(alias) = &r.alias {
                             format!("{} ({}/{})", alias, r.owner, r.name)
                         } else {
                             format!("{}/{}", r.owner, r.name)
                         };
+                        if !r.groups.is_empty() {
+                            text.push_str(&format!(" [groups: {}]", r.groups.join(", ")));
+                        }
+                        if !r.tags.is_empty() {
+                            text.push_str(&format!(" [tags: {}]", r.tags.join(", ")));
+                        }
                         ListItem::new(text)
                     })
                     .collect()
@@ -1106,7 +2526,7 @@ pub fn manage_config_with<B: Backend, E: EventSource>(
 
             // 2. If in Input mode, render the dialog overlay
             if let AppMode::Input = app_mode {
-                let area = centered_rect(60, 50, size);
+                let area = centered_rect(60, 80, size);
                 f.render_widget(ratatui::widgets::Clear, area); // Clear background under dialog
 
                 let block = Block::default()
@@ -1125,6 +2545,8 @@ pub fn manage_config_with<B: Backend, E: EventSource>(
                             Constraint::Length(3), // Owner
                             Constraint::Length(3), // Name
                             Constraint::Length(3), // Alias
+                            Constraint::Length(3), // Groups
+                            Constraint::Length(3), // Tags
                             Constraint::Length(1), // Error/Message
                         ]
                         .as_ref(),
@@ -1166,11 +2588,23 @@ pub fn manage_config_with<B: Backend, E: EventSource>(
                     InputField::Alias,
                     2,
                 );
+                render_field(
+                    "Groups (Optional, comma-separated)",
+                    &input_state.groups,
+                    InputField::Groups,
+                    3,
+                );
+                render_field(
+                    "Tags (Optional, comma-separated)",
+                    &input_state.tags,
+                    InputField::Tags,
+                    4,
+                );
 
                 f.render_widget(
                     Paragraph::new(input_state.error_msg.as_str())
                         .style(Style::default().fg(Color::Red)),
-                    input_chunks[3],
+                    input_chunks[5],
                 );
             }
         })?;
@@ -1184,35 +2618,31 @@ pub fn manage_config_with<B: Backend, E: EventSource>(
                 AppMode::Browsing => {
                     match key.code {
                         KeyCode::Char('q') | KeyCode::Esc => return Ok(Some(config)),
-                        KeyCode::Up => {
-                            if !config.repositories.is_empty() {
-                                let i = match list_state.selected() {
-                                    Some(i) => {
-                                        if i == 0 {
-                                            config.repositories.len() - 1
-                                        } else {
-                                            i - 1
-                                        }
+                        KeyCode::Up if !config.repositories.is_empty() => {
+                            let i = match list_state.selected() {
+                                Some(i) => {
+                                    if i == 0 {
+                                        config.repositories.len() - 1
+                                    } else {
+                                        i - 1
                                     }
-                                    None => 0,
-                                };
-                                list_state.select(Some(i));
-                            }
+                                }
+                                None => 0,
+                            };
+                            list_state.select(Some(i));
                         }
-                        KeyCode::Down => {
-                            if !config.repositories.is_empty() {
-                                let i = match list_state.selected() {
-                                    Some(i) => {
-                                        if i >= config.repositories.len() - 1 {
-                                            0
-                                        } else {
-                                            i + 1
-                                        }
+                        KeyCode::Down if !config.repositories.is_empty() => {
+                            let i = match list_state.selected() {
+                                Some(i) => {
+                                    if i >= config.repositories.len() - 1 {
+                                        0
+                                    } else {
+                                        i + 1
                                     }
-                                    None => 0,
-                                };
-                                list_state.select(Some(i));
-                            }
+                                }
+                                None => 0,
+                            };
+                            list_state.select(Some(i));
                         }
                         KeyCode::Char('a') => {
                             input_state = InputState::new_add();
@@ -1259,15 +2689,19 @@ pub fn manage_config_with<B: Backend, E: EventSource>(
                             input_state.active_field = match input_state.active_field {
                                 InputField::Owner => InputField::Name,
                                 InputField::Name => InputField::Alias,
-                                InputField::Alias => InputField::Owner,
+                                InputField::Alias => InputField::Groups,
+                                InputField::Groups => InputField::Tags,
+                                InputField::Tags => InputField::Owner,
                             };
                         }
                         KeyCode::BackTab => {
                             // Shift+Tab usually
                             input_state.active_field = match input_state.active_field {
-                                InputField::Owner => InputField::Alias,
+                                InputField::Owner => InputField::Tags,
                                 InputField::Name => InputField::Owner,
                                 InputField::Alias => InputField::Name,
+                                InputField::Groups => InputField::Alias,
+                                InputField::Tags => InputField::Groups,
                             };
                         }
                         KeyCode::Enter => {
@@ -1285,6 +2719,9 @@ pub fn manage_config_with<B: Backend, E: EventSource>(
                                     } else {
                                         Some(input_state.alias.trim().to_string())
                                     },
+                                    groups: crate::config::parse_label_list(&input_state.groups),
+                                    tags: crate::config::parse_label_list(&input_state.tags),
+                                    ..Default::default()
                                 };
 
                                 if let Some(idx) = input_state.edit_index {
@@ -1308,6 +2745,12 @@ pub fn manage_config_with<B: Backend, E: EventSource>(
                             InputField::Alias => {
                                 input_state.alias.pop();
                             }
+                            InputField::Groups => {
+                                input_state.groups.pop();
+                            }
+                            InputField::Tags => {
+                                input_state.tags.pop();
+                            }
                         },
                         KeyCode::Char(c) => {
                             if InputState::validate_char(c, input_state.active_field) {
@@ -1315,6 +2758,8 @@ pub fn manage_config_with<B: Backend, E: EventSource>(
                                     InputField::Owner => input_state.owner.push(c),
                                     InputField::Name => input_state.name.push(c),
                                     InputField::Alias => input_state.alias.push(c),
+                                    InputField::Groups => input_state.groups.push(c),
+                                    InputField::Tags => input_state.tags.push(c),
                                 }
                                 input_state.error_msg.clear();
                             } else {
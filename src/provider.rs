@@ -0,0 +1,167 @@
+//! External secret provider protocol.
+//!
+//! A provider is any executable named `github-secrets-provider-<name>` (or the
+//! `command` configured under `[providers.<name>]`). For each value it is run
+//! once with a JSON request on stdin:
+//!
+//! ```json
+//! {"version": 1, "reference": "Prod/DB", "field": "password", "options": {}}
+//! ```
+//!
+//! and must print a JSON response on stdout, either `{"value": "..."}` or
+//! `{"error": "..."}`. A nonzero exit status is treated as an error, with
+//! stderr included in the message.
+//!
+//! A `command` or `args` from the current directory's config.toml is not run,
+//! since that file comes with whatever directory the tool is started in.
+//! Configure them in the personal config, or point `CONFIG_PATH` at the file.
+
+use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::io::{ErrorKind, Write};
+use std::path::PathBuf;
+use std::process::{Command, Stdio};
+
+/// Protocol version sent in every request.
+pub const PROTOCOL_VERSION: u32 = 1;
+
+/// Prefix of provider executables looked up on `PATH`.
+pub const EXECUTABLE_PREFIX: &str = "github-secrets-provider-";
+
+/// Provider settings from a `[providers.<name>]` table in config.toml.
+#[derive(Debug, Clone, Default, Deserialize, Serialize, PartialEq)]
+#[serde(deny_unknown_fields)]
+pub struct ProviderConfig {
+    /// Executable to run instead of `github-secrets-provider-<name>`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub command: Option<String>,
+    /// Extra command-line arguments passed to the provider.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub args: Vec<String>,
+    /// Provider-specific settings, passed through as `options` in each request.
+    #[serde(default, skip_serializing_if = "toml::Table::is_empty")]
+    pub options: toml::Table,
+    /// The current directory's config file this entry came from, if any
+    /// (see [`crate::paths::untrusted_config_file`]).
+    #[serde(skip)]
+    pub untrusted_source: Option<PathBuf>,
+}
+
+/// Providers configured by name.
+pub type Providers = BTreeMap<String, ProviderConfig>;
+
+/// Request written to the provider's stdin.
+#[derive(Debug, Serialize)]
+pub struct ProviderRequest<'a> {
+    pub version: u32,
+    pub reference: &'a str,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub field: Option<&'a str>,
+    pub options: &'a toml::Table,
+}
+
+/// Response read from the provider's stdout.
+#[derive(Debug, Deserialize)]
+pub struct ProviderResponse {
+    #[serde(default)]
+    pub value: Option<String>,
+    #[serde(default)]
+    pub error: Option<String>,
+}
+
+/// Get the executable used for a provider.
+pub fn executable(name: &str, config: Option<&ProviderConfig>) -> String {
+    config
+        .and_then(|c| c.command.clone())
+        .unwrap_or_else(|| format!("{}{}", EXECUTABLE_PREFIX, name))
+}
+
+/// Fetch a value from a provider.
+///
+/// # Arguments
+///
+/// * `name` - Provider name, e.g. "onepassword"
+/// * `reference` - Provider-specific item path, e.g. "Prod/DB"
+/// * `field` - Optional field within the item, e.g. "password"
+/// * `providers` - Provider settings from the config
+///
+/// # Errors
+///
+/// Returns an error if the provider's command comes from the current
+/// directory's config, or if the provider can't be started, exits with a
+/// nonzero status, prints invalid JSON, or reports an error.
+pub fn fetch(
+    name: &str,
+    reference: &str,
+    field: Option<&str>,
+    providers: &Providers,
+) -> Result<String> {
+    let config = providers.get(name);
+    if let Some(config) = config
+        && let Some(source) = &config.untrusted_source
+        && (config.command.is_some() || !config.args.is_empty())
+    {
+        anyhow::bail!(
+            "Provider '{}' sets a command in {}, the current directory's config; \
+             provider commands only run from your personal config or CONFIG_PATH, \
+             so move [providers.{}] there",
+            name,
+            source.display(),
+            name
+        );
+    }
+    let program = executable(name, config);
+    let default_options = toml::Table::new();
+
+    let request = ProviderRequest {
+        version: PROTOCOL_VERSION,
+        reference,
+        field,
+        options: config.map(|c| &c.options).unwrap_or(&default_options),
+    };
+    let request = serde_json::to_vec(&request)?;
+
+    let mut child = Command::new(&program)
+        .args(config.map(|c| c.args.as_slice()).unwrap_or_default())
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()
+        .with_context(|| {
+            format!(
+                "Failed to start provider '{}' ({}); install it on PATH or set providers.{}.command",
+                name, program, name
+            )
+        })?;
+
+    // A provider may exit without reading its request; judge it by its output.
+    if let Some(mut stdin) = child.stdin.take()
+        && let Err(e) = stdin.write_all(&request)
+        && e.kind() != ErrorKind::BrokenPipe
+    {
+        return Err(e).with_context(|| format!("Failed to send request to provider '{}'", name));
+    }
+
+    let output = child
+        .wait_with_output()
+        .with_context(|| format!("Provider '{}' did not finish", name))?;
+
+    if !output.status.success() {
+        anyhow::bail!(
+            "Provider '{}' failed ({}): {}",
+            name,
+            output.status,
+            String::from_utf8_lossy(&output.stderr).trim()
+        );
+    }
+
+    let response: ProviderResponse = serde_json::from_slice(&output.stdout)
+        .with_context(|| format!("Provider '{}' returned an invalid response", name))?;
+
+    match (response.value, response.error) {
+        (_, Some(error)) => anyhow::bail!("Provider '{}' reported: {}", name, error),
+        (Some(value), None) => Ok(value),
+        (None, None) => anyhow::bail!("Provider '{}' returned neither value nor error", name),
+    }
+}
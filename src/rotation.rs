@@ -0,0 +1,78 @@
+//! Record of secret rotations.
+//!
+//! Every `rotate` run appends an entry to a JSON log under the XDG state
+//! directory (see [`crate::paths::get_rotation_log_path`]), noting when a
+//! secret was rotated and which repositories received the new value. Values
+//! are never recorded.
+
+use anyhow::{Context, Result};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::path::Path;
+
+use crate::config_edit;
+
+/// One rotation of a secret.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct RotationRecord {
+    /// Secret name.
+    pub secret: String,
+    /// RFC 3339 timestamp of the rotation.
+    pub rotated_at: String,
+    /// Repositories (`owner/name`) that received the new value.
+    pub repositories: Vec<String>,
+    /// Repositories (`owner/name`) where the update failed.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub failed: Vec<String>,
+}
+
+impl RotationRecord {
+    /// Create a record timestamped now.
+    pub fn now(secret: &str, repositories: Vec<String>, failed: Vec<String>) -> Self {
+        Self {
+            secret: secret.to_string(),
+            rotated_at: Utc::now().to_rfc3339(),
+            repositories,
+            failed,
+        }
+    }
+
+    /// Parse the rotation timestamp.
+    pub fn rotated_at(&self) -> Option<DateTime<Utc>> {
+        DateTime::parse_from_rfc3339(&self.rotated_at)
+            .ok()
+            .map(|dt| dt.with_timezone(&Utc))
+    }
+}
+
+/// Read all records from the log at `path`, oldest first.
+///
+/// A missing log is treated as empty.
+pub fn load(path: &Path) -> Result<Vec<RotationRecord>> {
+    if !path.exists() {
+        return Ok(Vec::new());
+    }
+    let content = std::fs::read_to_string(path)
+        .with_context(|| format!("Failed to read rotation log {}", path.display()))?;
+    serde_json::from_str(&content)
+        .with_context(|| format!("Rotation log {} is corrupted", path.display()))
+}
+
+/// Append `record` to the log at `path`.
+pub fn append(path: &Path, record: RotationRecord) -> Result<()> {
+    let mut records = load(path)?;
+    records.push(record);
+    config_edit::replace_file(path, &serde_json::to_string_pretty(&records)?)?;
+    Ok(())
+}
+
+/// Get the most recent rotation of `secret` (case-insensitive).
+pub fn last_rotation<'a>(
+    records: &'a [RotationRecord],
+    secret: &str,
+) -> Option<&'a RotationRecord> {
+    records
+        .iter()
+        .rev()
+        .find(|r| r.secret.eq_ignore_ascii_case(secret))
+}
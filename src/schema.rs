@@ -0,0 +1,232 @@
+//! Config schema versioning, strict checks and migration.
+//!
+//! Unknown keys are rejected while deserializing (see [`crate::config`]); this
+//! module adds the checks serde can't express, such as duplicate repositories
+//! and aliases or a misplaced legacy `[repository]` table, reporting each
+//! problem with its line and column. It also
+//! upgrades older config layouts to [`constants::schema::CURRENT_VERSION`].
+
+use anyhow::{Context, Result};
+use std::collections::HashMap;
+use std::ops::Range;
+use toml_edit::{ArrayOfTables, DocumentMut, ImDocument, Item, Table};
+
+use crate::constants;
+
+/// Line and column (both 1-based) of a position in a config file.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Location {
+    pub line: usize,
+    pub column: usize,
+}
+
+impl std::fmt::Display for Location {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "line {}, column {}", self.line, self.column)
+    }
+}
+
+/// Convert a byte offset into a [`Location`].
+pub fn locate(content: &str, offset: usize) -> Location {
+    let before = &content[..offset.min(content.len())];
+    let line = before.matches('\n').count() + 1;
+    let line_start = before.rfind('\n').map(|i| i + 1).unwrap_or(0);
+    Location {
+        line,
+        column: before[line_start..].chars().count() + 1,
+    }
+}
+
+/// Reject config versions newer than this build understands.
+pub fn check_version(version: Option<u32>) -> Result<()> {
+    match version {
+        Some(v) if v > constants::schema::CURRENT_VERSION => anyhow::bail!(
+            "Config version {} is newer than the supported version {}; upgrade github-secrets",
+            v,
+            constants::schema::CURRENT_VERSION
+        ),
+        Some(0) => anyhow::bail!("Config version 0 is not valid; remove the version key"),
+        _ => Ok(()),
+    }
+}
+
+/// Check a config file for duplicate repositories and duplicate aliases.
+///
+/// Repositories are compared case-insensitively by `owner/name`, as GitHub does.
+/// Every problem is listed with its location and the location of the first definition.
+pub fn check_duplicates(content: &str) -> Result<()> {
+    let doc = ImDocument::parse(content).context("Failed to parse config file")?;
+
+    let mut problems = Vec::new();
+    let mut paths: HashMap<String, Location> = HashMap::new();
+    let mut aliases: HashMap<String, Location> = HashMap::new();
+
+    for table in repository_tables(&doc) {
+        if let (Some(owner), Some(name)) = (
+            table.get("owner").and_then(Item::as_str),
+            table.get("name").and_then(Item::as_str),
+        ) {
+            let path = format!("{}/{}", owner, name);
+            let here = item_location(content, table.get("name"), table.span());
+            match paths.get(&path.to_lowercase()) {
+                Some(first) => problems.push(format!(
+                    "{}: duplicate repository '{}' (first defined at {})",
+                    here, path, first
+                )),
+                None => {
+                    paths.insert(path.to_lowercase(), here);
+                }
+            }
+        }
+
+        if let Some(alias) = table.get("alias").and_then(Item::as_str) {
+            let here = item_location(content, table.get("alias"), table.span());
+            match aliases.get(alias) {
+                Some(first) => problems.push(format!(
+                    "{}: duplicate alias '{}' (first defined at {})",
+                    here, alias, first
+                )),
+                None => {
+                    aliases.insert(alias.to_string(), here);
+                }
+            }
+        }
+    }
+
+    if !problems.is_empty() {
+        anyhow::bail!("Invalid config:\n  {}", problems.join("\n  "));
+    }
+    Ok(())
+}
+
+/// Reject a legacy `[repository]` table where it would be misread.
+///
+/// Versioned files list repositories as `[[repositories]]` only, and a legacy
+/// table next to a `[[repositories]]` list would otherwise be ignored.
+pub fn check_legacy_table(content: &str) -> Result<()> {
+    let doc = ImDocument::parse(content).context("Failed to parse config file")?;
+    let Some(table) = doc.get("repository").and_then(Item::as_table) else {
+        return Ok(());
+    };
+    let here = item_location(content, None, table.span());
+
+    if doc.contains_key("repositories") {
+        anyhow::bail!(
+            "{}: `[repository]` can't be combined with `[[repositories]]`; \
+             move it into the `[[repositories]]` list",
+            here
+        );
+    }
+    if doc.contains_key("version") {
+        anyhow::bail!(
+            "{}: versioned configs list repositories as `[[repositories]]`; \
+             run `github-secrets config migrate` to convert `[repository]`",
+            here
+        );
+    }
+    Ok(())
+}
+
+fn repository_tables<'a>(doc: &'a ImDocument<&str>) -> Vec<&'a Table> {
+    let mut tables: Vec<&Table> = Vec::new();
+    if let Some(aot) = doc.get("repositories").and_then(Item::as_array_of_tables) {
+        tables.extend(aot.iter());
+    } else if let Some(table) = doc.get("repository").and_then(Item::as_table) {
+        tables.push(table);
+    }
+    tables
+}
+
+fn item_location(content: &str, item: Option<&Item>, fallback: Option<Range<usize>>) -> Location {
+    let offset = item
+        .and_then(Item::span)
+        .or(fallback)
+        .map(|span| span.start)
+        .unwrap_or(0);
+    locate(content, offset)
+}
+
+/// Upgrade a config file to the current schema version, preserving comments.
+///
+/// Returns `None` when the file is already current. Migration steps run in
+/// order, so a file several versions behind is upgraded one step at a time.
+///
+/// # Errors
+///
+/// Returns an error if the file can't be parsed or is newer than this build supports.
+pub fn migrate(content: &str) -> Result<Option<String>> {
+    let mut doc: DocumentMut = content.parse().context("Failed to parse config file")?;
+
+    let version = match doc.get("version") {
+        None => 0,
+        Some(item) => item
+            .as_integer()
+            .and_then(|v| u32::try_from(v).ok())
+            .context("Config `version` must be a positive integer")?,
+    };
+    if version > constants::schema::CURRENT_VERSION {
+        check_version(Some(version))?;
+    }
+    // A versioned file can still carry a legacy table written by hand.
+    let has_legacy_table = doc.contains_key("repository");
+    if version == constants::schema::CURRENT_VERSION && !has_legacy_table {
+        return Ok(None);
+    }
+
+    if version < 1 || has_legacy_table {
+        migrate_to_v1(&mut doc)?;
+    }
+
+    doc.remove("version");
+    Ok(Some(with_version_line(&doc.to_string())))
+}
+
+/// Put `version = N` at the top of the file, below a leading comment header if there is one.
+fn with_version_line(rendered: &str) -> String {
+    let version_line = format!("version = {}\n", constants::schema::CURRENT_VERSION);
+    let rendered = rendered.trim_start_matches('\n');
+
+    let header_len = rendered
+        .find("\n\n")
+        .filter(|&end| {
+            rendered[..end]
+                .lines()
+                .all(|l| l.trim_start().starts_with('#'))
+        })
+        .map(|end| end + 2)
+        .unwrap_or(0);
+    let (header, body) = rendered.split_at(header_len);
+
+    if body.is_empty() {
+        format!("{}{}", header, version_line)
+    } else {
+        format!("{}{}\n{}", header, version_line, body)
+    }
+}
+
+/// Version 1: the legacy single `[repository]` table becomes `[[repositories]]`.
+///
+/// # Errors
+///
+/// Returns an error if the file also has a `[[repositories]]` list, as it's
+/// unclear whether the legacy entry belongs in it.
+fn migrate_to_v1(doc: &mut DocumentMut) -> Result<()> {
+    if !matches!(doc.get("repository"), Some(Item::Table(_))) {
+        return Ok(());
+    }
+    if doc.contains_key("repositories") {
+        anyhow::bail!(
+            "Config has both `[repository]` and `[[repositories]]`; move the `[repository]` \
+             entry into the `[[repositories]]` list or delete it, then migrate again"
+        );
+    }
+
+    let Some(Item::Table(mut table)) = doc.remove("repository") else {
+        return Ok(());
+    };
+    let mut tables = ArrayOfTables::new();
+    table.set_implicit(false);
+    tables.push(table);
+    doc.insert("repositories", Item::ArrayOfTables(tables));
+    Ok(())
+}
@@ -0,0 +1,206 @@
+//! Secret value references resolved at run time.
+//!
+//! Instead of typing a real value, a secret value can point at where the value lives:
+//!
+//! - `env:VAR` - the value of environment variable `VAR`
+//! - `file:path` - the contents of a file (`~/` is expanded)
+//! - `cmd:<shell command>` - the standard output of a shell command, without trailing newlines
+//! - `provider:<name>/<item>#<field>` - a value fetched from an external provider (see [`crate::provider`])
+//! - `literal:text` - `text` as-is, for literal values that start with one of these prefixes
+//!
+//! Anything else is a literal value. References are resolved just before the
+//! secret is uploaded, so the real value never appears in the terminal.
+
+use anyhow::{Context, Result};
+use std::path::PathBuf;
+use std::process::Command;
+
+use crate::provider::{self, Providers};
+
+const ENV_PREFIX: &str = "env:";
+const FILE_PREFIX: &str = "file:";
+const CMD_PREFIX: &str = "cmd:";
+const PROVIDER_PREFIX: &str = "provider:";
+const LITERAL_PREFIX: &str = "literal:";
+
+/// Where a secret value comes from.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum SecretSource {
+    /// The value itself.
+    Literal(String),
+    /// An environment variable name.
+    Env(String),
+    /// A file whose contents are the value.
+    File(PathBuf),
+    /// A shell command whose standard output is the value.
+    Command(String),
+    /// An item fetched from an external provider.
+    Provider {
+        /// Provider name, e.g. "onepassword".
+        name: String,
+        /// Provider-specific item path, e.g. "Prod/DB".
+        reference: String,
+        /// Optional field within the item, e.g. "password".
+        field: Option<String>,
+    },
+}
+
+impl SecretSource {
+    /// Parse a raw secret value into its source.
+    pub fn parse(raw: &str) -> Self {
+        if let Some(var) = raw.strip_prefix(ENV_PREFIX) {
+            Self::Env(var.trim().to_string())
+        } else if let Some(path) = raw.strip_prefix(FILE_PREFIX) {
+            Self::File(expand_home(path.trim()))
+        } else if let Some(command) = raw.strip_prefix(CMD_PREFIX) {
+            Self::Command(command.trim().to_string())
+        } else if let Some(spec) = raw.strip_prefix(PROVIDER_PREFIX) {
+            let spec = spec.trim();
+            let (path, field) = match spec.rsplit_once('#') {
+                Some((path, field)) => (path, Some(field.to_string())),
+                None => (spec, None),
+            };
+            let (name, reference) = path.split_once('/').unwrap_or((path, ""));
+            Self::Provider {
+                name: name.to_string(),
+                reference: reference.to_string(),
+                field,
+            }
+        } else if let Some(text) = raw.strip_prefix(LITERAL_PREFIX) {
+            Self::Literal(text.to_string())
+        } else {
+            Self::Literal(raw.to_string())
+        }
+    }
+
+    /// Whether the value is looked up at run time rather than given directly.
+    pub fn is_reference(&self) -> bool {
+        !matches!(self, Self::Literal(_))
+    }
+
+    /// Check that the reference is well-formed, without resolving it.
+    pub fn validate(&self) -> Result<()> {
+        match self {
+            Self::Env(var) if var.is_empty() => {
+                anyhow::bail!("env: reference needs a variable name")
+            }
+            Self::File(path) if path.as_os_str().is_empty() => {
+                anyhow::bail!("file: reference needs a path")
+            }
+            Self::Command(command) if command.is_empty() => {
+                anyhow::bail!("cmd: reference needs a command")
+            }
+            Self::Provider {
+                name, reference, ..
+            } if name.is_empty() || reference.is_empty() => {
+                anyhow::bail!("provider: reference must look like provider:<name>/<item>[#field]")
+            }
+            _ => Ok(()),
+        }
+    }
+
+    /// Look up the actual secret value, using `providers` for `provider:` references.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the variable is unset, the file can't be read, the
+    /// command fails to start or exits with a nonzero status, or the provider fails.
+    pub fn resolve(&self, providers: &Providers) -> Result<String> {
+        self.validate()?;
+        match self {
+            Self::Literal(value) => Ok(value.clone()),
+            Self::Env(var) => std::env::var(var)
+                .with_context(|| format!("Environment variable '{}' is not set", var)),
+            Self::File(path) => std::fs::read_to_string(path)
+                .with_context(|| format!("Failed to read secret file {}", path.display())),
+            Self::Command(command) => run_command(command),
+            Self::Provider {
+                name,
+                reference,
+                field,
+            } => provider::fetch(name, reference, field.as_deref(), providers),
+        }
+    }
+}
+
+impl std::fmt::Display for SecretSource {
+    /// Describe the source without revealing literal values.
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Literal(_) => write!(f, "literal value"),
+            Self::Env(var) => write!(f, "{}{}", ENV_PREFIX, var),
+            Self::File(path) => write!(f, "{}{}", FILE_PREFIX, path.display()),
+            Self::Command(command) => write!(f, "{}{}", CMD_PREFIX, command),
+            Self::Provider {
+                name,
+                reference,
+                field,
+            } => {
+                write!(f, "{}{}/{}", PROVIDER_PREFIX, name, reference)?;
+                match field {
+                    Some(field) => write!(f, "#{}", field),
+                    None => Ok(()),
+                }
+            }
+        }
+    }
+}
+
+/// Resolve a raw secret value (reference or literal) into the value to upload.
+pub fn resolve_value(raw: &str, providers: &Providers) -> Result<String> {
+    let source = SecretSource::parse(raw);
+    source
+        .resolve(providers)
+        .with_context(|| format!("Failed to resolve secret value from {}", source))
+}
+
+/// Escape a literal value so it is never taken for a reference.
+///
+/// Values that start with a reference prefix get a `literal:` prefix; any
+/// other value is returned unchanged.
+pub fn escape_literal(value: &str) -> String {
+    let prefixes = [
+        ENV_PREFIX,
+        FILE_PREFIX,
+        CMD_PREFIX,
+        PROVIDER_PREFIX,
+        LITERAL_PREFIX,
+    ];
+    if prefixes.iter().any(|p| value.starts_with(p)) {
+        format!("{}{}", LITERAL_PREFIX, value)
+    } else {
+        value.to_string()
+    }
+}
+
+fn expand_home(path: &str) -> PathBuf {
+    match path.strip_prefix("~/") {
+        Some(rest) => dirs::home_dir()
+            .map(|home| home.join(rest))
+            .unwrap_or_else(|| PathBuf::from(path)),
+        None => PathBuf::from(path),
+    }
+}
+
+fn run_command(command: &str) -> Result<String> {
+    let output = if cfg!(windows) {
+        Command::new("cmd").args(["/C", command]).output()
+    } else {
+        Command::new("sh").args(["-c", command]).output()
+    }
+    .with_context(|| format!("Failed to run command '{}'", command))?;
+
+    if !output.status.success() {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        anyhow::bail!(
+            "Command '{}' failed ({}): {}",
+            command,
+            output.status,
+            stderr.trim()
+        );
+    }
+
+    let stdout = String::from_utf8(output.stdout)
+        .with_context(|| format!("Command '{}' did not print valid UTF-8", command))?;
+    Ok(stdout.trim_end_matches(['\n', '\r']).to_string())
+}
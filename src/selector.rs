@@ -0,0 +1,189 @@
+//! Command-line repository selection by name pattern.
+//!
+//! Patterns given with `--repo` are matched against each configured repository's
+//! `owner/name` path and alias (case-insensitively):
+//!
+//! - `my-org/api` or `Production API` - exact path or alias
+//! - `my-org/service-*` - glob, where `*` matches any run of characters and `?` a single one
+//! - `re:^my-org/(api|web)$` - regular expression
+//! - `!my-org/legacy-*` - any of the above prefixed with `!` excludes the matches
+//!
+//! Included repositories are the union of all positive patterns, groups and tags, or every
+//! configured repository when only exclusions are given. Exclusions are applied last.
+
+use anyhow::{Context, Result};
+use regex::{Regex, RegexBuilder};
+
+use crate::config::{self, Repository};
+
+/// Prefix marking a pattern as a regular expression.
+const REGEX_PREFIX: &str = "re:";
+/// Prefix marking a pattern as an exclusion.
+const NEGATION_PREFIX: char = '!';
+/// Number of suggestions listed when a pattern matches nothing.
+const MAX_SUGGESTIONS: usize = 3;
+
+/// A parsed `--repo` pattern.
+#[derive(Debug, Clone)]
+pub struct RepoPattern {
+    /// The pattern as given on the command line.
+    pub raw: String,
+    /// Whether matching repositories are removed from the selection.
+    pub negated: bool,
+    matcher: Regex,
+}
+
+impl RepoPattern {
+    /// Parse a pattern, compiling globs and exact names into an anchored case-insensitive regex.
+    pub fn parse(raw: &str) -> Result<Self> {
+        let trimmed = raw.trim();
+        let (negated, body) = match trimmed.strip_prefix(NEGATION_PREFIX) {
+            Some(rest) => (true, rest.trim()),
+            None => (false, trimmed),
+        };
+
+        if body.is_empty() {
+            anyhow::bail!("Repository pattern '{}' is empty", raw);
+        }
+
+        let source = match body.strip_prefix(REGEX_PREFIX) {
+            Some(expr) => expr.to_string(),
+            None => glob_to_regex(body),
+        };
+
+        let matcher = RegexBuilder::new(&source)
+            .case_insensitive(true)
+            .build()
+            .with_context(|| format!("Invalid repository pattern '{}'", raw))?;
+
+        Ok(Self {
+            raw: raw.to_string(),
+            negated,
+            matcher,
+        })
+    }
+
+    /// Check whether the pattern matches a repository's path or alias.
+    pub fn matches(&self, repository: &Repository) -> bool {
+        self.matcher.is_match(&repository.path())
+            || repository
+                .alias
+                .as_deref()
+                .is_some_and(|alias| self.matcher.is_match(alias))
+    }
+
+    /// The pattern text without the negation or regex prefix, used for suggestions.
+    fn literal(&self) -> &str {
+        let body = self.raw.trim().trim_start_matches(NEGATION_PREFIX).trim();
+        body.strip_prefix(REGEX_PREFIX).unwrap_or(body)
+    }
+}
+
+/// Translate a glob into an anchored regex; names without wildcards match exactly.
+pub(crate) fn glob_to_regex(glob: &str) -> String {
+    let mut source = String::from("^");
+    for c in glob.chars() {
+        match c {
+            '*' => source.push_str(".*"),
+            '?' => source.push('.'),
+            _ => source.push_str(&regex::escape(&c.to_string())),
+        }
+    }
+    source.push('$');
+    source
+}
+
+/// Whether the characters of `query` appear in `name` in order, ignoring case and
+/// whitespace in the query; `api` matches `my-org/backend-api` and `moapi`.
+pub fn fuzzy_matches(query: &str, name: &str) -> bool {
+    let mut chars = name.chars().flat_map(char::to_lowercase);
+    query
+        .chars()
+        .filter(|c| !c.is_whitespace())
+        .flat_map(char::to_lowercase)
+        .all(|q| chars.any(|c| c == q))
+}
+
+/// List the configured paths and aliases closest to `input`, best match first.
+pub fn closest_names(repositories: &[Repository], input: &str) -> Vec<String> {
+    let needle = input.to_lowercase();
+    let mut scored: Vec<(f64, String)> = repositories
+        .iter()
+        .flat_map(|r| std::iter::once(r.path()).chain(r.alias.clone()))
+        .map(|name| (strsim::jaro_winkler(&needle, &name.to_lowercase()), name))
+        .collect();
+
+    scored.sort_by(|a, b| b.0.total_cmp(&a.0));
+    let mut names: Vec<String> = Vec::new();
+    for (_, name) in scored {
+        if !names.contains(&name) {
+            names.push(name);
+        }
+        if names.len() == MAX_SUGGESTIONS {
+            break;
+        }
+    }
+    names
+}
+
+/// Resolve groups, tags and `--repo` patterns into repository indices.
+///
+/// Indices are returned in config order, the same way the selection menu returns them.
+/// A pattern that matches no repository is an error listing the closest names.
+pub fn select(
+    repositories: &[Repository],
+    groups: &[String],
+    tags: &[String],
+    patterns: &[String],
+) -> Result<Vec<usize>> {
+    let patterns = patterns
+        .iter()
+        .map(|p| RepoPattern::parse(p))
+        .collect::<Result<Vec<_>>>()?;
+
+    for pattern in &patterns {
+        if !repositories.iter().any(|r| pattern.matches(r)) {
+            let suggestions = closest_names(repositories, pattern.literal());
+            anyhow::bail!(
+                "Repository pattern '{}' did not match any configured repository. Closest names: {}",
+                pattern.raw,
+                suggestions.join(", ")
+            );
+        }
+    }
+
+    let (excludes, includes): (Vec<_>, Vec<_>) = patterns.iter().partition(|p| p.negated);
+
+    let mut selected = vec![false; repositories.len()];
+    if includes.is_empty() && groups.is_empty() && tags.is_empty() {
+        selected.iter_mut().for_each(|s| *s = true);
+    } else {
+        for idx in config::select_by_labels(repositories, groups, tags)? {
+            selected[idx] = true;
+        }
+        for (idx, repository) in repositories.iter().enumerate() {
+            if includes.iter().any(|p| p.matches(repository)) {
+                selected[idx] = true;
+            }
+        }
+    }
+
+    for (idx, repository) in repositories.iter().enumerate() {
+        if excludes.iter().any(|p| p.matches(repository)) {
+            selected[idx] = false;
+        }
+    }
+
+    let indices: Vec<usize> = selected
+        .iter()
+        .enumerate()
+        .filter(|(_, s)| **s)
+        .map(|(idx, _)| idx)
+        .collect();
+
+    if indices.is_empty() {
+        anyhow::bail!("No repositories left after applying --repo patterns");
+    }
+
+    Ok(indices)
+}
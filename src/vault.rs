@@ -0,0 +1,307 @@
+//! Local encrypted vault of reusable secret sets.
+//!
+//! The vault is a single JSON file under the XDG data directory (see
+//! [`crate::paths::get_vault_path`]) holding named sets of secrets, e.g.
+//! "datadog" → `DD_API_KEY`, `DD_APP_KEY`. The sets are serialized, then
+//! encrypted with XChaCha20-Poly1305 using a key derived from a passphrase
+//! with Argon2id. A fresh salt and nonce are used on every save, and the KDF
+//! parameters are stored alongside so they can be raised later.
+
+use anyhow::{Context, Result};
+use argon2::{Algorithm, Argon2, Params, Version};
+use base64::Engine;
+use base64::engine::general_purpose::STANDARD;
+use chacha20poly1305::aead::{Aead, KeyInit, Payload};
+use chacha20poly1305::{XChaCha20Poly1305, XNonce};
+use rand::RngCore;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+
+use crate::config_edit;
+use crate::paths;
+use crate::prompt::SecretPair;
+
+/// Vault file format version.
+pub const FORMAT_VERSION: u32 = 1;
+
+/// Environment variable read instead of prompting for the passphrase.
+pub const PASSPHRASE_ENV: &str = "GITHUB_SECRETS_VAULT_PASSPHRASE";
+
+/// Associated data binding the ciphertext to this file format.
+const AAD: &[u8] = b"github-secrets-vault-v1";
+const SALT_LEN: usize = 16;
+const KEY_LEN: usize = 32;
+const NONCE_LEN: usize = 24;
+
+/// Argon2id cost parameters stored in the vault file.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct KdfParams {
+    /// Memory cost in KiB.
+    pub m_cost: u32,
+    /// Number of iterations.
+    pub t_cost: u32,
+    /// Degree of parallelism.
+    pub p_cost: u32,
+}
+
+impl Default for KdfParams {
+    fn default() -> Self {
+        Self {
+            m_cost: Params::DEFAULT_M_COST,
+            t_cost: Params::DEFAULT_T_COST,
+            p_cost: Params::DEFAULT_P_COST,
+        }
+    }
+}
+
+/// On-disk representation of the vault.
+#[derive(Debug, Serialize, Deserialize)]
+struct VaultFile {
+    version: u32,
+    kdf: KdfParams,
+    salt: String,
+    nonce: String,
+    ciphertext: String,
+}
+
+/// Decrypted vault contents: secret sets by name, each mapping keys to values.
+#[derive(Default, Serialize, Deserialize)]
+pub struct Vault {
+    #[serde(default)]
+    sets: BTreeMap<String, BTreeMap<String, String>>,
+    #[serde(skip)]
+    kdf: KdfParams,
+}
+
+impl Vault {
+    /// Open the vault at `path`, or start an empty one if the file does not exist.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the file can't be read or parsed, or the passphrase is wrong.
+    pub fn open(path: &Path, passphrase: &str) -> Result<Self> {
+        if !path.exists() {
+            return Ok(Self::default());
+        }
+
+        let content = std::fs::read_to_string(path)
+            .with_context(|| format!("Failed to read vault {}", path.display()))?;
+        let file: VaultFile = serde_json::from_str(&content)
+            .with_context(|| format!("Vault {} is corrupted", path.display()))?;
+        if file.version != FORMAT_VERSION {
+            anyhow::bail!(
+                "Vault {} has unsupported format version {}",
+                path.display(),
+                file.version
+            );
+        }
+
+        let salt = STANDARD.decode(&file.salt).context("Invalid vault salt")?;
+        let nonce: [u8; NONCE_LEN] = STANDARD
+            .decode(&file.nonce)
+            .ok()
+            .and_then(|n| n.try_into().ok())
+            .context("Invalid vault nonce")?;
+        let ciphertext = STANDARD
+            .decode(&file.ciphertext)
+            .context("Invalid vault data")?;
+
+        let cipher = XChaCha20Poly1305::new(&derive_key(passphrase, &salt, file.kdf)?.into());
+        let plaintext = cipher
+            .decrypt(
+                &XNonce::from(nonce),
+                Payload {
+                    msg: &ciphertext,
+                    aad: AAD,
+                },
+            )
+            .map_err(|_| anyhow::anyhow!("Wrong passphrase or damaged vault"))?;
+
+        let mut vault: Vault =
+            serde_json::from_slice(&plaintext).context("Vault contents are corrupted")?;
+        vault.kdf = file.kdf;
+        Ok(vault)
+    }
+
+    /// Encrypt the vault with `passphrase` and write it to `path` atomically.
+    ///
+    /// On Unix the file is only readable by the current user.
+    pub fn save(&self, path: &Path, passphrase: &str) -> Result<()> {
+        let mut salt = [0u8; SALT_LEN];
+        let mut nonce = [0u8; NONCE_LEN];
+        rand::thread_rng().fill_bytes(&mut salt);
+        rand::thread_rng().fill_bytes(&mut nonce);
+
+        let plaintext = serde_json::to_vec(self)?;
+        let cipher = XChaCha20Poly1305::new(&derive_key(passphrase, &salt, self.kdf)?.into());
+        let ciphertext = cipher
+            .encrypt(
+                &XNonce::from(nonce),
+                Payload {
+                    msg: &plaintext,
+                    aad: AAD,
+                },
+            )
+            .map_err(|_| anyhow::anyhow!("Failed to encrypt vault"))?;
+
+        let file = VaultFile {
+            version: FORMAT_VERSION,
+            kdf: self.kdf,
+            salt: STANDARD.encode(salt),
+            nonce: STANDARD.encode(nonce),
+            ciphertext: STANDARD.encode(ciphertext),
+        };
+        config_edit::replace_private_file(path, &serde_json::to_string_pretty(&file)?)
+    }
+
+    /// Use different Argon2id parameters on the next save.
+    pub fn set_kdf_params(&mut self, kdf: KdfParams) {
+        self.kdf = kdf;
+    }
+
+    /// Names of all sets, sorted.
+    pub fn set_names(&self) -> Vec<String> {
+        self.sets.keys().cloned().collect()
+    }
+
+    /// Get the secrets of a set, sorted by key.
+    pub fn get(&self, name: &str) -> Option<Vec<SecretPair>> {
+        self.sets.get(name).map(|set| {
+            set.iter()
+                .map(|(key, value)| SecretPair {
+                    key: key.clone(),
+                    value: value.clone(),
+                })
+                .collect()
+        })
+    }
+
+    /// Store `secrets` as set `name`, replacing any set with that name.
+    ///
+    /// Returns `true` if an existing set was replaced.
+    pub fn insert(&mut self, name: &str, secrets: &[SecretPair]) -> Result<bool> {
+        validate_set_name(name)?;
+        let set = secrets
+            .iter()
+            .map(|s| (s.key.clone(), s.value.clone()))
+            .collect();
+        Ok(self.sets.insert(name.to_string(), set).is_some())
+    }
+
+    /// Rename a set.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if `old` does not exist or `new` is already taken.
+    pub fn rename(&mut self, old: &str, new: &str) -> Result<()> {
+        validate_set_name(new)?;
+        if self.sets.contains_key(new) {
+            anyhow::bail!("Secret set '{}' already exists", new);
+        }
+        let set = self
+            .sets
+            .remove(old)
+            .with_context(|| format!("Secret set '{}' not found", old))?;
+        self.sets.insert(new.to_string(), set);
+        Ok(())
+    }
+
+    /// Remove a set.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the set does not exist.
+    pub fn remove(&mut self, name: &str) -> Result<()> {
+        self.sets
+            .remove(name)
+            .map(|_| ())
+            .with_context(|| format!("Secret set '{}' not found", name))
+    }
+}
+
+/// An unlocked vault together with where and how to save it.
+///
+/// Used by the secret input TUI, which asks for the passphrase on first use.
+pub struct VaultSession {
+    path: PathBuf,
+    unlocked: Option<(Vault, String)>,
+}
+
+impl VaultSession {
+    /// Create a locked session for the vault at `path`.
+    pub fn new(path: PathBuf) -> Self {
+        Self {
+            path,
+            unlocked: None,
+        }
+    }
+
+    /// Create a locked session for the default vault location.
+    pub fn default_location() -> Self {
+        Self::new(paths::get_vault_path())
+    }
+
+    /// Whether the vault file exists yet.
+    pub fn exists(&self) -> bool {
+        self.path.exists()
+    }
+
+    /// Whether the passphrase has been entered.
+    pub fn is_unlocked(&self) -> bool {
+        self.unlocked.is_some()
+    }
+
+    /// Unlock the vault with `passphrase`.
+    pub fn unlock(&mut self, passphrase: &str) -> Result<()> {
+        let vault = Vault::open(&self.path, passphrase)?;
+        self.unlocked = Some((vault, passphrase.to_string()));
+        Ok(())
+    }
+
+    /// Get the unlocked vault.
+    pub fn vault(&self) -> Result<&Vault> {
+        self.unlocked
+            .as_ref()
+            .map(|(vault, _)| vault)
+            .context("Vault is locked")
+    }
+
+    /// Store `secrets` as set `name` and save the vault.
+    ///
+    /// Returns `true` if an existing set was replaced.
+    pub fn save_set(&mut self, name: &str, secrets: &[SecretPair]) -> Result<bool> {
+        let (vault, passphrase) = self.unlocked.as_mut().context("Vault is locked")?;
+        let replaced = vault.insert(name, secrets)?;
+        vault.save(&self.path, passphrase)?;
+        Ok(replaced)
+    }
+}
+
+/// Read the vault passphrase from [`PASSPHRASE_ENV`] or the terminal.
+pub fn read_passphrase(prompt: &str) -> Result<String> {
+    if let Ok(passphrase) = std::env::var(PASSPHRASE_ENV) {
+        return Ok(passphrase);
+    }
+    rpassword::prompt_password(prompt).context("Failed to read passphrase")
+}
+
+fn validate_set_name(name: &str) -> Result<()> {
+    if name.trim().is_empty() {
+        anyhow::bail!("Secret set name cannot be empty");
+    }
+    if name.trim() != name {
+        anyhow::bail!("Secret set name cannot start or end with whitespace");
+    }
+    Ok(())
+}
+
+fn derive_key(passphrase: &str, salt: &[u8], kdf: KdfParams) -> Result<[u8; KEY_LEN]> {
+    let params = Params::new(kdf.m_cost, kdf.t_cost, kdf.p_cost, Some(KEY_LEN))
+        .map_err(|e| anyhow::anyhow!("Invalid vault KDF parameters: {}", e))?;
+    let mut key = [0u8; KEY_LEN];
+    Argon2::new(Algorithm::Argon2id, Version::V0x13, params)
+        .hash_password_into(passphrase.as_bytes(), salt, &mut key)
+        .map_err(|e| anyhow::anyhow!("Failed to derive vault key: {}", e))?;
+    Ok(key)
+}
@@ -0,0 +1,198 @@
+use std::sync::Arc;
+
+use anyhow::Result;
+use chrono::Duration;
+
+use github_secrets::app::{AgeOptions, AgeReport, App, RunOptions};
+use github_secrets::config;
+use github_secrets::policy::{self, RotationPolicy, parse_duration};
+
+mod common;
+use common::{FakeGitHub, NoopRateLimiter};
+
+type SecretsByRepo<'a> = &'a [(&'a str, &'a [(&'a str, Option<i64>)])];
+
+/// Secrets by repository; repositories not listed here can't be listed.
+fn github(secrets: SecretsByRepo) -> FakeGitHub {
+    secrets
+        .iter()
+        .fold(FakeGitHub::default(), |github, (repo, list)| {
+            github.with_secrets(repo, list)
+        })
+}
+
+fn sample_config(policies: &[&str]) -> config::Config {
+    let repo = |name: &str, group: &str| config::Repository {
+        owner: "o".to_string(),
+        name: name.to_string(),
+        groups: vec![group.to_string()],
+        ..Default::default()
+    };
+    config::Config {
+        repositories: vec![repo("a", "prod"), repo("b", "dev")],
+        rotation_policy: policies.iter().map(|p| p.parse().unwrap()).collect(),
+        ..Default::default()
+    }
+}
+
+async fn age(
+    github: &FakeGitHub,
+    config: config::Config,
+    options: &AgeOptions,
+) -> Result<AgeReport> {
+    let report = App::age_with_deps(
+        github,
+        &mut NoopRateLimiter,
+        Arc::new("token".to_string()),
+        config,
+        options,
+    )
+    .await;
+    assert!(github.uploads().is_empty(), "age must not update secrets");
+    report
+}
+
+fn names(report: &AgeReport) -> Vec<String> {
+    report
+        .secrets
+        .iter()
+        .map(|s| format!("{}/{}", s.repository, s.secret))
+        .collect()
+}
+
+#[test]
+fn test_parse_duration() {
+    assert_eq!(parse_duration("90d").unwrap(), Duration::days(90));
+    assert_eq!(parse_duration("12w").unwrap(), Duration::weeks(12));
+    assert_eq!(parse_duration(" 36h ").unwrap(), Duration::hours(36));
+    assert_eq!(parse_duration("30m").unwrap(), Duration::minutes(30));
+
+    assert!(parse_duration("90").is_err());
+    assert!(parse_duration("d").is_err());
+    assert!(parse_duration("3 months").is_err());
+    assert!(parse_duration("99999999999999w").is_err());
+}
+
+#[test]
+fn test_policy_parsing_and_first_match_wins() {
+    let policies = policy::compile_all(&[
+        "DEPLOY_TOKEN=7d".parse::<RotationPolicy>().unwrap(),
+        "*_token=90d".parse().unwrap(),
+        "*=365d".parse().unwrap(),
+    ])
+    .unwrap();
+
+    assert_eq!(
+        policy::find(&policies, "DEPLOY_TOKEN").unwrap().max_age,
+        Duration::days(7)
+    );
+    assert_eq!(
+        policy::find(&policies, "NPM_TOKEN").unwrap().pattern,
+        "*_token"
+    );
+    assert_eq!(
+        policy::find(&policies, "DB_PASSWORD").unwrap().max_age,
+        Duration::days(365)
+    );
+
+    assert!("*_TOKEN".parse::<RotationPolicy>().is_err());
+    assert!("=90d".parse::<RotationPolicy>().is_err());
+}
+
+#[test]
+fn test_invalid_policy_in_config_is_rejected() {
+    let temp_dir = tempfile::TempDir::new().unwrap();
+    let path = temp_dir.path().join("config.toml");
+    std::fs::write(
+        &path,
+        "[[repositories]]\nowner = \"o\"\nname = \"a\"\n\n\
+         [[rotation_policy]]\npattern = \"*_TOKEN\"\nmax_age = \"ninety days\"\n",
+    )
+    .unwrap();
+
+    let err = config::Config::from_file(path.to_str().unwrap()).unwrap_err();
+    assert!(format!("{:#}", err).contains("*_TOKEN"), "{:#}", err);
+}
+
+#[tokio::test]
+async fn test_age_sorts_oldest_first_and_flags_stale() -> Result<()> {
+    let github = github(&[
+        (
+            "a",
+            &[
+                ("NPM_TOKEN", Some(120)),
+                ("DB_PASSWORD", Some(400)),
+                ("LEGACY", None),
+            ],
+        ),
+        ("b", &[("NPM_TOKEN", Some(30))]),
+    ]);
+
+    let report = age(
+        &github,
+        sample_config(&["*_TOKEN=90d"]),
+        &AgeOptions::default(),
+    )
+    .await?;
+
+    assert_eq!(
+        names(&report),
+        vec![
+            "o/a/DB_PASSWORD",
+            "o/a/NPM_TOKEN",
+            "o/b/NPM_TOKEN",
+            "o/a/LEGACY"
+        ]
+    );
+    let stale: Vec<bool> = report.secrets.iter().map(|s| s.stale).collect();
+    assert_eq!(stale, vec![false, true, false, false]);
+    assert_eq!(
+        report.secrets[1].policy,
+        Some(("*_TOKEN".to_string(), "90d".to_string()))
+    );
+
+    let err = report.check().unwrap_err();
+    assert!(err.to_string().contains("1 secret(s)"), "{}", err);
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_age_cli_policy_overrides_config_and_selection_applies() -> Result<()> {
+    let github = github(&[
+        ("a", &[("NPM_TOKEN", Some(120))]),
+        ("b", &[("NPM_TOKEN", Some(30))]),
+    ]);
+
+    let options = AgeOptions {
+        policies: vec!["NPM_*=180d".parse()?],
+        selection: RunOptions {
+            groups: vec!["prod".to_string()],
+            ..Default::default()
+        },
+        ..Default::default()
+    };
+    let report = age(&github, sample_config(&["*_TOKEN=90d"]), &options).await?;
+
+    assert_eq!(names(&report), vec!["o/a/NPM_TOKEN"]);
+    assert!(!report.secrets[0].stale);
+    report.check()?;
+
+    let json: serde_json::Value = serde_json::from_str(&App::age_json(&report)?)?;
+    assert_eq!(json["secrets"][0]["policy"], "NPM_*");
+    assert_eq!(json["secrets"][0]["age_days"], 120);
+    assert_eq!(json["secrets"][0]["stale"], false);
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_age_fails_when_a_repository_cannot_be_listed() -> Result<()> {
+    let github = github(&[("a", &[("NPM_TOKEN", Some(1))])]);
+
+    let report = age(&github, sample_config(&[]), &AgeOptions::default()).await?;
+
+    assert_eq!(names(&report), vec!["o/a/NPM_TOKEN"]);
+    assert_eq!(report.unchecked.len(), 1);
+    let err = report.check().unwrap_err();
+    assert!(err.to_string().contains("o/b: 403 Forbidden"), "{}", err);
+    Ok(())
+}
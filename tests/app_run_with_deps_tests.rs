@@ -3,11 +3,12 @@ use std::sync::Arc;
 use anyhow::Result;
 use async_trait::async_trait;
 
-use github_secrets::app::App;
+use github_secrets::app::{App, RunOptions};
 use github_secrets::app_deps::{
     GitHubApi, GitHubApiFactory, PromptInterface, RateLimiterInterface,
 };
 use github_secrets::config;
+use github_secrets::overwrite::OverwriteAnswer;
 use github_secrets::prompt;
 
 struct MockGitHubApi;
@@ -24,6 +25,17 @@ impl GitHubApi for MockGitHubApi {
     async fn update_secret(&self, _secret_name: &str, _secret_value: &str) -> Result<()> {
         Ok(())
     }
+
+    async fn list_repositories(
+        &self,
+        _kind: github_secrets::github::OwnerKind,
+    ) -> Result<Vec<github_secrets::github::RemoteRepository>> {
+        Ok(vec![])
+    }
+
+    async fn list_secrets(&self) -> Result<Vec<github_secrets::github::RemoteSecret>> {
+        Ok(vec![])
+    }
 }
 
 struct MockFactory;
@@ -47,17 +59,30 @@ impl PromptInterface for MockPrompt {
         }])
     }
 
-    fn confirm_secret_update(&self, _key: &str, _last_updated: Option<&str>) -> Result<bool> {
-        Ok(true)
+    fn confirm_secret_update(
+        &self,
+        _: &str,
+        _key: &str,
+        _last_updated: Option<&str>,
+    ) -> Result<OverwriteAnswer> {
+        Ok(OverwriteAnswer::YES)
     }
 
     fn confirm_retry(&self) -> Result<bool> {
         Ok(false)
     }
 
+    fn confirm_action(&self, _: &str) -> Result<bool> {
+        Ok(true)
+    }
+
     fn manage_config(&self, _initial: config::Config) -> Result<Option<config::Config>> {
         Ok(None)
     }
+
+    fn select_import(&self, _candidates: &[config::Repository]) -> Result<Vec<usize>> {
+        Ok(vec![])
+    }
 }
 
 struct MockRateLimiter;
@@ -82,8 +107,10 @@ async fn test_run_with_deps_success_path() -> Result<()> {
             owner: "owner".to_string(),
             name: "repo".to_string(),
             alias: None,
+            ..Default::default()
         }],
         repository: None,
+        ..Default::default()
     };
 
     let token = Arc::new("token".to_string());
@@ -92,3 +119,58 @@ async fn test_run_with_deps_success_path() -> Result<()> {
     assert!(res.is_ok());
     Ok(())
 }
+
+#[test]
+fn test_resolve_selection_uses_groups_without_prompt() -> Result<()> {
+    struct PanicPrompt;
+    impl PromptInterface for PanicPrompt {
+        fn select_repositories(&self, _: &[config::Repository]) -> Result<Vec<usize>> {
+            panic!("selection menu should be skipped when --group is given");
+        }
+        fn prompt_secrets(&self) -> Result<Vec<prompt::SecretPair>> {
+            Ok(vec![])
+        }
+        fn confirm_secret_update(
+            &self,
+            _: &str,
+            _: &str,
+            _: Option<&str>,
+        ) -> Result<OverwriteAnswer> {
+            Ok(OverwriteAnswer::NO)
+        }
+        fn confirm_retry(&self) -> Result<bool> {
+            Ok(false)
+        }
+        fn confirm_action(&self, _: &str) -> Result<bool> {
+            Ok(true)
+        }
+        fn manage_config(&self, _: config::Config) -> Result<Option<config::Config>> {
+            Ok(None)
+        }
+        fn select_import(&self, _: &[config::Repository]) -> Result<Vec<usize>> {
+            Ok(vec![])
+        }
+    }
+
+    let repositories = vec![
+        config::Repository {
+            owner: "owner".to_string(),
+            name: "api".to_string(),
+            groups: vec!["prod".to_string()],
+            ..Default::default()
+        },
+        config::Repository {
+            owner: "owner".to_string(),
+            name: "web".to_string(),
+            ..Default::default()
+        },
+    ];
+    let options = RunOptions {
+        groups: vec!["prod".to_string()],
+        ..Default::default()
+    };
+
+    let selected = App::resolve_selection(&PanicPrompt, &repositories, &options)?;
+    assert_eq!(selected, vec![0]);
+    Ok(())
+}
@@ -10,7 +10,7 @@ async fn test_run_fails_on_empty_token() {
         env::set_var("GITHUB_TOKEN", "");
     }
 
-    let res = github_secrets::app::App::run().await;
+    let res = github_secrets::app::App::run(Default::default()).await;
 
     // Restore
     match orig {
@@ -32,7 +32,7 @@ async fn test_run_fails_on_invalid_token_format() {
         env::set_var("GITHUB_TOKEN", "invalid_token");
     }
 
-    let res = github_secrets::app::App::run().await;
+    let res = github_secrets::app::App::run(Default::default()).await;
 
     // Restore
     match orig {
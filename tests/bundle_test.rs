@@ -0,0 +1,355 @@
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+
+use aes_gcm::AesGcm;
+use aes_gcm::aead::consts::U32;
+use aes_gcm::aead::{Aead, KeyInit, Payload};
+use aes_gcm::aes::Aes256;
+use age::secrecy::ExposeSecret;
+use anyhow::Result;
+use base64::Engine;
+use base64::engine::general_purpose::STANDARD;
+use sha2::{Digest, Sha512};
+use tempfile::TempDir;
+
+use github_secrets::app::{App, RunOptions};
+use github_secrets::bundle;
+use github_secrets::secret_ref;
+
+mod common;
+use common::{FakeGitHub, FakePrompt, NoopRateLimiter, repositories};
+
+/// Write a fresh age identity file and return it with its path.
+fn write_identity(dir: &Path) -> (age::x25519::Identity, PathBuf) {
+    let identity = age::x25519::Identity::generate();
+    let path = dir.join("keys.txt");
+    std::fs::write(
+        &path,
+        format!(
+            "# public key: {}\n{}\n",
+            identity.to_public(),
+            identity.to_string().expose_secret()
+        ),
+    )
+    .unwrap();
+    (identity, path)
+}
+
+fn age_encrypt(identity: &age::x25519::Identity, plaintext: &[u8]) -> Vec<u8> {
+    age::encrypt(&identity.to_public(), plaintext).unwrap()
+}
+
+fn age_encrypt_armored(identity: &age::x25519::Identity, plaintext: &[u8]) -> String {
+    age::encrypt_and_armor(&identity.to_public(), plaintext).unwrap()
+}
+
+/// Encrypt a value the way SOPS does (AES-256-GCM, 32-byte IV, key path as AAD).
+fn sops_encrypt(data_key: &[u8], iv: &[u8; 32], value: &str, path: &str) -> String {
+    sops_seal(data_key, iv, value, &format!("{}:", path))
+}
+
+fn sops_seal(data_key: &[u8], iv: &[u8; 32], value: &str, aad: &str) -> String {
+    let cipher = AesGcm::<Aes256, U32>::new_from_slice(data_key).unwrap();
+    let sealed = cipher
+        .encrypt(
+            iv.into(),
+            Payload {
+                msg: value.as_bytes(),
+                aad: aad.as_bytes(),
+            },
+        )
+        .unwrap();
+    let (data, tag) = sealed.split_at(sealed.len() - 16);
+    format!(
+        "ENC[AES256_GCM,data:{},iv:{},tag:{},type:str]",
+        STANDARD.encode(data),
+        STANDARD.encode(iv),
+        STANDARD.encode(tag)
+    )
+}
+
+fn sops_document(identity: &age::x25519::Identity, data_key: &[u8; 32]) -> String {
+    let enc = age_encrypt_armored(identity, data_key);
+    let enc = enc
+        .lines()
+        .map(|line| format!("            {}", line))
+        .collect::<Vec<_>>()
+        .join("\n");
+    // The MAC covers every value, in order, keyed by the last modification time.
+    let last_modified = "2026-01-01T00:00:00Z";
+    let digest = Sha512::digest(b"s3cr3tenv:NOT_A_REFERENCEeu-west-1");
+    let mac: String = digest.iter().map(|b| format!("{:02X}", b)).collect();
+    format!(
+        "DB_PASSWORD: {}\nAPI_TOKEN: {}\nREGION_unencrypted: eu-west-1\nsops:\n    age:\n        - recipient: {}\n          enc: |\n{}\n    lastmodified: \"{}\"\n    mac: {}\n    unencrypted_suffix: _unencrypted\n    version: 3.9.0\n",
+        sops_encrypt(data_key, &[7; 32], "s3cr3t", "DB_PASSWORD"),
+        sops_encrypt(data_key, &[9; 32], "env:NOT_A_REFERENCE", "API_TOKEN"),
+        identity.to_public(),
+        enc,
+        last_modified,
+        sops_seal(data_key, &[11; 32], &mac, last_modified)
+    )
+}
+
+fn pair(key: &str, value: &str) -> (String, String) {
+    (key.to_string(), value.to_string())
+}
+
+/// Load a bundle as `(key, value)` tuples (`SecretPair` has no `Debug` on purpose).
+fn load(path: &Path, identity_path: &Path) -> Result<Vec<(String, String)>> {
+    Ok(bundle::load(path, Some(identity_path))?
+        .into_iter()
+        .map(|s| (s.key, s.value))
+        .collect())
+}
+
+#[test]
+fn test_load_age_yaml_bundle() {
+    let temp_dir = TempDir::new().unwrap();
+    let (identity, identity_path) = write_identity(temp_dir.path());
+    let path = temp_dir.path().join("secrets.yaml.age");
+    std::fs::write(
+        &path,
+        age_encrypt(&identity, b"DB_PASSWORD: hunter2\nPORT: 5432\n"),
+    )
+    .unwrap();
+
+    let secrets = load(&path, &identity_path).unwrap();
+    assert_eq!(
+        secrets,
+        vec![pair("DB_PASSWORD", "hunter2"), pair("PORT", "5432")]
+    );
+}
+
+#[test]
+fn test_load_armored_dotenv_bundle() {
+    let temp_dir = TempDir::new().unwrap();
+    let (identity, identity_path) = write_identity(temp_dir.path());
+    let path = temp_dir.path().join("secrets.env.age");
+    std::fs::write(
+        &path,
+        age_encrypt_armored(
+            &identity,
+            b"# comment\nexport DB_PASSWORD=\"hunter2\"\nTOKEN=cmd:rm -rf\n",
+        ),
+    )
+    .unwrap();
+
+    let secrets = load(&path, &identity_path).unwrap();
+    assert_eq!(
+        secrets,
+        vec![
+            pair("DB_PASSWORD", "hunter2"),
+            pair("TOKEN", "literal:cmd:rm -rf"),
+        ]
+    );
+}
+
+#[test]
+fn test_load_sops_bundle() {
+    let temp_dir = TempDir::new().unwrap();
+    let (identity, identity_path) = write_identity(temp_dir.path());
+    let data_key = [42u8; 32];
+    let path = temp_dir.path().join("secrets.enc.yaml");
+    std::fs::write(&path, sops_document(&identity, &data_key)).unwrap();
+
+    let secrets = load(&path, &identity_path).unwrap();
+    assert_eq!(
+        secrets,
+        vec![
+            pair("DB_PASSWORD", "s3cr3t"),
+            pair("API_TOKEN", "literal:env:NOT_A_REFERENCE"),
+            pair("REGION_unencrypted", "eu-west-1"),
+        ]
+    );
+}
+
+/// The documents above are built by hand; this checks a file written by the
+/// real `sops`, and is skipped when it isn't installed.
+#[test]
+fn test_load_file_encrypted_by_sops() {
+    let temp_dir = TempDir::new().unwrap();
+    let (identity, identity_path) = write_identity(temp_dir.path());
+    let plain = temp_dir.path().join("plain.yaml");
+    std::fs::write(
+        &plain,
+        "DB_PASSWORD: s3cr3t\nAPI_TOKEN: env:NOT_A_REFERENCE\nREGION_unencrypted: eu-west-1\n",
+    )
+    .unwrap();
+
+    let output = match std::process::Command::new("sops")
+        .args(["--encrypt", "--age", &identity.to_public().to_string()])
+        .args(["--unencrypted-suffix", "_unencrypted"])
+        .arg(&plain)
+        .output()
+    {
+        Ok(output) => output,
+        Err(err) if err.kind() == std::io::ErrorKind::NotFound => {
+            eprintln!("sops is not installed; skipping");
+            return;
+        }
+        Err(err) => panic!("failed to run sops: {}", err),
+    };
+    assert!(
+        output.status.success(),
+        "{}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+    let path = temp_dir.path().join("secrets.enc.yaml");
+    std::fs::write(&path, &output.stdout).unwrap();
+
+    let secrets = load(&path, &identity_path).unwrap();
+    assert_eq!(
+        secrets,
+        vec![
+            pair("DB_PASSWORD", "s3cr3t"),
+            pair("API_TOKEN", "literal:env:NOT_A_REFERENCE"),
+            pair("REGION_unencrypted", "eu-west-1"),
+        ]
+    );
+}
+
+#[test]
+fn test_sops_value_moved_to_other_key_fails() {
+    let temp_dir = TempDir::new().unwrap();
+    let (identity, identity_path) = write_identity(temp_dir.path());
+    let data_key = [42u8; 32];
+    let document = sops_document(&identity, &data_key).replacen("DB_PASSWORD:", "OTHER:", 1);
+    let path = temp_dir.path().join("secrets.enc.yaml");
+    std::fs::write(&path, document).unwrap();
+
+    let err = load(&path, &identity_path).unwrap_err();
+    assert!(format!("{:#}", err).contains("OTHER"), "{:#}", err);
+}
+
+/// Write `document` as a SOPS bundle and return the error loading it.
+fn sops_load_error(document: &str) -> String {
+    let temp_dir = TempDir::new().unwrap();
+    let (_, identity_path) = write_identity(temp_dir.path());
+    let path = temp_dir.path().join("secrets.enc.yaml");
+    std::fs::write(&path, document).unwrap();
+    format!("{:#}", load(&path, &identity_path).unwrap_err())
+}
+
+#[test]
+fn test_sops_tampering_fails() {
+    let temp_dir = TempDir::new().unwrap();
+    let (identity, identity_path) = write_identity(temp_dir.path());
+    let data_key = [42u8; 32];
+    let document = sops_document(&identity, &data_key);
+    let path = temp_dir.path().join("secrets.enc.yaml");
+
+    // Editing one value breaks the MAC.
+    let edited = document.replacen("eu-west-1", "us-east-1", 1);
+    std::fs::write(&path, &edited).unwrap();
+    let err = format!("{:#}", load(&path, &identity_path).unwrap_err());
+    assert!(err.contains("MAC mismatch"), "{}", err);
+
+    // So does swapping two encrypted values' order.
+    let lines: Vec<&str> = document.lines().collect();
+    let swapped = format!("{}\n{}\n{}\n", lines[1], lines[0], lines[2..].join("\n"));
+    std::fs::write(&path, swapped).unwrap();
+    let err = format!("{:#}", load(&path, &identity_path).unwrap_err());
+    assert!(err.contains("MAC mismatch"), "{}", err);
+
+    // Without a MAC nothing is trusted.
+    let no_mac: String = document
+        .lines()
+        .filter(|line| !line.trim_start().starts_with("mac:"))
+        .map(|line| format!("{}\n", line))
+        .collect();
+    std::fs::write(&path, no_mac).unwrap();
+    let err = format!("{:#}", load(&path, &identity_path).unwrap_err());
+    assert!(err.contains("no MAC"), "{}", err);
+
+    // A plaintext value is only accepted under the unencrypted suffix.
+    let first_line = document.lines().next().unwrap();
+    let plaintext = document.replacen(first_line, "DB_PASSWORD: s3cr3t", 1);
+    std::fs::write(&path, plaintext).unwrap();
+    let err = format!("{:#}", load(&path, &identity_path).unwrap_err());
+    assert!(err.contains("'DB_PASSWORD' is not encrypted"), "{}", err);
+}
+
+#[test]
+fn test_sops_unsupported_key_selection_fails() {
+    let err = sops_load_error("A: b\nsops:\n    encrypted_regex: ^password$\n");
+    assert!(err.contains("encrypted_regex"), "{}", err);
+}
+
+#[test]
+fn test_wrong_identity_fails() {
+    let temp_dir = TempDir::new().unwrap();
+    let other = age::x25519::Identity::generate();
+    let (_, identity_path) = write_identity(temp_dir.path());
+    let path = temp_dir.path().join("secrets.age");
+    std::fs::write(&path, age_encrypt(&other, b"A: b\n")).unwrap();
+
+    assert!(load(&path, &identity_path).is_err());
+}
+
+#[test]
+fn test_plaintext_and_nested_bundles_rejected() {
+    let temp_dir = TempDir::new().unwrap();
+    let (identity, identity_path) = write_identity(temp_dir.path());
+
+    let plain = temp_dir.path().join("plain.yaml");
+    std::fs::write(&plain, "A: b\n").unwrap();
+    let err = load(&plain, &identity_path).unwrap_err();
+    assert!(err.to_string().contains("neither"), "{}", err);
+
+    let nested = temp_dir.path().join("nested.age");
+    std::fs::write(&nested, age_encrypt(&identity, b"DB:\n  password: x\n")).unwrap();
+    let err = load(&nested, &identity_path).unwrap_err();
+    assert!(format!("{:#}", err).contains("Nested"), "{:#}", err);
+
+    let invalid = temp_dir.path().join("invalid.age");
+    std::fs::write(&invalid, age_encrypt(&identity, b"bad name: x\n")).unwrap();
+    assert!(load(&invalid, &identity_path).is_err());
+}
+
+#[test]
+fn test_escape_literal() {
+    assert_eq!(secret_ref::escape_literal("plain"), "plain");
+    assert_eq!(secret_ref::escape_literal("env:X"), "literal:env:X");
+    assert_eq!(
+        secret_ref::resolve_value(
+            &secret_ref::escape_literal("literal:x"),
+            &Default::default()
+        )
+        .unwrap(),
+        "literal:x"
+    );
+}
+
+#[tokio::test]
+async fn test_run_uploads_bundle_to_selected_repositories() -> Result<()> {
+    let temp_dir = TempDir::new()?;
+    let (identity, identity_path) = write_identity(temp_dir.path());
+    let path = temp_dir.path().join("secrets.age");
+    std::fs::write(&path, age_encrypt(&identity, b"TOKEN: env:HOME\n"))?;
+
+    let github = FakeGitHub::default();
+    let before: Vec<_> = std::fs::read_dir(temp_dir.path())?.collect();
+
+    App::run_with_options(
+        &github,
+        // Secrets come from the bundle, so the prompt isn't asked for any.
+        &FakePrompt::default().selecting(vec![1]),
+        &mut NoopRateLimiter,
+        Arc::new("token".to_string()),
+        repositories(&["a", "b"]),
+        &RunOptions {
+            bundle: Some(path),
+            identity: Some(identity_path),
+            ..Default::default()
+        },
+    )
+    .await?;
+
+    assert_eq!(
+        github.uploads(),
+        vec![("b".to_string(), "TOKEN".to_string(), "env:HOME".to_string())]
+    );
+    // Nothing decrypted was written next to the bundle.
+    assert_eq!(std::fs::read_dir(temp_dir.path())?.count(), before.len());
+    Ok(())
+}
@@ -0,0 +1,471 @@
+//! Helpers shared by the tests: scripted key events for the `*_with`
+//! functions in `prompt`, and fakes for the `app_deps` traits.
+
+#![allow(dead_code)]
+
+use std::collections::{HashMap, VecDeque};
+use std::sync::{Arc, Mutex};
+
+use anyhow::Result;
+use async_trait::async_trait;
+use chrono::{Duration, Utc};
+use crossterm::event::{Event, KeyCode, KeyEvent, KeyModifiers};
+
+use github_secrets::app_deps::{
+    GitHubApi, GitHubApiFactory, PromptInterface, RateLimiterInterface,
+};
+use github_secrets::config;
+use github_secrets::github::{OwnerKind, RemoteRepository, RemoteSecret, SecretInfo};
+use github_secrets::overwrite::OverwriteAnswer;
+use github_secrets::progress::Progress;
+use github_secrets::prompt::{EventSource, SecretPair};
+
+/// Replays scripted events, then keeps returning a fallback key.
+pub struct FakeEventSource {
+    events: Vec<Event>,
+    idx: usize,
+    fallback: Option<KeyCode>,
+}
+
+impl FakeEventSource {
+    /// Replay `events`, then press ESC.
+    pub fn new(events: Vec<Event>) -> Self {
+        Self::ending_with(events, KeyCode::Esc)
+    }
+
+    /// Replay `events`, then press `fallback`.
+    pub fn ending_with(events: Vec<Event>, fallback: KeyCode) -> Self {
+        Self {
+            events,
+            idx: 0,
+            fallback: Some(fallback),
+        }
+    }
+
+    /// Replay `events`, then fail, leaving the last frame on the test backend.
+    pub fn stopping_after(events: Vec<Event>) -> Self {
+        Self {
+            events,
+            idx: 0,
+            fallback: None,
+        }
+    }
+}
+
+impl EventSource for FakeEventSource {
+    fn read_event(&mut self) -> anyhow::Result<Event> {
+        let ev = match (self.events.get(self.idx), self.fallback) {
+            (Some(ev), _) => ev.clone(),
+            (None, Some(fallback)) => key(fallback),
+            (None, None) => anyhow::bail!("no more scripted events"),
+        };
+        self.idx += 1;
+        Ok(ev)
+    }
+}
+
+/// A key press without modifiers.
+pub fn key(code: KeyCode) -> Event {
+    Event::Key(KeyEvent::new(code, KeyModifiers::NONE))
+}
+
+/// A key press with Shift held.
+pub fn shift(code: KeyCode) -> Event {
+    Event::Key(KeyEvent::new(code, KeyModifiers::SHIFT))
+}
+
+/// A character typed with Ctrl held.
+pub fn ctrl(c: char) -> Event {
+    Event::Key(KeyEvent::new(KeyCode::Char(c), KeyModifiers::CONTROL))
+}
+
+/// Key presses typing `text`.
+pub fn typed(text: &str) -> Vec<Event> {
+    text.chars().map(|c| key(KeyCode::Char(c))).collect()
+}
+
+/// Secrets of a repository: names and how many days ago they were updated.
+type Secrets = Vec<(String, Option<i64>)>;
+
+#[derive(Default)]
+struct GitHubState {
+    /// Secrets by repository name; repositories missing here can't be listed.
+    secrets: HashMap<String, Secrets>,
+    /// Secrets that exist in every repository, without making it listable.
+    everywhere: Secrets,
+    /// How many more times uploads fail, by repository name.
+    update_failures: HashMap<String, usize>,
+    /// How many more times lookups fail, by repository name.
+    lookup_failures: HashMap<String, usize>,
+    remote_repositories: Vec<RemoteRepository>,
+    uploads: Vec<(String, String, String)>,
+    listings: Vec<(String, OwnerKind)>,
+}
+
+/// Fake GitHub shared by every repository of a test run.
+///
+/// Names are matched case-insensitively, like the real API.
+#[derive(Clone, Default)]
+pub struct FakeGitHub(Arc<Mutex<GitHubState>>);
+
+impl FakeGitHub {
+    /// Give `repo` these secrets, updated the given number of days ago.
+    pub fn with_secrets(self, repo: &str, secrets: &[(&str, Option<i64>)]) -> Self {
+        self.0
+            .lock()
+            .unwrap()
+            .secrets
+            .insert(repo.to_string(), to_secrets(secrets));
+        self
+    }
+
+    /// Give every repository these secrets.
+    pub fn with_secrets_everywhere(self, secrets: &[(&str, Option<i64>)]) -> Self {
+        self.0.lock().unwrap().everywhere = to_secrets(secrets);
+        self
+    }
+
+    /// Fail the next `times` uploads to `repo`.
+    pub fn failing_updates(self, repo: &str, times: usize) -> Self {
+        self.0
+            .lock()
+            .unwrap()
+            .update_failures
+            .insert(repo.to_string(), times);
+        self
+    }
+
+    /// Fail the next `times` secret lookups in `repo`.
+    pub fn failing_lookups(self, repo: &str, times: usize) -> Self {
+        self.0
+            .lock()
+            .unwrap()
+            .lookup_failures
+            .insert(repo.to_string(), times);
+        self
+    }
+
+    /// Repositories returned by every repository listing.
+    pub fn with_remote_repositories(self, repositories: Vec<RemoteRepository>) -> Self {
+        self.0.lock().unwrap().remote_repositories = repositories;
+        self
+    }
+
+    /// Uploads so far as (repository, name, value).
+    pub fn uploads(&self) -> Vec<(String, String, String)> {
+        self.0.lock().unwrap().uploads.clone()
+    }
+
+    /// Names of the secrets uploaded so far.
+    pub fn uploaded_names(&self) -> Vec<String> {
+        self.uploads()
+            .into_iter()
+            .map(|(_, name, _)| name)
+            .collect()
+    }
+
+    /// Repository listings requested so far as (owner, kind).
+    pub fn listings(&self) -> Vec<(String, OwnerKind)> {
+        self.0.lock().unwrap().listings.clone()
+    }
+}
+
+fn to_secrets(secrets: &[(&str, Option<i64>)]) -> Secrets {
+    secrets
+        .iter()
+        .map(|(name, days)| (name.to_string(), *days))
+        .collect()
+}
+
+fn days_ago(days: Option<i64>) -> Option<String> {
+    days.map(|d| (Utc::now() - Duration::days(d)).to_rfc3339())
+}
+
+/// Count down a failure budget, returning whether this call fails.
+fn take_failure(budget: &mut HashMap<String, usize>, repo: &str) -> bool {
+    match budget.get_mut(repo) {
+        Some(left) if *left > 0 => {
+            *left -= 1;
+            true
+        }
+        _ => false,
+    }
+}
+
+impl GitHubApiFactory for FakeGitHub {
+    fn create(&self, _: String, owner: String, repo: String) -> Result<Box<dyn GitHubApi>> {
+        Ok(Box::new(FakeRepository {
+            github: self.clone(),
+            owner,
+            repo,
+        }))
+    }
+}
+
+struct FakeRepository {
+    github: FakeGitHub,
+    owner: String,
+    repo: String,
+}
+
+#[async_trait]
+impl GitHubApi for FakeRepository {
+    async fn get_secret_info(&self, name: &str) -> Result<Option<SecretInfo>> {
+        let mut state = self.github.0.lock().unwrap();
+        if take_failure(&mut state.lookup_failures, &self.repo) {
+            anyhow::bail!("connection reset");
+        }
+        let found = state
+            .secrets
+            .get(&self.repo)
+            .into_iter()
+            .flatten()
+            .chain(&state.everywhere)
+            .find(|(existing, _)| existing.eq_ignore_ascii_case(name));
+        Ok(found.map(|(existing, days)| SecretInfo {
+            name: existing.clone(),
+            updated_at: days_ago(*days),
+        }))
+    }
+
+    async fn update_secret(&self, name: &str, value: &str) -> Result<()> {
+        let mut state = self.github.0.lock().unwrap();
+        if take_failure(&mut state.update_failures, &self.repo) {
+            anyhow::bail!("502 Bad Gateway");
+        }
+        state
+            .uploads
+            .push((self.repo.clone(), name.to_string(), value.to_string()));
+        Ok(())
+    }
+
+    async fn list_repositories(&self, kind: OwnerKind) -> Result<Vec<RemoteRepository>> {
+        let mut state = self.github.0.lock().unwrap();
+        state.listings.push((self.owner.clone(), kind));
+        Ok(state.remote_repositories.clone())
+    }
+
+    async fn list_secrets(&self) -> Result<Vec<RemoteSecret>> {
+        let state = self.github.0.lock().unwrap();
+        match state.secrets.get(&self.repo) {
+            Some(secrets) => Ok(secrets
+                .iter()
+                .map(|(name, days)| RemoteSecret {
+                    name: name.clone(),
+                    created_at: None,
+                    updated_at: days_ago(*days),
+                })
+                .collect()),
+            None => anyhow::bail!("403 Forbidden"),
+        }
+    }
+}
+
+/// How [`FakePrompt`] answers overwrite questions.
+enum Overwrite {
+    Always(bool),
+    Keys(VecDeque<char>),
+}
+
+/// Prompt with fixed answers that records what it was asked.
+pub struct FakePrompt {
+    secrets: Option<Vec<SecretPair>>,
+    selection: Option<Vec<usize>>,
+    overwrite: Mutex<Overwrite>,
+    confirm: bool,
+    pick_last_import: bool,
+    retries: Mutex<usize>,
+    /// Overwrite questions as `repository:secret`.
+    pub asked: Mutex<Vec<String>>,
+    /// Questions passed to `confirm_action`.
+    pub questions: Mutex<Vec<String>>,
+    /// Candidate paths offered by each import preview.
+    pub import_candidates: Mutex<Vec<Vec<String>>>,
+    /// Every progress snapshot shown.
+    pub shown: Mutex<Vec<Progress>>,
+    /// Every summary reviewed.
+    pub reviewed: Mutex<Vec<Progress>>,
+}
+
+impl Default for FakePrompt {
+    /// Selects every repository, overwrites and confirms everything, and
+    /// panics when asked for secrets.
+    fn default() -> Self {
+        Self {
+            secrets: None,
+            selection: None,
+            overwrite: Mutex::new(Overwrite::Always(true)),
+            confirm: true,
+            pick_last_import: false,
+            retries: Mutex::new(0),
+            asked: Mutex::default(),
+            questions: Mutex::default(),
+            import_candidates: Mutex::default(),
+            shown: Mutex::default(),
+            reviewed: Mutex::default(),
+        }
+    }
+}
+
+impl FakePrompt {
+    /// Enter these `(key, value)` secrets.
+    pub fn with_secrets(secrets: &[(&str, &str)]) -> Self {
+        Self {
+            secrets: Some(
+                secrets
+                    .iter()
+                    .map(|(key, value)| SecretPair {
+                        key: key.to_string(),
+                        value: value.to_string(),
+                    })
+                    .collect(),
+            ),
+            ..Default::default()
+        }
+    }
+
+    /// Pick these repositories instead of all of them.
+    pub fn selecting(mut self, indices: Vec<usize>) -> Self {
+        self.selection = Some(indices);
+        self
+    }
+
+    /// Answer every overwrite question with `overwrite`.
+    pub fn overwriting(self, overwrite: bool) -> Self {
+        *self.overwrite.lock().unwrap() = Overwrite::Always(overwrite);
+        self
+    }
+
+    /// Answer overwrite questions by pressing `keys`; running out panics.
+    pub fn answering(self, keys: &str) -> Self {
+        *self.overwrite.lock().unwrap() = Overwrite::Keys(keys.chars().collect());
+        self
+    }
+
+    /// Answer `confirm_action` with `confirm`.
+    pub fn confirming(mut self, confirm: bool) -> Self {
+        self.confirm = confirm;
+        self
+    }
+
+    /// Import the last candidate of the preview.
+    pub fn picking_last_import(mut self) -> Self {
+        self.pick_last_import = true;
+        self
+    }
+
+    /// Choose retry on the summary screen `retries` times.
+    pub fn retrying(self, retries: usize) -> Self {
+        *self.retries.lock().unwrap() = retries;
+        self
+    }
+}
+
+impl PromptInterface for FakePrompt {
+    fn select_repositories(&self, repositories: &[config::Repository]) -> Result<Vec<usize>> {
+        Ok(self
+            .selection
+            .clone()
+            .unwrap_or_else(|| (0..repositories.len()).collect()))
+    }
+    fn prompt_secrets(&self) -> Result<Vec<SecretPair>> {
+        Ok(self
+            .secrets
+            .clone()
+            .expect("secrets should not be prompted for here"))
+    }
+    fn confirm_secret_update(
+        &self,
+        repository: &str,
+        key: &str,
+        _: Option<&str>,
+    ) -> Result<OverwriteAnswer> {
+        self.asked
+            .lock()
+            .unwrap()
+            .push(format!("{}:{}", repository, key));
+        match &mut *self.overwrite.lock().unwrap() {
+            Overwrite::Always(true) => Ok(OverwriteAnswer::YES),
+            Overwrite::Always(false) => Ok(OverwriteAnswer::NO),
+            Overwrite::Keys(keys) => {
+                let key = keys.pop_front().expect("asked too often");
+                OverwriteAnswer::from_keys(key, |_| Ok(keys.pop_front().expect("no second key")))
+            }
+        }
+    }
+    fn confirm_retry(&self) -> Result<bool> {
+        Ok(false)
+    }
+    fn confirm_action(&self, question: &str) -> Result<bool> {
+        self.questions.lock().unwrap().push(question.to_string());
+        Ok(self.confirm)
+    }
+    fn manage_config(&self, _: config::Config) -> Result<Option<config::Config>> {
+        Ok(None)
+    }
+    fn select_import(&self, candidates: &[config::Repository]) -> Result<Vec<usize>> {
+        self.import_candidates
+            .lock()
+            .unwrap()
+            .push(candidates.iter().map(|c| c.path()).collect());
+        Ok(match candidates.len() {
+            n if n > 0 && self.pick_last_import => vec![n - 1],
+            _ => vec![],
+        })
+    }
+    fn show_progress(&self, progress: &Progress) -> Result<()> {
+        self.shown.lock().unwrap().push(progress.clone());
+        Ok(())
+    }
+    fn review_progress(&self, progress: &Progress) -> Result<bool> {
+        self.reviewed.lock().unwrap().push(progress.clone());
+        let mut retries = self.retries.lock().unwrap();
+        Ok(match retries.checked_sub(1) {
+            Some(left) => {
+                *retries = left;
+                true
+            }
+            None => false,
+        })
+    }
+}
+
+/// Rate limiter that never waits.
+pub struct NoopRateLimiter;
+
+#[async_trait]
+impl RateLimiterInterface for NoopRateLimiter {
+    async fn wait_if_needed(&mut self) {}
+    fn release(&mut self) {}
+}
+
+/// Rate limiter counting the requests in flight.
+#[derive(Default)]
+pub struct CountingRateLimiter {
+    pub in_flight: usize,
+}
+
+#[async_trait]
+impl RateLimiterInterface for CountingRateLimiter {
+    async fn wait_if_needed(&mut self) {
+        self.in_flight += 1;
+    }
+    fn release(&mut self) {
+        self.in_flight -= 1;
+    }
+}
+
+/// Repositories `o/<name>` for each of `names`.
+pub fn repositories(names: &[&str]) -> config::Config {
+    config::Config {
+        repositories: names
+            .iter()
+            .map(|name| config::Repository {
+                owner: "o".to_string(),
+                name: name.to_string(),
+                ..Default::default()
+            })
+            .collect(),
+        ..Default::default()
+    }
+}
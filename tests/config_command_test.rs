@@ -1,36 +1,14 @@
 use anyhow::Result;
-use async_trait::async_trait;
 use github_secrets::app::App;
-use github_secrets::app_deps::{
-    GitHubApi, GitHubApiFactory, PromptInterface, RateLimiterInterface,
-};
+use github_secrets::app_deps::PromptInterface;
 use github_secrets::config;
+use github_secrets::config_edit::backup_path;
+use github_secrets::overwrite::OverwriteAnswer;
 use github_secrets::prompt;
-
-struct MockGitHubApi;
-#[async_trait]
-impl GitHubApi for MockGitHubApi {
-    async fn get_secret_info(&self, _: &str) -> Result<Option<github_secrets::github::SecretInfo>> {
-        Ok(None)
-    }
-    async fn update_secret(&self, _: &str, _: &str) -> Result<()> {
-        Ok(())
-    }
-}
-
-struct MockFactory;
-impl GitHubApiFactory for MockFactory {
-    fn create(&self, _: String, _: String, _: String) -> Result<Box<dyn GitHubApi>> {
-        Ok(Box::new(MockGitHubApi))
-    }
-}
-
-struct MockRateLimiter;
-#[async_trait]
-impl RateLimiterInterface for MockRateLimiter {
-    async fn wait_if_needed(&mut self) {}
-    fn release(&mut self) {}
-}
+use serial_test::serial;
+use std::env;
+use std::fs;
+use tempfile::TempDir;
 
 struct MockPrompt {}
 
@@ -41,21 +19,30 @@ impl PromptInterface for MockPrompt {
     fn prompt_secrets(&self) -> Result<Vec<prompt::SecretPair>> {
         Ok(vec![])
     }
-    fn confirm_secret_update(&self, _: &str, _: Option<&str>) -> Result<bool> {
-        Ok(true)
+    fn confirm_secret_update(&self, _: &str, _: &str, _: Option<&str>) -> Result<OverwriteAnswer> {
+        Ok(OverwriteAnswer::YES)
     }
     fn confirm_retry(&self) -> Result<bool> {
         Ok(false)
     }
 
+    fn confirm_action(&self, _: &str) -> Result<bool> {
+        Ok(true)
+    }
+
     fn manage_config(&self, initial: config::Config) -> Result<Option<config::Config>> {
         // Return initial config as is, or modified if needed for testing.
         // For basic test, just return None (no change) or Some(initial).
         Ok(Some(initial))
     }
+
+    fn select_import(&self, _: &[config::Repository]) -> Result<Vec<usize>> {
+        Ok(vec![])
+    }
 }
 
 #[tokio::test]
+#[serial]
 async fn test_config_command_mocked() -> Result<()> {
     // This test verifies that App::config_with_deps runs without error when mocked
     let prompt = MockPrompt {};
@@ -74,3 +61,61 @@ async fn test_config_command_mocked() -> Result<()> {
     assert!(res.is_ok());
     Ok(())
 }
+
+/// Open and quit the dashboard on `contents` (the mock returns the config
+/// unchanged), returning the result, the file afterwards and whether it was saved.
+async fn edit_and_quit(contents: &str) -> (Result<()>, String, bool) {
+    let temp_dir = TempDir::new().unwrap();
+    let path = temp_dir.path().join("config.toml");
+    fs::write(&path, contents).unwrap();
+
+    let original = env::var("CONFIG_PATH").ok();
+    unsafe {
+        env::set_var("CONFIG_PATH", &path);
+    }
+    let result = App::config_with_deps(&MockPrompt {}).await;
+    unsafe {
+        match original {
+            Some(value) => env::set_var("CONFIG_PATH", value),
+            None => env::remove_var("CONFIG_PATH"),
+        }
+    }
+    let saved = backup_path(&path).exists();
+    (result, fs::read_to_string(&path).unwrap(), saved)
+}
+
+#[tokio::test]
+#[serial]
+async fn test_config_command_keeps_include_only_file() {
+    let contents = "include = [\"team/repos.toml\"]\n";
+    let (result, after, saved) = edit_and_quit(contents).await;
+    assert!(result.is_ok(), "{:?}", result);
+    assert_eq!(after, contents);
+    assert!(!saved);
+}
+
+#[tokio::test]
+#[serial]
+async fn test_config_command_refuses_a_config_it_cannot_load() {
+    let contents = "[[repositories]]\nowner = \"o\"\nname = \"a\"\naliass = \"A\"\n\n\
+                    [[repositories]]\nowner = \"o\"\nname = \"b\"\n";
+    let (result, after, saved) = edit_and_quit(contents).await;
+    let err = result.unwrap_err();
+    assert!(
+        format!("{:#}", err).contains("fix it before editing"),
+        "{:#}",
+        err
+    );
+    assert_eq!(after, contents);
+    assert!(!saved);
+}
+
+#[tokio::test]
+#[serial]
+async fn test_config_command_skips_unchanged_save() {
+    let contents = "# team\n[[repositories]]\nowner = \"o\"\nname = \"a\"   # kept\n";
+    let (result, after, saved) = edit_and_quit(contents).await;
+    assert!(result.is_ok(), "{:?}", result);
+    assert_eq!(after, contents);
+    assert!(!saved);
+}
@@ -0,0 +1,187 @@
+use github_secrets::config::{Config, Repository};
+use github_secrets::config_edit::{backup_path, render, replace_file, save, write_atomic};
+use std::fs;
+use tempfile::TempDir;
+
+fn repo(owner: &str, name: &str, alias: Option<&str>) -> Repository {
+    Repository {
+        owner: owner.to_string(),
+        name: name.to_string(),
+        alias: alias.map(str::to_string),
+        ..Default::default()
+    }
+}
+
+fn parse(content: &str) -> Config {
+    let temp_dir = TempDir::new().unwrap();
+    let path = temp_dir.path().join("config.toml");
+    fs::write(&path, content).unwrap();
+    Config::load_editable(path.to_str().unwrap()).unwrap()
+}
+
+const HAND_WRITTEN: &str = r#"# Team repositories
+# (keep sorted)
+
+[[repositories]]
+# main backend
+owner = "my-org"
+name = "api"
+alias = "API"   # shown in menus
+groups = [ "prod" ]   # odd spacing kept
+
+[[repositories]]
+owner = "my-org"
+name = "web"
+"#;
+
+#[test]
+fn test_render_preserves_comments_and_unknown_keys() {
+    let mut config = parse(HAND_WRITTEN);
+    config.repositories[1].alias = Some("Website".to_string());
+
+    let rendered = render(Some(HAND_WRITTEN), &config).unwrap();
+
+    assert!(rendered.starts_with("# Team repositories\n# (keep sorted)\n"));
+    assert!(rendered.contains("# main backend"));
+    assert!(rendered.contains("alias = \"API\"   # shown in menus"));
+    assert!(rendered.contains("groups = [ \"prod\" ]   # odd spacing kept"));
+    assert!(rendered.contains("alias = \"Website\""));
+}
+
+#[test]
+fn test_render_unchanged_config_is_identical() {
+    let config = parse(HAND_WRITTEN);
+    assert_eq!(render(Some(HAND_WRITTEN), &config).unwrap(), HAND_WRITTEN);
+}
+
+#[test]
+fn test_render_removes_and_appends_repositories() {
+    let mut config = parse(HAND_WRITTEN);
+    config.repositories.remove(0);
+    config.repositories.push(repo("my-org", "worker", None));
+
+    let rendered = render(Some(HAND_WRITTEN), &config).unwrap();
+    let reparsed: Config = toml::from_str(&rendered).unwrap();
+
+    let paths: Vec<String> = reparsed.repositories.iter().map(|r| r.path()).collect();
+    assert_eq!(paths, vec!["my-org/web", "my-org/worker"]);
+    assert!(!rendered.contains("# main backend"));
+    assert!(rendered.starts_with("# Team repositories"));
+}
+
+#[test]
+fn test_render_keeps_legacy_table_for_single_repository() {
+    let original = "# legacy\n[repository]\nowner = \"o\"\nname = \"r\"\n";
+    let mut config = parse(original);
+    config.repositories[0].alias = Some("Main".to_string());
+
+    let rendered = render(Some(original), &config).unwrap();
+    assert!(rendered.contains("[repository]"));
+    assert!(!rendered.contains("[[repositories]]"));
+    assert!(rendered.contains("alias = \"Main\""));
+
+    config.repositories.push(repo("o", "second", None));
+    let rendered = render(Some(original), &config).unwrap();
+    assert!(!rendered.contains("[repository]"));
+    assert!(rendered.contains("# legacy"));
+
+    assert_eq!(parse(&rendered).repositories.len(), 2);
+}
+
+#[test]
+fn test_render_updates_overwrite_policy() {
+    let mut config = parse(HAND_WRITTEN);
+    config.repositories[1].overwrite = Some("if-older-than=7d".to_string());
+
+    let rendered = render(Some(HAND_WRITTEN), &config).unwrap();
+    assert!(rendered.contains("name = \"web\"\noverwrite = \"if-older-than=7d\""));
+
+    let mut config = parse(&rendered);
+    config.repositories[1].overwrite = None;
+    assert_eq!(render(Some(&rendered), &config).unwrap(), HAND_WRITTEN);
+}
+
+#[test]
+fn test_render_updates_include() {
+    let mut config = parse(HAND_WRITTEN);
+    config.include = vec!["../team/repos.toml".to_string()];
+
+    let rendered = render(Some(HAND_WRITTEN), &config).unwrap();
+    let reparsed: Config = toml::from_str(&rendered).unwrap();
+    assert_eq!(reparsed.include, config.include);
+
+    config.include.clear();
+    let rendered = render(Some(&rendered), &config).unwrap();
+    assert!(!rendered.contains("include"));
+}
+
+#[test]
+fn test_render_without_original_serializes() {
+    let config = Config {
+        repositories: vec![repo("o", "r", None)],
+        ..Default::default()
+    };
+    let rendered = render(None, &config).unwrap();
+    assert!(rendered.contains("[[repositories]]"));
+}
+
+#[test]
+fn test_replace_file_skips_backup() {
+    let temp_dir = TempDir::new().unwrap();
+    let path = temp_dir.path().join("state.json");
+
+    replace_file(&path, "first").unwrap();
+    replace_file(&path, "second").unwrap();
+    assert_eq!(fs::read_to_string(&path).unwrap(), "second");
+    assert!(!backup_path(&path).exists());
+}
+
+#[cfg(unix)]
+#[test]
+fn test_write_atomic_keeps_permissions() {
+    use std::os::unix::fs::PermissionsExt;
+
+    let temp_dir = TempDir::new().unwrap();
+    let path = temp_dir.path().join("config.toml");
+    fs::write(&path, "first").unwrap();
+    fs::set_permissions(&path, fs::Permissions::from_mode(0o600)).unwrap();
+
+    write_atomic(&path, "second").unwrap();
+    let mode = fs::metadata(&path).unwrap().permissions().mode();
+    assert_eq!(mode & 0o777, 0o600);
+}
+
+#[test]
+fn test_write_atomic_keeps_backup() {
+    let temp_dir = TempDir::new().unwrap();
+    let path = temp_dir.path().join("config.toml");
+
+    write_atomic(&path, "first").unwrap();
+    assert!(!backup_path(&path).exists());
+
+    write_atomic(&path, "second").unwrap();
+    assert_eq!(fs::read_to_string(&path).unwrap(), "second");
+    assert_eq!(fs::read_to_string(backup_path(&path)).unwrap(), "first");
+
+    let leftovers: Vec<_> = fs::read_dir(temp_dir.path())
+        .unwrap()
+        .filter_map(|e| e.ok())
+        .filter(|e| e.file_name().to_string_lossy().contains(".tmp-"))
+        .collect();
+    assert!(leftovers.is_empty());
+}
+
+#[test]
+fn test_save_creates_parent_directories() {
+    let temp_dir = TempDir::new().unwrap();
+    let path = temp_dir.path().join("nested").join("config.toml");
+    let config = Config {
+        repositories: vec![repo("o", "r", Some("Alias"))],
+        ..Default::default()
+    };
+
+    save(&path, &config).unwrap();
+
+    let loaded = Config::load_editable(path.to_str().unwrap()).unwrap();
+    assert_eq!(loaded.repositories[0].alias.as_deref(), Some("Alias"));
+}
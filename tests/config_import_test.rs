@@ -0,0 +1,168 @@
+use std::sync::Arc;
+
+use anyhow::Result;
+use regex::Regex;
+
+use github_secrets::app::App;
+use github_secrets::config::{Config, Repository};
+use github_secrets::github::{OwnerKind, RemoteOwner, RemoteRepository};
+use github_secrets::import::{ImportOptions, filter_candidates, matches};
+
+mod common;
+use common::{FakeGitHub, FakePrompt};
+
+fn remote(owner: &str, name: &str, archived: bool, topics: &[&str]) -> RemoteRepository {
+    RemoteRepository {
+        name: name.to_string(),
+        owner: RemoteOwner {
+            login: owner.to_string(),
+        },
+        archived,
+        topics: topics.iter().map(|t| t.to_string()).collect(),
+    }
+}
+
+fn options() -> ImportOptions {
+    ImportOptions {
+        owner: "my-org".to_string(),
+        kind: OwnerKind::Organization,
+        filter: None,
+        topic: None,
+        exclude_archived: false,
+    }
+}
+
+fn existing(paths: &[(&str, &str)]) -> Config {
+    Config {
+        repositories: paths
+            .iter()
+            .map(|(owner, name)| Repository {
+                owner: owner.to_string(),
+                name: name.to_string(),
+                ..Default::default()
+            })
+            .collect(),
+        ..Default::default()
+    }
+}
+
+#[test]
+fn test_matches_filters() {
+    let repo = remote("my-org", "service-api", true, &["payments"]);
+
+    assert!(matches(&repo, &options()));
+
+    let mut opts = options();
+    opts.exclude_archived = true;
+    assert!(!matches(&repo, &opts));
+
+    let mut opts = options();
+    opts.filter = Some(Regex::new("^service-").unwrap());
+    assert!(matches(&repo, &opts));
+    opts.filter = Some(Regex::new("^legacy-").unwrap());
+    assert!(!matches(&repo, &opts));
+
+    let mut opts = options();
+    opts.topic = Some("Payments".to_string());
+    assert!(matches(&repo, &opts));
+    opts.topic = Some("billing".to_string());
+    assert!(!matches(&repo, &opts));
+}
+
+#[test]
+fn test_filter_candidates_skips_existing_and_invalid() {
+    let listing = vec![
+        remote("my-org", "api", false, &[]),
+        remote("my-org", "web", false, &[]),
+        remote("my-org", &"x".repeat(101), false, &[]),
+    ];
+    let config = existing(&[("My-Org", "API")]);
+
+    let candidates = filter_candidates(&listing, &options(), &config);
+
+    assert_eq!(candidates.repositories.len(), 1);
+    assert_eq!(candidates.repositories[0].path(), "my-org/web");
+    assert_eq!(candidates.already_configured, 1);
+    assert_eq!(candidates.invalid.len(), 1);
+}
+
+#[test]
+fn test_add_repositories_deduplicates() {
+    let mut config = existing(&[("my-org", "api")]);
+    let added = config.add_repositories(vec![
+        Repository {
+            owner: "my-org".to_string(),
+            name: "api".to_string(),
+            ..Default::default()
+        },
+        Repository {
+            owner: "my-org".to_string(),
+            name: "web".to_string(),
+            ..Default::default()
+        },
+        Repository {
+            owner: "my-org".to_string(),
+            name: "web".to_string(),
+            ..Default::default()
+        },
+    ]);
+
+    assert_eq!(added, 1);
+    assert_eq!(config.repositories.len(), 2);
+}
+
+/// GitHub listing `api`, `web` and `worker` for `my-org`.
+fn github() -> FakeGitHub {
+    FakeGitHub::default().with_remote_repositories(vec![
+        remote("my-org", "api", false, &[]),
+        remote("my-org", "web", false, &[]),
+        remote("my-org", "worker", false, &[]),
+    ])
+}
+
+#[tokio::test]
+async fn test_config_import_with_deps_appends_chosen() -> Result<()> {
+    let config = existing(&[("my-org", "api")]);
+    let github = github();
+    let prompt = FakePrompt::default().picking_last_import();
+
+    let updated = App::config_import_with_deps(
+        &github,
+        &prompt,
+        Arc::new("token".to_string()),
+        &options(),
+        config,
+    )
+    .await?
+    .expect("config should be updated");
+
+    assert_eq!(
+        github.listings(),
+        vec![("my-org".to_string(), OwnerKind::Organization)]
+    );
+    // The already configured repository is hidden from the preview.
+    assert_eq!(
+        *prompt.import_candidates.lock().unwrap(),
+        vec![vec!["my-org/web", "my-org/worker"]]
+    );
+    let paths: Vec<String> = updated.repositories.iter().map(|r| r.path()).collect();
+    assert_eq!(paths, vec!["my-org/api", "my-org/worker"]);
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_config_import_with_deps_nothing_new() -> Result<()> {
+    let config = existing(&[("my-org", "api"), ("my-org", "web"), ("my-org", "worker")]);
+
+    let updated = App::config_import_with_deps(
+        &github(),
+        &FakePrompt::default().picking_last_import(),
+        Arc::new("token".to_string()),
+        &options(),
+        config,
+    )
+    .await?;
+
+    assert!(updated.is_none());
+    Ok(())
+}
@@ -0,0 +1,245 @@
+use github_secrets::config::Config;
+use github_secrets::paths::{find_config_layers, select_config_layers};
+use serial_test::serial;
+use std::env;
+use std::fs;
+use tempfile::TempDir;
+
+fn write(dir: &std::path::Path, name: &str, content: &str) -> std::path::PathBuf {
+    let path = dir.join(name);
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent).unwrap();
+    }
+    fs::write(&path, content).unwrap();
+    path.canonicalize().unwrap()
+}
+
+#[test]
+fn test_layers_merge_and_deduplicate_by_path() {
+    let temp_dir = TempDir::new().unwrap();
+    let local = write(
+        temp_dir.path(),
+        "local/config.toml",
+        r#"
+[[repositories]]
+owner = "my-org"
+name = "api"
+alias = "Local API"
+"#,
+    );
+    let personal = write(
+        temp_dir.path(),
+        "xdg/config.toml",
+        r#"
+[[repositories]]
+owner = "My-Org"
+name = "API"
+alias = "Personal API"
+
+[[repositories]]
+owner = "me"
+name = "dotfiles"
+"#,
+    );
+
+    let config = Config::load_layered(&[local.clone(), personal.clone()]).unwrap();
+
+    assert_eq!(config.repositories.len(), 2);
+    assert_eq!(config.repositories[0].alias.as_deref(), Some("Local API"));
+    assert_eq!(config.repositories[0].source.as_ref(), Some(&local));
+    assert_eq!(config.repositories[1].path(), "me/dotfiles");
+    assert_eq!(config.repositories[1].source.as_ref(), Some(&personal));
+}
+
+#[test]
+fn test_include_relative_to_including_file() {
+    let temp_dir = TempDir::new().unwrap();
+    let team = write(
+        temp_dir.path(),
+        "team/repos.toml",
+        r#"
+[[repositories]]
+owner = "my-org"
+name = "shared"
+
+[[repositories]]
+owner = "my-org"
+name = "api"
+alias = "Team API"
+"#,
+    );
+    let main = write(
+        temp_dir.path(),
+        "project/config.toml",
+        r#"
+include = ["../team/repos.toml"]
+
+[[repositories]]
+owner = "my-org"
+name = "api"
+alias = "My API"
+"#,
+    );
+
+    let config = Config::load_layered(std::slice::from_ref(&main)).unwrap();
+
+    let paths: Vec<String> = config.repositories.iter().map(|r| r.path()).collect();
+    assert_eq!(paths, vec!["my-org/api", "my-org/shared"]);
+    assert_eq!(config.repositories[0].alias.as_deref(), Some("My API"));
+    assert_eq!(config.repositories[0].source.as_ref(), Some(&main));
+    assert_eq!(config.repositories[1].source.as_ref(), Some(&team));
+}
+
+#[test]
+fn test_include_only_file_is_valid() {
+    let temp_dir = TempDir::new().unwrap();
+    write(
+        temp_dir.path(),
+        "repos.toml",
+        "[[repositories]]\nowner = \"o\"\nname = \"r\"\n",
+    );
+    let main = write(
+        temp_dir.path(),
+        "config.toml",
+        "include = [\"repos.toml\"]\n",
+    );
+
+    let config = Config::load_layered(&[main]).unwrap();
+    assert_eq!(config.repositories.len(), 1);
+}
+
+#[test]
+fn test_include_cycle_terminates() {
+    let temp_dir = TempDir::new().unwrap();
+    let a = write(
+        temp_dir.path(),
+        "a.toml",
+        "include = [\"b.toml\"]\n[[repositories]]\nowner = \"o\"\nname = \"a\"\n",
+    );
+    write(
+        temp_dir.path(),
+        "b.toml",
+        "include = [\"a.toml\"]\n[[repositories]]\nowner = \"o\"\nname = \"b\"\n",
+    );
+
+    let config = Config::load_layered(&[a]).unwrap();
+    assert_eq!(config.repositories.len(), 2);
+}
+
+#[test]
+fn test_missing_include_errors_with_context() {
+    let temp_dir = TempDir::new().unwrap();
+    let main = write(
+        temp_dir.path(),
+        "config.toml",
+        "include = [\"missing.toml\"]\n[[repositories]]\nowner = \"o\"\nname = \"r\"\n",
+    );
+
+    let err = Config::load_layered(&[main]).unwrap_err();
+    assert!(format!("{:#}", err).contains("Failed to include 'missing.toml'"));
+}
+
+#[test]
+fn test_include_survives_serialization() {
+    let temp_dir = TempDir::new().unwrap();
+    let main = write(
+        temp_dir.path(),
+        "config.toml",
+        "include = [\"team.toml\"]\n[[repositories]]\nowner = \"o\"\nname = \"r\"\n",
+    );
+
+    let config = Config::load_editable(main.to_str().unwrap()).unwrap();
+    let serialized = toml::to_string(&config).unwrap();
+    assert!(serialized.contains("include = [\"team.toml\"]"));
+    assert!(!serialized.contains("source"));
+}
+
+#[test]
+fn test_select_config_layers() {
+    let temp_dir = TempDir::new().unwrap();
+    let present = write(temp_dir.path(), "config.toml", "");
+    let missing = temp_dir.path().join("missing.toml");
+
+    assert_eq!(
+        select_config_layers(&[missing.clone(), present.clone(), present.clone()], None),
+        vec![present.clone()]
+    );
+    assert_eq!(
+        select_config_layers(std::slice::from_ref(&present), Some(&missing)),
+        Vec::<std::path::PathBuf>::new()
+    );
+    assert_eq!(
+        select_config_layers(&[missing, present.clone()], Some(&present)),
+        vec![present]
+    );
+}
+
+#[test]
+#[serial]
+fn test_personal_xdg_entry_overrides_team_entry() {
+    let temp_dir = TempDir::new().unwrap();
+    let team = write(
+        temp_dir.path(),
+        "project/config.toml",
+        r#"
+[[repositories]]
+owner = "my-org"
+name = "api"
+alias = "Team API"
+
+[[repositories]]
+owner = "my-org"
+name = "shared"
+"#,
+    );
+    let personal = write(
+        temp_dir.path(),
+        "xdg/github-secrets/config.toml",
+        r#"
+[[repositories]]
+owner = "my-org"
+name = "api"
+alias = "My API"
+"#,
+    );
+
+    let original_cwd = env::current_dir().ok();
+    let original_xdg = env::var("XDG_CONFIG_HOME").ok();
+    let original_config_path = env::var("CONFIG_PATH").ok();
+    unsafe {
+        env::remove_var("CONFIG_PATH");
+        env::set_var("XDG_CONFIG_HOME", temp_dir.path().join("xdg"));
+    }
+    env::set_current_dir(team.parent().unwrap()).unwrap();
+
+    let layers = find_config_layers();
+
+    if let Some(cwd) = original_cwd {
+        let _ = env::set_current_dir(cwd);
+    }
+    unsafe {
+        match original_xdg {
+            Some(value) => env::set_var("XDG_CONFIG_HOME", value),
+            None => env::remove_var("XDG_CONFIG_HOME"),
+        }
+        if let Some(value) = original_config_path {
+            env::set_var("CONFIG_PATH", value);
+        }
+    }
+
+    let layers: Vec<_> = layers.iter().map(|l| l.canonicalize().unwrap()).collect();
+    let personal_at = layers.iter().position(|l| *l == personal).unwrap();
+    let team_at = layers.iter().position(|l| *l == team).unwrap();
+    assert!(personal_at < team_at, "{:?}", layers);
+
+    let config = Config::load_layered(&layers).unwrap();
+    let api = &config.repositories[0];
+    assert_eq!(api.alias.as_deref(), Some("My API"));
+    assert_eq!(api.source.as_ref(), Some(&personal));
+    assert!(
+        config
+            .repositories
+            .iter()
+            .any(|r| r.path() == "my-org/shared")
+    );
+}
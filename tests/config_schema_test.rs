@@ -0,0 +1,166 @@
+use github_secrets::app::App;
+use github_secrets::config::Config;
+use github_secrets::config_edit::backup_path;
+use github_secrets::schema::{Location, check_duplicates, locate, migrate};
+use std::fs;
+use tempfile::TempDir;
+
+fn load(content: &str) -> anyhow::Result<Config> {
+    let temp_dir = TempDir::new().unwrap();
+    let path = temp_dir.path().join("config.toml");
+    fs::write(&path, content).unwrap();
+    Config::load_editable(path.to_str().unwrap())
+}
+
+#[test]
+fn test_locate() {
+    let content = "a = 1\nbb = 2\n";
+    assert_eq!(locate(content, 0), Location { line: 1, column: 1 });
+    assert_eq!(locate(content, 6), Location { line: 2, column: 1 });
+    assert_eq!(locate(content, 9), Location { line: 2, column: 4 });
+}
+
+#[test]
+fn test_unknown_key_reports_line_and_column() {
+    let err =
+        load("[[repositories]]\nowner = \"o\"\nname = \"r\"\naliass = \"typo\"\n").unwrap_err();
+    let message = format!("{:#}", err);
+    assert!(message.contains("aliass"), "{}", message);
+    assert!(message.contains("line 4"), "{}", message);
+}
+
+#[test]
+fn test_unknown_top_level_key_rejected() {
+    assert!(load("repositorys = []\n").is_err());
+}
+
+#[test]
+fn test_duplicate_repository_is_rejected_case_insensitively() {
+    let content = "[[repositories]]\nowner = \"o\"\nname = \"api\"\n\n[[repositories]]\nowner = \"O\"\nname = \"API\"\n";
+    let message = check_duplicates(content).unwrap_err().to_string();
+    assert!(
+        message.contains("duplicate repository 'O/API'"),
+        "{}",
+        message
+    );
+    assert!(message.contains("line 7, column 8"), "{}", message);
+    assert!(
+        message.contains("first defined at line 3, column 8"),
+        "{}",
+        message
+    );
+    assert!(load(content).is_err());
+}
+
+#[test]
+fn test_duplicate_alias_is_rejected() {
+    let content = "[[repositories]]\nowner = \"o\"\nname = \"a\"\nalias = \"Main\"\n\n[[repositories]]\nowner = \"o\"\nname = \"b\"\nalias = \"Main\"\n";
+    let message = check_duplicates(content).unwrap_err().to_string();
+    assert!(message.contains("duplicate alias 'Main'"), "{}", message);
+    assert!(message.contains("line 9"), "{}", message);
+}
+
+#[test]
+fn test_version_checks() {
+    let body = "[[repositories]]\nowner = \"o\"\nname = \"r\"\n";
+    assert_eq!(
+        load(&format!("version = 1\n{}", body)).unwrap().version,
+        Some(1)
+    );
+    assert!(load(body).unwrap().version.is_none());
+
+    let err = load(&format!("version = 99\n{}", body)).unwrap_err();
+    assert!(err.to_string().contains("newer than the supported version"));
+}
+
+#[test]
+fn test_migrate_legacy_repository() {
+    let legacy = "# my config\n\n[repository]\nowner = \"o\"  # owner\nname = \"r\"\n";
+
+    let migrated = migrate(legacy).unwrap().expect("should migrate");
+
+    assert!(
+        migrated.starts_with("# my config\n\nversion = 1\n"),
+        "{}",
+        migrated
+    );
+    assert!(migrated.contains("[[repositories]]"));
+    assert!(!migrated.contains("[repository]"));
+    assert!(migrated.contains("owner = \"o\"  # owner"));
+
+    let config = load(&migrated).unwrap();
+    assert_eq!(config.version, Some(1));
+    assert_eq!(config.repositories[0].path(), "o/r");
+
+    assert!(migrate(&migrated).unwrap().is_none());
+}
+
+#[test]
+fn test_legacy_table_rejected_where_it_would_be_misread() {
+    let legacy = "[repository]\nowner = \"o\"\nname = \"r\"\n";
+
+    let err = load(&format!("version = 1\n\n{}", legacy)).unwrap_err();
+    let message = format!("{:#}", err);
+    assert!(message.contains("line 3"), "{}", message);
+    assert!(message.contains("config migrate"), "{}", message);
+
+    let both = format!(
+        "[[repositories]]\nowner = \"o\"\nname = \"a\"\n\n{}",
+        legacy
+    );
+    let err = load(&both).unwrap_err();
+    assert!(
+        format!("{:#}", err).contains("can't be combined"),
+        "{:#}",
+        err
+    );
+    assert!(migrate(&both).is_err());
+}
+
+#[test]
+fn test_migrate_converts_legacy_table_in_versioned_file() {
+    let content = "version = 1\n\n[repository]\nowner = \"o\"\nname = \"r\"\n";
+    let migrated = migrate(content).unwrap().expect("should migrate");
+    assert!(migrated.contains("[[repositories]]"), "{}", migrated);
+    assert_eq!(migrated.matches("version = 1").count(), 1);
+    assert_eq!(load(&migrated).unwrap().repositories[0].path(), "o/r");
+}
+
+#[test]
+fn test_example_config_loads() {
+    let content =
+        fs::read_to_string(concat!(env!("CARGO_MANIFEST_DIR"), "/config.example.toml")).unwrap();
+    let config = load(&content).unwrap();
+    assert_eq!(config.version, Some(1));
+    assert_eq!(config.repositories[0].path(), "example-org/example-repo");
+}
+
+#[test]
+fn test_migrate_adds_version_only() {
+    let content = "[[repositories]]\nowner = \"o\"\nname = \"r\"\n";
+    let migrated = migrate(content).unwrap().unwrap();
+    assert_eq!(migrated, format!("version = 1\n\n{}", content));
+}
+
+#[test]
+fn test_migrate_rejects_newer_version() {
+    assert!(migrate("version = 2\n").is_err());
+}
+
+#[test]
+fn test_config_migrate_at_writes_backup() {
+    let temp_dir = TempDir::new().unwrap();
+    let path = temp_dir.path().join("config.toml");
+    let legacy = "[repository]\nowner = \"o\"\nname = \"r\"\n";
+    fs::write(&path, legacy).unwrap();
+
+    assert!(App::config_migrate_at(&path).unwrap());
+    assert_eq!(fs::read_to_string(backup_path(&path)).unwrap(), legacy);
+    assert!(
+        fs::read_to_string(&path)
+            .unwrap()
+            .contains("[[repositories]]")
+    );
+
+    assert!(!App::config_migrate_at(&path).unwrap());
+}
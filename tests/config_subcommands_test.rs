@@ -0,0 +1,180 @@
+use github_secrets::app::{App, ConfigAction};
+use github_secrets::config::{Config, Repository, parse_repository_path};
+use std::fs;
+use tempfile::TempDir;
+
+fn repo(owner: &str, name: &str) -> Repository {
+    Repository {
+        owner: owner.to_string(),
+        name: name.to_string(),
+        ..Default::default()
+    }
+}
+
+#[test]
+fn test_parse_repository_path() {
+    assert_eq!(
+        parse_repository_path("my-org/api").unwrap(),
+        ("my-org".to_string(), "api".to_string())
+    );
+    assert!(parse_repository_path("my-org").is_err());
+    assert!(parse_repository_path("my-org/").is_err());
+    assert!(parse_repository_path("/api").is_err());
+}
+
+#[test]
+fn test_add_remove_set_alias() {
+    let mut config = Config::default();
+    config.add_repository(repo("o", "a")).unwrap();
+    config.add_repository(repo("o", "b")).unwrap();
+
+    let err = config.add_repository(repo("O", "A")).unwrap_err();
+    assert!(err.to_string().contains("already configured"));
+
+    config.set_alias("o/b", Some("Bee".to_string())).unwrap();
+    assert_eq!(config.repositories[1].alias.as_deref(), Some("Bee"));
+    config.set_alias("o/b", None).unwrap();
+    assert!(config.repositories[1].alias.is_none());
+
+    let removed = config.remove_repository("o/a").unwrap();
+    assert_eq!(removed.path(), "o/a");
+    assert!(config.remove_repository("o/a").is_err());
+    assert!(config.set_alias("o/missing", None).is_err());
+}
+
+#[test]
+fn test_add_repository_rejects_invalid_label() {
+    let mut config = Config::default();
+    let mut invalid = repo("o", "a");
+    invalid.tags = vec!["bad tag".to_string()];
+    assert!(config.add_repository(invalid).is_err());
+}
+
+#[test]
+fn test_config_edit_at_creates_missing_file() {
+    let temp_dir = TempDir::new().unwrap();
+    let path = temp_dir.path().join("github-secrets").join("config.toml");
+
+    let mut new_repo = repo("my-org", "api");
+    new_repo.alias = Some("API".to_string());
+    new_repo.tags = vec!["payments".to_string()];
+    App::config_edit_at(&path, ConfigAction::Add(new_repo)).unwrap();
+
+    let config = Config::from_file(path.to_str().unwrap()).unwrap();
+    assert_eq!(config.repositories.len(), 1);
+    assert_eq!(config.repositories[0].alias.as_deref(), Some("API"));
+    assert_eq!(config.repositories[0].tags, vec!["payments".to_string()]);
+}
+
+#[test]
+fn test_config_edit_at_keeps_comments() {
+    let temp_dir = TempDir::new().unwrap();
+    let path = temp_dir.path().join("config.toml");
+    fs::write(
+        &path,
+        "# team config\n\n[[repositories]]\nowner = \"o\"\nname = \"a\"  # primary\n",
+    )
+    .unwrap();
+
+    App::config_edit_at(
+        &path,
+        ConfigAction::SetAlias {
+            repository: "o/a".to_string(),
+            alias: Some("Primary".to_string()),
+        },
+    )
+    .unwrap();
+    App::config_edit_at(&path, ConfigAction::Add(repo("o", "b"))).unwrap();
+    App::config_edit_at(&path, ConfigAction::Remove("o/b".to_string())).unwrap();
+
+    let content = fs::read_to_string(&path).unwrap();
+    assert!(content.starts_with("# team config"));
+    assert!(content.contains("name = \"a\"  # primary"));
+    assert!(content.contains("alias = \"Primary\""));
+    assert!(!content.contains("\"b\""));
+}
+
+#[test]
+fn test_config_edit_at_unknown_repository_errors_without_writing() {
+    let temp_dir = TempDir::new().unwrap();
+    let path = temp_dir.path().join("config.toml");
+
+    let result = App::config_edit_at(&path, ConfigAction::Remove("o/a".to_string()));
+
+    assert!(result.is_err());
+    assert!(!path.exists());
+}
+
+#[test]
+fn test_format_config_list() {
+    let mut config = Config::default();
+    let mut first = repo("o", "a");
+    first.alias = Some("Alpha".to_string());
+    first.groups = vec!["prod".to_string()];
+    config.repositories = vec![first, repo("o", "b")];
+
+    let text = App::format_config_list(&config, false).unwrap();
+    assert_eq!(text, "Alpha (o/a) [groups: prod]\no/b");
+
+    let json = App::format_config_list(&config, true).unwrap();
+    let parsed: serde_json::Value = serde_json::from_str(&json).unwrap();
+    assert_eq!(parsed[0]["owner"], "o");
+    assert_eq!(parsed[0]["alias"], "Alpha");
+    assert_eq!(parsed[0]["groups"][0], "prod");
+    assert!(parsed[1]["alias"].is_null());
+
+    let empty = App::format_config_list(&Config::default(), false).unwrap();
+    assert_eq!(empty, "No repositories configured.");
+    assert_eq!(
+        App::format_config_list(&Config::default(), true).unwrap(),
+        "[]"
+    );
+}
+
+#[test]
+fn test_config_edit_refuses_entries_from_another_layer() {
+    let temp_dir = TempDir::new().unwrap();
+    let personal = temp_dir.path().join("personal.toml");
+    let team = temp_dir.path().join("team.toml");
+    fs::write(
+        &personal,
+        "[[repositories]]\nowner = \"o\"\nname = \"mine\"\n",
+    )
+    .unwrap();
+    fs::write(
+        &team,
+        "[[repositories]]\nowner = \"o\"\nname = \"shared\"\n",
+    )
+    .unwrap();
+    let layers = vec![personal.clone(), team.clone()];
+
+    let err = App::config_edit_layered(
+        &personal,
+        &layers,
+        ConfigAction::Remove("O/Shared".to_string()),
+    )
+    .unwrap_err()
+    .to_string();
+    assert!(err.contains("is defined in"), "{}", err);
+    assert!(err.contains("team.toml"), "{}", err);
+    assert!(fs::read_to_string(&team).unwrap().contains("shared"));
+
+    let err = App::config_edit_layered(
+        &personal,
+        &layers,
+        ConfigAction::SetAlias {
+            repository: "o/shared".to_string(),
+            alias: Some("Shared".to_string()),
+        },
+    )
+    .unwrap_err();
+    assert!(err.to_string().contains("team.toml"));
+
+    App::config_edit_layered(
+        &personal,
+        &layers,
+        ConfigAction::Remove("o/mine".to_string()),
+    )
+    .unwrap();
+    assert!(!fs::read_to_string(&personal).unwrap().contains("mine"));
+}
@@ -84,6 +84,7 @@ fn test_repository_methods() {
         owner: "owner".to_string(),
         name: "repo".to_string(),
         alias: None,
+        ..Default::default()
     };
 
     assert_eq!(repo.path(), "owner/repo");
@@ -93,6 +94,7 @@ fn test_repository_methods() {
         owner: "owner".to_string(),
         name: "repo".to_string(),
         alias: Some("Alias".to_string()),
+        ..Default::default()
     };
 
     assert_eq!(repo_with_alias.display_name(), "Alias (owner/repo)");
@@ -0,0 +1,204 @@
+use chrono::{Duration, Utc};
+use github_secrets::config::Repository;
+use github_secrets::doctor::{
+    CheckResult, CheckStatus, check_clock_skew, check_config, check_config_paths, check_env_files,
+    check_repository_access, check_token_format, check_token_identity, count_by_status,
+};
+use github_secrets::github::TokenInfo;
+use std::fs;
+use tempfile::TempDir;
+
+#[test]
+fn test_check_config_paths_reports_chosen_and_missing() {
+    let temp_dir = TempDir::new().unwrap();
+    let missing = temp_dir.path().join("missing.toml");
+    let present = temp_dir.path().join("config.toml");
+    fs::write(&present, "").unwrap();
+
+    let result = check_config_paths(&[missing.clone(), present.clone()], None);
+
+    assert_eq!(result.status, CheckStatus::Pass);
+    assert!(result.summary.contains("config.toml"));
+    assert_eq!(result.details.len(), 2);
+    assert!(result.details[0].ends_with("(missing)"));
+    assert!(result.details[1].ends_with("(using)"));
+}
+
+#[test]
+fn test_check_config_paths_merges_all_existing() {
+    let temp_dir = TempDir::new().unwrap();
+    let first = temp_dir.path().join("first.toml");
+    let second = temp_dir.path().join("second.toml");
+    fs::write(&first, "").unwrap();
+    fs::write(&second, "").unwrap();
+
+    let result = check_config_paths(&[first.clone(), second.clone()], None);
+
+    assert_eq!(result.status, CheckStatus::Pass);
+    assert!(result.details.iter().all(|d| d.ends_with("(using)")));
+
+    let result = check_config_paths(&[first.clone(), second], Some(&first));
+    assert!(result.details[1].ends_with("(found, not merged)"));
+}
+
+#[test]
+fn test_check_config_paths_none_found_fails() {
+    let temp_dir = TempDir::new().unwrap();
+    let missing = temp_dir.path().join("config.toml");
+
+    let result = check_config_paths(&[missing], None);
+
+    assert_eq!(result.status, CheckStatus::Fail);
+    assert!(result.hint.is_some());
+}
+
+#[test]
+fn test_check_config_paths_missing_override_warns() {
+    let temp_dir = TempDir::new().unwrap();
+    let override_path = temp_dir.path().join("override.toml");
+    let present = temp_dir.path().join("config.toml");
+    fs::write(&present, "").unwrap();
+
+    let result = check_config_paths(&[override_path.clone(), present], Some(&override_path));
+
+    assert_eq!(result.status, CheckStatus::Warn);
+    assert!(result.summary.contains("CONFIG_PATH"));
+}
+
+#[test]
+fn test_check_env_files() {
+    let temp_dir = TempDir::new().unwrap();
+    let env_path = temp_dir.path().join(".env");
+    fs::write(&env_path, "GITHUB_TOKEN=x").unwrap();
+
+    let loaded = check_env_files(
+        std::slice::from_ref(&env_path),
+        std::slice::from_ref(&env_path),
+    );
+    assert_eq!(loaded.status, CheckStatus::Pass);
+    assert!(loaded.details[0].ends_with("(loaded)"));
+
+    let not_loaded = check_env_files(&[env_path], &[]);
+    assert_eq!(not_loaded.status, CheckStatus::Warn);
+    assert!(not_loaded.details[0].ends_with("(found, not loaded)"));
+}
+
+#[test]
+fn test_check_config_valid_and_invalid() {
+    let temp_dir = TempDir::new().unwrap();
+    let config_path = temp_dir.path().join("config.toml");
+
+    fs::write(
+        &config_path,
+        "[[repositories]]\nowner = \"owner\"\nname = \"repo\"\n",
+    )
+    .unwrap();
+    let (result, config) = check_config(std::slice::from_ref(&config_path));
+    assert_eq!(result.status, CheckStatus::Pass);
+    assert!(config.is_some());
+    assert_eq!(
+        result.details,
+        vec![format!(
+            "owner/repo (from {})",
+            config_path.canonicalize().unwrap().display()
+        )]
+    );
+
+    fs::write(&config_path, "").unwrap();
+    let (result, config) = check_config(std::slice::from_ref(&config_path));
+    assert_eq!(result.status, CheckStatus::Fail);
+    assert!(config.is_none());
+    assert!(result.summary.contains("No repositories found"));
+
+    let (result, _) = check_config(&[]);
+    assert_eq!(result.status, CheckStatus::Fail);
+}
+
+#[test]
+fn test_check_token_format() {
+    assert_eq!(check_token_format(None).status, CheckStatus::Fail);
+    assert_eq!(check_token_format(Some("short")).status, CheckStatus::Fail);
+
+    let classic = format!("ghp_{}", "a".repeat(36));
+    let result = check_token_format(Some(&classic));
+    assert_eq!(result.status, CheckStatus::Pass);
+    assert!(result.summary.contains("classic"));
+
+    let unknown = "x".repeat(40);
+    assert_eq!(check_token_format(Some(&unknown)).status, CheckStatus::Warn);
+
+    let padded = format!(" {} ", classic);
+    assert_eq!(check_token_format(Some(&padded)).status, CheckStatus::Warn);
+}
+
+#[test]
+fn test_check_token_identity() {
+    let ok = Ok(TokenInfo {
+        login: "octocat".to_string(),
+        scopes: vec!["repo".to_string()],
+        server_time: None,
+    });
+    let result = check_token_identity(&ok);
+    assert_eq!(result.status, CheckStatus::Pass);
+    assert!(result.summary.contains("octocat"));
+
+    let missing_scope = Ok(TokenInfo {
+        login: "octocat".to_string(),
+        scopes: vec!["gist".to_string()],
+        server_time: None,
+    });
+    assert_eq!(
+        check_token_identity(&missing_scope).status,
+        CheckStatus::Warn
+    );
+
+    let err = Err(anyhow::anyhow!("Bad credentials"));
+    let result = check_token_identity(&err);
+    assert_eq!(result.status, CheckStatus::Fail);
+    assert!(result.summary.contains("Bad credentials"));
+}
+
+#[test]
+fn test_check_repository_access() {
+    let repo = Repository {
+        owner: "owner".to_string(),
+        name: "repo".to_string(),
+        alias: None,
+        ..Default::default()
+    };
+
+    let result = check_repository_access(&repo, &Ok(()));
+    assert_eq!(result.status, CheckStatus::Pass);
+    assert!(result.name.contains("owner/repo"));
+
+    let result = check_repository_access(&repo, &Err(anyhow::anyhow!("Not Found")));
+    assert_eq!(result.status, CheckStatus::Fail);
+}
+
+#[test]
+fn test_check_clock_skew_thresholds() {
+    let now = Utc::now();
+
+    assert_eq!(check_clock_skew(Some(now), now).status, CheckStatus::Pass);
+    assert_eq!(
+        check_clock_skew(Some(now - Duration::seconds(60)), now).status,
+        CheckStatus::Warn
+    );
+    assert_eq!(
+        check_clock_skew(Some(now + Duration::seconds(600)), now).status,
+        CheckStatus::Fail
+    );
+    assert_eq!(check_clock_skew(None, now).status, CheckStatus::Warn);
+}
+
+#[test]
+fn test_count_by_status() {
+    let results = vec![
+        CheckResult::pass("a", "ok"),
+        CheckResult::warn("b", "meh", "fix"),
+        CheckResult::fail("c", "bad", "fix"),
+        CheckResult::fail("d", "bad", "fix"),
+    ];
+
+    assert_eq!(count_by_status(&results), (1, 1, 2));
+}
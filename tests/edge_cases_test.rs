@@ -52,6 +52,7 @@ fn test_repository_path_formatting() {
         owner: "test-owner".to_string(),
         name: "test-repo".to_string(),
         alias: None,
+        ..Default::default()
     };
 
     assert_eq!(repo.path(), "test-owner/test-repo");
@@ -63,6 +64,7 @@ fn test_repository_display_name_with_alias() {
         owner: "test-owner".to_string(),
         name: "test-repo".to_string(),
         alias: Some("My Test Repo".to_string()),
+        ..Default::default()
     };
 
     let display = repo.display_name();
@@ -76,6 +78,7 @@ fn test_repository_display_name_without_alias() {
         owner: "test-owner".to_string(),
         name: "test-repo".to_string(),
         alias: None,
+        ..Default::default()
     };
 
     assert_eq!(repo.display_name(), "test-owner/test-repo");
@@ -99,7 +102,7 @@ fn test_validate_secret_key_edge_cases() {
 
     // Test special characters
     assert!(validation::validate_secret_key("key_with_underscore").is_ok());
-    assert!(validation::validate_secret_key("key-with-hyphen").is_ok());
+    assert!(validation::validate_secret_key("key-with-hyphen").is_err());
     assert!(validation::validate_secret_key("key123").is_ok());
     assert!(validation::validate_secret_key("KEY_UPPER").is_ok());
     assert!(validation::validate_secret_key("key_lower").is_ok());
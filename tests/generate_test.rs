@@ -0,0 +1,170 @@
+use std::sync::Arc;
+
+use anyhow::Result;
+use crossterm::event::KeyCode;
+use ratatui::{Terminal, backend::TestBackend};
+use tempfile::TempDir;
+
+use github_secrets::app::{App, RunOptions};
+use github_secrets::generate::{Charset, GenerateSpec, generate};
+use github_secrets::prompt;
+
+mod common;
+use common::{FakeEventSource, FakeGitHub, FakePrompt, NoopRateLimiter, ctrl, key, repositories};
+
+#[test]
+fn test_parse_generate_spec() {
+    assert_eq!(
+        "WEBHOOK_SECRET:64:hex".parse::<GenerateSpec>().unwrap(),
+        GenerateSpec {
+            key: "WEBHOOK_SECRET".to_string(),
+            length: 64,
+            charset: Charset::Hex,
+        }
+    );
+    assert_eq!(
+        "API_KEY".parse::<GenerateSpec>().unwrap(),
+        GenerateSpec {
+            key: "API_KEY".to_string(),
+            length: 32,
+            charset: Charset::Alphanumeric,
+        }
+    );
+    assert_eq!(
+        "T:10:URLSAFE".parse::<GenerateSpec>().unwrap().charset,
+        Charset::UrlSafe
+    );
+
+    assert!("bad key:10".parse::<GenerateSpec>().is_err());
+    assert!("KEY:0".parse::<GenerateSpec>().is_err());
+    assert!("KEY:ten".parse::<GenerateSpec>().is_err());
+    assert!("KEY:10:emoji".parse::<GenerateSpec>().is_err());
+    assert!("KEY:10:hex:extra".parse::<GenerateSpec>().is_err());
+}
+
+#[test]
+fn test_generated_values_use_charset() {
+    for charset in [
+        Charset::Hex,
+        Charset::Base64,
+        Charset::Alphanumeric,
+        Charset::UrlSafe,
+    ] {
+        let value = generate(200, charset);
+        assert_eq!(value.len(), 200);
+        assert!(
+            value.bytes().all(|b| charset.alphabet().contains(&b)),
+            "{} produced {}",
+            charset,
+            value
+        );
+    }
+    assert_ne!(
+        generate(32, Charset::Alphanumeric),
+        generate(32, Charset::Alphanumeric)
+    );
+}
+
+#[tokio::test]
+async fn test_run_uploads_generated_values_and_writes_output() -> Result<()> {
+    let temp_dir = TempDir::new()?;
+    let output = temp_dir.path().join("generated.env");
+    let github = FakeGitHub::default();
+
+    App::run_with_options(
+        &github,
+        &FakePrompt::default(),
+        &mut NoopRateLimiter,
+        Arc::new("token".to_string()),
+        repositories(&["a", "b"]),
+        &RunOptions {
+            generate: vec!["WEBHOOK_SECRET:16:hex".parse()?],
+            generate_output: Some(output.clone()),
+            ..Default::default()
+        },
+    )
+    .await?;
+
+    let written = std::fs::read_to_string(&output)?;
+    let (key, value) = written.trim().split_once('=').unwrap();
+    assert_eq!(key, "WEBHOOK_SECRET");
+    assert_eq!(value.len(), 16);
+
+    // The same value goes to every repository.
+    let uploads: Vec<_> = github
+        .uploads()
+        .into_iter()
+        .map(|(_, name, value)| (name, value))
+        .collect();
+    assert_eq!(
+        uploads,
+        vec![
+            ("WEBHOOK_SECRET".to_string(), value.to_string()),
+            ("WEBHOOK_SECRET".to_string(), value.to_string()),
+        ]
+    );
+
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::PermissionsExt;
+        let mode = std::fs::metadata(&output)?.permissions().mode();
+        assert_eq!(mode & 0o777, 0o600);
+    }
+    Ok(())
+}
+
+#[test]
+fn test_ctrl_g_generates_value_in_tui() {
+    let events = vec![
+        key(KeyCode::Char('K')),
+        key(KeyCode::Enter),
+        ctrl('g'),
+        key(KeyCode::Enter),
+        key(KeyCode::Esc),
+        key(KeyCode::Char('y')),
+    ];
+    let mut terminal = Terminal::new(TestBackend::new(100, 30)).unwrap();
+    let mut src = FakeEventSource::new(events);
+
+    let secrets = prompt::prompt_secrets_with(&mut terminal, &mut src).unwrap();
+    assert_eq!(secrets.len(), 1);
+    assert_eq!(secrets[0].value.len(), 32);
+    assert!(secrets[0].value.chars().all(|c| c.is_ascii_alphanumeric()));
+}
+
+/// Generate a value with Ctrl+G after `before`, and return the last screen.
+fn screen_after_ctrl_g(before: Vec<crossterm::event::Event>) -> String {
+    let mut events = vec![key(KeyCode::Char('K')), key(KeyCode::Enter)];
+    events.extend(before);
+    events.push(ctrl('g'));
+    let mut terminal = Terminal::new(TestBackend::new(140, 30)).unwrap();
+    let mut src = FakeEventSource::stopping_after(events);
+
+    assert!(prompt::prompt_secrets_with(&mut terminal, &mut src).is_err());
+    let buffer = terminal.backend().buffer();
+    (0..buffer.area.height)
+        .map(|y| {
+            (0..buffer.area.width)
+                .map(|x| buffer.get(x, y).symbol())
+                .collect::<String>()
+        })
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+#[test]
+fn test_ctrl_g_shows_value_only_when_revealed() {
+    let value = regex::Regex::new("[A-Za-z0-9]{32}").unwrap();
+
+    let hidden = screen_after_ctrl_g(vec![]);
+    assert!(hidden.contains("Ctrl+R: reveal it"), "{}", hidden);
+    assert!(!value.is_match(&hidden), "{}", hidden);
+
+    let revealed = screen_after_ctrl_g(vec![ctrl('r')]);
+    assert!(
+        revealed.contains("Generated (store it now)"),
+        "{}",
+        revealed
+    );
+    assert!(value.is_match(&revealed), "{}", revealed);
+}
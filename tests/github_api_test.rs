@@ -25,7 +25,7 @@ async fn test_successful_secret_encryption() {
     crypto_box::xsalsa_keypair(&mut public_key_bytes, &mut secret_key_bytes)
         .expect("Failed to generate keypair");
 
-    let public_key = general_purpose::STANDARD.encode(&public_key_bytes);
+    let public_key = general_purpose::STANDARD.encode(public_key_bytes);
     let encrypted = client.encrypt_secret(&public_key, "test-secret-value");
 
     // Encryption should succeed with a valid key
@@ -76,7 +76,7 @@ fn test_validation_before_api_calls() {
 
     // Valid secret key
     assert!(validation::validate_secret_key("VALID_SECRET_KEY").is_ok());
-    assert!(validation::validate_secret_key("valid-secret-123").is_ok());
+    assert!(validation::validate_secret_key("valid_secret_123").is_ok());
 
     // Invalid repository owner
     assert!(validation::validate_repo_owner("").is_err());
@@ -110,7 +110,7 @@ async fn test_encrypt_secret_invalid_public_key() {
     assert!(error_msg.contains("decode") || error_msg.contains("Invalid"));
 
     // Test with wrong length
-    let short_key = general_purpose::STANDARD.encode(&[0u8; 16]);
+    let short_key = general_purpose::STANDARD.encode([0u8; 16]);
     let result2 = client.encrypt_secret(&short_key, "secret");
     assert!(result2.is_err());
     let error_msg2 = result2.unwrap_err().to_string();
@@ -330,7 +330,7 @@ async fn test_encryption_uniqueness() {
     crypto_box::xsalsa_keypair(&mut public_key_bytes, &mut secret_key_bytes)
         .expect("Failed to generate keypair");
 
-    let public_key = general_purpose::STANDARD.encode(&public_key_bytes);
+    let public_key = general_purpose::STANDARD.encode(public_key_bytes);
 
     let secret1 = "secret-value-1";
     let secret2 = "secret-value-2";
@@ -359,6 +359,9 @@ fn test_validation_prevents_invalid_api_calls() {
         "key with spaces",
         "key@invalid",
         "key#invalid",
+        "key-with-hyphen",
+        "1KEY",
+        "GITHUB_KEY",
         &too_long, // Too long
     ];
 
@@ -371,7 +374,7 @@ fn test_validation_prevents_invalid_api_calls() {
     }
 
     // Valid secret keys should pass
-    let valid_keys = vec!["VALID_KEY", "valid-key", "valid_key", "key123", "KEY_123"];
+    let valid_keys = vec!["VALID_KEY", "valid_key", "key123", "KEY_123"];
 
     for key in valid_keys {
         assert!(
@@ -32,6 +32,35 @@ async fn test_get_secret_info_not_found() {
     assert!(info.is_none(), "Expected None for 404 response");
 }
 
+#[tokio::test]
+async fn test_get_secret_info_returns_stored_name() {
+    let mock_server = MockServer::start().await;
+
+    Mock::given(method("GET"))
+        .and(path_regex(r"/repos/.*/.*/actions/secrets/db_url"))
+        .respond_with(ResponseTemplate::new(200).set_body_string(
+            r#"{"name":"DB_URL","created_at":"2024-01-01T00:00:00Z","updated_at":"2024-02-01T00:00:00Z"}"#,
+        ))
+        .mount(&mock_server)
+        .await;
+
+    let octocrab = Octocrab::builder()
+        .personal_token("test-token".to_string())
+        .base_uri(mock_server.uri())
+        .unwrap()
+        .build()
+        .unwrap();
+    let client = GitHubClient::with_octocrab(octocrab, "owner".to_string(), "repo".to_string());
+
+    let info = client
+        .get_secret_info("db_url")
+        .await
+        .expect("call should succeed")
+        .expect("secret should exist");
+    assert_eq!(info.name, "DB_URL");
+    assert_eq!(info.updated_at.as_deref(), Some("2024-02-01T00:00:00Z"));
+}
+
 #[tokio::test]
 async fn test_update_secret_success_204() {
     let mock_server = MockServer::start().await;
@@ -40,7 +69,7 @@ async fn test_update_secret_success_204() {
     let mut public_key_bytes = [0u8; sodoken::crypto_box::XSALSA_PUBLICKEYBYTES];
     let mut secret_key_bytes = [0u8; sodoken::crypto_box::XSALSA_SECRETKEYBYTES];
     sodoken::crypto_box::xsalsa_keypair(&mut public_key_bytes, &mut secret_key_bytes).unwrap();
-    let public_key_b64 = general_purpose::STANDARD.encode(&public_key_bytes);
+    let public_key_b64 = general_purpose::STANDARD.encode(public_key_bytes);
     let pk_body = format!(r#"{{"key_id":"test-key-id","key":"{}"}}"#, public_key_b64);
 
     Mock::given(method("GET"))
@@ -80,7 +109,7 @@ async fn test_update_secret_api_error_propagates() {
     let mut public_key_bytes = [0u8; sodoken::crypto_box::XSALSA_PUBLICKEYBYTES];
     let mut secret_key_bytes = [0u8; sodoken::crypto_box::XSALSA_SECRETKEYBYTES];
     sodoken::crypto_box::xsalsa_keypair(&mut public_key_bytes, &mut secret_key_bytes).unwrap();
-    let public_key_b64 = general_purpose::STANDARD.encode(&public_key_bytes);
+    let public_key_b64 = general_purpose::STANDARD.encode(public_key_bytes);
     let pk_body = format!(r#"{{"key_id":"test-key-id","key":"{}"}}"#, public_key_b64);
 
     Mock::given(method("GET"))
@@ -116,3 +145,240 @@ async fn test_update_secret_api_error_propagates() {
         msg.contains("GitHub API error") || msg.contains("Bad Request") || msg.contains("Invalid")
     );
 }
+
+#[tokio::test]
+async fn test_get_token_info_reads_login_scopes_and_date() {
+    let mock_server = MockServer::start().await;
+
+    Mock::given(method("GET"))
+        .and(path_regex(r"^/user$"))
+        .respond_with(
+            ResponseTemplate::new(200)
+                .insert_header("x-oauth-scopes", "repo, workflow")
+                .insert_header("date", "Tue, 15 Nov 1994 08:12:31 GMT")
+                .set_body_string(r#"{"login":"octocat"}"#),
+        )
+        .mount(&mock_server)
+        .await;
+
+    let octocrab = Octocrab::builder()
+        .personal_token("test-token".to_string())
+        .base_uri(mock_server.uri())
+        .unwrap()
+        .build()
+        .unwrap();
+
+    let client = GitHubClient::with_octocrab(octocrab, String::new(), String::new());
+
+    let info = client.get_token_info().await.expect("call should succeed");
+    assert_eq!(info.login, "octocat");
+    assert_eq!(
+        info.scopes,
+        vec!["repo".to_string(), "workflow".to_string()]
+    );
+    assert_eq!(
+        info.server_time.map(|t| t.to_rfc3339()),
+        Some("1994-11-15T08:12:31+00:00".to_string())
+    );
+}
+
+#[tokio::test]
+async fn test_get_token_info_bad_credentials() {
+    let mock_server = MockServer::start().await;
+
+    Mock::given(method("GET"))
+        .and(path_regex(r"^/user$"))
+        .respond_with(
+            ResponseTemplate::new(401).set_body_string(r#"{"message":"Bad credentials"}"#),
+        )
+        .mount(&mock_server)
+        .await;
+
+    let octocrab = Octocrab::builder()
+        .personal_token("test-token".to_string())
+        .base_uri(mock_server.uri())
+        .unwrap()
+        .build()
+        .unwrap();
+
+    let client = GitHubClient::with_octocrab(octocrab, String::new(), String::new());
+
+    let res = client.get_token_info().await;
+    assert!(res.is_err());
+    let msg = github_secrets::error::format_error_chain(&res.unwrap_err());
+    assert!(msg.contains("Bad credentials"), "unexpected error: {}", msg);
+}
+
+#[tokio::test]
+async fn test_check_secrets_access_not_found() {
+    let mock_server = MockServer::start().await;
+
+    Mock::given(method("GET"))
+        .and(path_regex(r"/repos/.*/.*/actions/secrets/public-key"))
+        .respond_with(ResponseTemplate::new(404).set_body_string(r#"{"message":"Not Found"}"#))
+        .mount(&mock_server)
+        .await;
+
+    let octocrab = Octocrab::builder()
+        .personal_token("test-token".to_string())
+        .base_uri(mock_server.uri())
+        .unwrap()
+        .build()
+        .unwrap();
+
+    let client = GitHubClient::with_octocrab(octocrab, "owner".to_string(), "repo".to_string());
+
+    assert!(client.check_secrets_access().await.is_err());
+}
+
+#[tokio::test]
+async fn test_list_repositories_follows_pages() {
+    use wiremock::matchers::query_param;
+
+    let mock_server = MockServer::start().await;
+
+    let first_page: Vec<String> = (0..100)
+        .map(|i| {
+            format!(
+                r#"{{"name":"repo{}","owner":{{"login":"my-org"}},"archived":false,"topics":[]}}"#,
+                i
+            )
+        })
+        .collect();
+    Mock::given(method("GET"))
+        .and(path_regex(r"^/orgs/my-org/repos$"))
+        .and(query_param("page", "1"))
+        .respond_with(
+            ResponseTemplate::new(200).set_body_string(format!("[{}]", first_page.join(","))),
+        )
+        .mount(&mock_server)
+        .await;
+    Mock::given(method("GET"))
+        .and(path_regex(r"^/orgs/my-org/repos$"))
+        .and(query_param("page", "2"))
+        .respond_with(ResponseTemplate::new(200).set_body_string(
+            r#"[{"name":"last","owner":{"login":"my-org"},"archived":true,"topics":["t"]}]"#,
+        ))
+        .mount(&mock_server)
+        .await;
+
+    let octocrab = Octocrab::builder()
+        .personal_token("test-token".to_string())
+        .base_uri(mock_server.uri())
+        .unwrap()
+        .build()
+        .unwrap();
+
+    let client = GitHubClient::with_octocrab(octocrab, "my-org".to_string(), String::new());
+
+    let repos = client
+        .list_repositories(github_secrets::github::OwnerKind::Organization)
+        .await
+        .expect("call should succeed");
+    assert_eq!(repos.len(), 101);
+    assert_eq!(repos[100].name, "last");
+    assert!(repos[100].archived);
+    assert_eq!(repos[100].topics, vec!["t".to_string()]);
+}
+
+#[tokio::test]
+async fn test_list_secrets_follows_pages() {
+    use wiremock::matchers::query_param;
+
+    let mock_server = MockServer::start().await;
+
+    let first_page: Vec<String> = (0..100)
+        .map(|i| {
+            format!(
+                r#"{{"name":"SECRET_{}","created_at":"2024-01-01T00:00:00Z","updated_at":"2024-01-02T00:00:00Z"}}"#,
+                i
+            )
+        })
+        .collect();
+    Mock::given(method("GET"))
+        .and(path_regex(r"^/repos/owner/repo/actions/secrets$"))
+        .and(query_param("page", "1"))
+        .respond_with(ResponseTemplate::new(200).set_body_string(format!(
+            r#"{{"total_count":101,"secrets":[{}]}}"#,
+            first_page.join(",")
+        )))
+        .mount(&mock_server)
+        .await;
+    Mock::given(method("GET"))
+        .and(path_regex(r"^/repos/owner/repo/actions/secrets$"))
+        .and(query_param("page", "2"))
+        .respond_with(ResponseTemplate::new(200).set_body_string(
+            r#"{"total_count":101,"secrets":[{"name":"LAST","updated_at":"2024-02-01T00:00:00Z"}]}"#,
+        ))
+        .mount(&mock_server)
+        .await;
+
+    let octocrab = Octocrab::builder()
+        .personal_token("test-token".to_string())
+        .base_uri(mock_server.uri())
+        .unwrap()
+        .build()
+        .unwrap();
+
+    let client = GitHubClient::with_octocrab(octocrab, "owner".to_string(), "repo".to_string());
+
+    let secrets = client.list_secrets().await.expect("call should succeed");
+    assert_eq!(secrets.len(), 101);
+    assert_eq!(secrets[100].name, "LAST");
+    assert_eq!(secrets[100].created_at, None);
+    assert_eq!(
+        secrets[0].updated_at.as_deref(),
+        Some("2024-01-02T00:00:00Z")
+    );
+}
+
+#[tokio::test]
+async fn test_list_repositories_of_authenticated_user_includes_private() {
+    use wiremock::matchers::query_param;
+
+    let mock_server = MockServer::start().await;
+
+    Mock::given(method("GET"))
+        .and(path_regex(r"^/user$"))
+        .respond_with(ResponseTemplate::new(200).set_body_string(r#"{"login":"Me"}"#))
+        .mount(&mock_server)
+        .await;
+    Mock::given(method("GET"))
+        .and(path_regex(r"^/user/repos$"))
+        .and(query_param("affiliation", "owner"))
+        .respond_with(ResponseTemplate::new(200).set_body_string(
+            r#"[{"name":"secret-project","owner":{"login":"me"},"archived":false,"topics":[]}]"#,
+        ))
+        .mount(&mock_server)
+        .await;
+    Mock::given(method("GET"))
+        .and(path_regex(r"^/users/someone-else/repos$"))
+        .and(query_param("type", "owner"))
+        .respond_with(ResponseTemplate::new(200).set_body_string(
+            r#"[{"name":"public-project","owner":{"login":"someone-else"},"archived":false,"topics":[]}]"#,
+        ))
+        .mount(&mock_server)
+        .await;
+
+    let octocrab = || {
+        Octocrab::builder()
+            .personal_token("test-token".to_string())
+            .base_uri(mock_server.uri())
+            .unwrap()
+            .build()
+            .unwrap()
+    };
+
+    let own = GitHubClient::with_octocrab(octocrab(), "me".to_string(), String::new())
+        .list_repositories(github_secrets::github::OwnerKind::User)
+        .await
+        .expect("call should succeed");
+    assert_eq!(own.len(), 1);
+    assert_eq!(own[0].name, "secret-project");
+
+    let other = GitHubClient::with_octocrab(octocrab(), "someone-else".to_string(), String::new())
+        .list_repositories(github_secrets::github::OwnerKind::User)
+        .await
+        .expect("call should succeed");
+    assert_eq!(other[0].name, "public-project");
+}
@@ -60,6 +60,7 @@ fn test_repository_path_formatting() {
         owner: "my-org".to_string(),
         name: "my-repo".to_string(),
         alias: None,
+        ..Default::default()
     };
 
     assert_eq!(repo.path(), "my-org/my-repo");
@@ -72,12 +73,14 @@ fn test_repository_display_name_variations() {
         owner: "org".to_string(),
         name: "repo".to_string(),
         alias: Some("My Awesome Repo".to_string()),
+        ..Default::default()
     };
 
     let repo_without_alias = Repository {
         owner: "org".to_string(),
         name: "repo".to_string(),
         alias: None,
+        ..Default::default()
     };
 
     assert_eq!(repo_with_alias.display_name(), "My Awesome Repo (org/repo)");
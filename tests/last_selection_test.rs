@@ -0,0 +1,128 @@
+use anyhow::Result;
+use crossterm::event::{Event, KeyCode};
+use ratatui::{Terminal, backend::TestBackend};
+use serial_test::serial;
+use std::env;
+use tempfile::TempDir;
+
+use github_secrets::app::{App, RunOptions};
+use github_secrets::app_deps::PromptInterface;
+use github_secrets::config::{self, Repository};
+use github_secrets::last_selection;
+use github_secrets::overwrite::OverwriteAnswer;
+use github_secrets::paths::get_last_selection_path;
+use github_secrets::prompt::{SecretPair, select_repositories_with_last};
+
+mod common;
+use common::{FakeEventSource, key};
+
+fn repos() -> Vec<Repository> {
+    ["api", "web", "worker"]
+        .iter()
+        .map(|name| Repository {
+            owner: "org".to_string(),
+            name: name.to_string(),
+            ..Default::default()
+        })
+        .collect()
+}
+
+fn select(events: Vec<Event>, last: &[usize]) -> Result<Vec<usize>> {
+    let mut terminal = Terminal::new(TestBackend::new(100, 20)).unwrap();
+    let mut src = FakeEventSource::ending_with(events, KeyCode::Enter);
+    select_repositories_with_last(&mut terminal, &mut src, &repos(), last)
+}
+
+#[test]
+fn test_save_and_load_by_path() -> Result<()> {
+    let temp_dir = TempDir::new()?;
+    let path = temp_dir.path().join("state").join("last-selection.json");
+    assert!(last_selection::load(&path)?.repositories.is_empty());
+
+    last_selection::save(&path, &repos(), &[0, 2])?;
+    let saved = last_selection::load(&path)?;
+    assert_eq!(saved.repositories, vec!["org/api", "org/worker"]);
+
+    // Matching is by path, not position, and ignores case.
+    let mut reordered = repos();
+    reordered.reverse();
+    reordered[0].name = "WORKER".to_string();
+    reordered.pop();
+    assert_eq!(saved.indices(&reordered), vec![0]);
+    assert_eq!(saved.missing(&reordered), vec!["org/api"]);
+    Ok(())
+}
+
+#[test]
+fn test_menu_prechecks_last_selection() -> Result<()> {
+    assert_eq!(select(vec![], &[0, 2])?, vec![0, 2]);
+
+    // Select everything, then `l` goes back to the last selection.
+    let events = vec![key(KeyCode::Char(' ')), key(KeyCode::Char('l'))];
+    assert_eq!(select(events, &[1])?, vec![1]);
+
+    // Without a last selection `l` does nothing.
+    let events = vec![key(KeyCode::Char(' ')), key(KeyCode::Char('l'))];
+    assert_eq!(select(events, &[])?, vec![0, 1, 2]);
+    Ok(())
+}
+
+/// The menu must not be shown when the command line decides the selection.
+struct NoMenuPrompt;
+
+impl PromptInterface for NoMenuPrompt {
+    fn select_repositories(&self, _: &[Repository]) -> Result<Vec<usize>> {
+        panic!("selection menu shown")
+    }
+    fn prompt_secrets(&self) -> Result<Vec<SecretPair>> {
+        Ok(vec![])
+    }
+    fn confirm_secret_update(&self, _: &str, _: &str, _: Option<&str>) -> Result<OverwriteAnswer> {
+        Ok(OverwriteAnswer::NO)
+    }
+    fn confirm_retry(&self) -> Result<bool> {
+        Ok(false)
+    }
+    fn confirm_action(&self, _: &str) -> Result<bool> {
+        Ok(false)
+    }
+    fn manage_config(&self, _: config::Config) -> Result<Option<config::Config>> {
+        Ok(None)
+    }
+    fn select_import(&self, _: &[Repository]) -> Result<Vec<usize>> {
+        Ok(vec![])
+    }
+}
+
+#[test]
+#[serial]
+fn test_last_flag_reuses_saved_selection() -> Result<()> {
+    let temp_dir = TempDir::new()?;
+    let original = env::var("XDG_STATE_HOME").ok();
+    unsafe { env::set_var("XDG_STATE_HOME", temp_dir.path()) };
+
+    let options = RunOptions {
+        last: true,
+        ..Default::default()
+    };
+    let before_save = App::resolve_selection(&NoMenuPrompt, &repos(), &options);
+
+    last_selection::save(&get_last_selection_path(), &repos(), &[1, 2])?;
+    let mut fewer = repos();
+    fewer.pop();
+    let after_save = App::resolve_selection(&NoMenuPrompt, &fewer, &options);
+
+    match original {
+        Some(value) => unsafe { env::set_var("XDG_STATE_HOME", value) },
+        None => unsafe { env::remove_var("XDG_STATE_HOME") },
+    }
+
+    let err = before_save.unwrap_err();
+    assert!(
+        err.to_string().contains("No saved selection yet"),
+        "{}",
+        err
+    );
+    assert_eq!(after_save?, vec![1]);
+    Ok(())
+}
@@ -0,0 +1,202 @@
+use std::sync::Arc;
+
+use anyhow::Result;
+use crossterm::event::{Event, KeyCode};
+use ratatui::{Terminal, backend::TestBackend};
+
+use github_secrets::app::{App, RunOptions};
+use github_secrets::lint::{MAX_VALUE_BYTES, Severity, blocks_upload, lint_value};
+use github_secrets::prompt;
+
+mod common;
+use common::{FakeEventSource, FakeGitHub, FakePrompt, NoopRateLimiter, key, repositories, typed};
+
+fn messages(key: &str, value: &str) -> Vec<(Severity, String)> {
+    lint_value(key, value)
+        .into_iter()
+        .map(|issue| (issue.severity, issue.message))
+        .collect()
+}
+
+#[test]
+fn test_clean_values_pass() {
+    assert!(lint_value("DB_PASSWORD", "hunter2-but-longer").is_empty());
+    assert!(lint_value("PEM", "-----BEGIN KEY-----\nabc\n-----END KEY-----").is_empty());
+    assert!(lint_value("NAME", "two words").is_empty());
+}
+
+#[test]
+fn test_whitespace_newline_and_quotes_are_warnings() {
+    assert_eq!(
+        messages("K", "value\n"),
+        vec![(Severity::Warning, "value ends with a newline".to_string())]
+    );
+    assert_eq!(
+        messages("K", " value\t"),
+        vec![
+            (
+                Severity::Warning,
+                "value starts with whitespace".to_string()
+            ),
+            (Severity::Warning, "value ends with whitespace".to_string()),
+        ]
+    );
+    assert_eq!(
+        messages("K", "\"quoted\""),
+        vec![(Severity::Warning, "value is wrapped in quotes".to_string())]
+    );
+    assert_eq!(
+        messages("K", "'changeme'"),
+        vec![
+            (Severity::Warning, "value is wrapped in quotes".to_string()),
+            (
+                Severity::Warning,
+                "value looks like a placeholder".to_string()
+            ),
+        ]
+    );
+}
+
+#[test]
+fn test_placeholders_are_warnings() {
+    for value in [
+        "changeme",
+        "TODO",
+        "xxxxxx",
+        "<your-token>",
+        "${API_KEY}",
+        "your_key_here",
+    ] {
+        assert_eq!(
+            messages("K", value),
+            vec![(
+                Severity::Warning,
+                "value looks like a placeholder".to_string()
+            )],
+            "{}",
+            value
+        );
+    }
+}
+
+#[test]
+fn test_errors() {
+    let severities = |key: &str, value: &str| -> Vec<Severity> {
+        lint_value(key, value).iter().map(|i| i.severity).collect()
+    };
+    assert_eq!(severities("K", "   "), vec![Severity::Error]);
+    assert_eq!(severities("K", "\"\""), vec![Severity::Error]);
+    assert_eq!(severities("API_KEY", "api_key"), vec![Severity::Error]);
+    assert_eq!(severities("K", "bin\0ary"), vec![Severity::Error]);
+    assert_eq!(severities("K", "bad \u{FFFD} byte"), vec![Severity::Error]);
+    assert_eq!(
+        severities("K", &"a".repeat(MAX_VALUE_BYTES + 1)),
+        vec![Severity::Error]
+    );
+    assert!(lint_value("K", &"a".repeat(MAX_VALUE_BYTES)).is_empty());
+}
+
+#[test]
+fn test_strict_mode_blocks_warnings() {
+    let warnings = lint_value("K", "value ");
+    assert!(!blocks_upload(&warnings, false));
+    assert!(blocks_upload(&warnings, true));
+    assert!(blocks_upload(&lint_value("K", "K"), false));
+    assert!(!blocks_upload(&[], true));
+}
+
+async fn run(github: &FakeGitHub, secrets: &[(&str, &str)], strict: bool) -> Result<()> {
+    App::run_with_options(
+        github,
+        &FakePrompt::with_secrets(secrets),
+        &mut NoopRateLimiter,
+        Arc::new("token".to_string()),
+        repositories(&["a"]),
+        &RunOptions {
+            strict,
+            ..Default::default()
+        },
+    )
+    .await
+}
+
+#[tokio::test]
+async fn test_run_uploads_values_with_warnings_unless_strict() -> Result<()> {
+    let github = FakeGitHub::default();
+    run(&github, &[("TOKEN", "abc "), ("OTHER", "fine")], false).await?;
+    assert_eq!(github.uploaded_names(), vec!["TOKEN", "OTHER"]);
+
+    let strict = FakeGitHub::default();
+    let err = run(&strict, &[("TOKEN", "abc "), ("OTHER", "fine")], true)
+        .await
+        .unwrap_err();
+    assert!(err.to_string().contains("TOKEN failed lint"), "{}", err);
+    assert!(strict.uploaded_names().is_empty());
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_run_refuses_lint_errors_before_uploading() {
+    let github = FakeGitHub::default();
+    let err = run(&github, &[("OK", "fine"), ("API_KEY", "API_KEY")], false)
+        .await
+        .unwrap_err();
+    assert!(err.to_string().contains("API_KEY"), "{}", err);
+    assert!(github.uploaded_names().is_empty());
+}
+
+#[tokio::test]
+async fn test_run_lints_resolved_references() -> Result<()> {
+    unsafe { std::env::set_var("LINT_TEST_QUOTED", "\"from-dotenv\"") };
+
+    let github = FakeGitHub::default();
+    run(&github, &[("A", "env:LINT_TEST_QUOTED")], false).await?;
+    assert_eq!(github.uploaded_names(), vec!["A"]);
+
+    // In strict mode the reference fails like any other resolution error.
+    let strict = FakeGitHub::default();
+    run(&strict, &[("A", "env:LINT_TEST_QUOTED")], true).await?;
+    assert!(strict.uploaded_names().is_empty());
+    Ok(())
+}
+
+fn run_tui(events: Vec<Event>) -> Vec<(String, String)> {
+    let mut terminal = Terminal::new(TestBackend::new(100, 30)).unwrap();
+    let mut src = FakeEventSource::new(events);
+    prompt::prompt_secrets_with(&mut terminal, &mut src)
+        .unwrap()
+        .into_iter()
+        .map(|s| (s.key, s.value))
+        .collect()
+}
+
+#[test]
+fn test_tui_needs_second_enter_for_warnings() {
+    let mut events = typed("TOKEN");
+    events.push(key(KeyCode::Enter));
+    events.extend(typed("abc "));
+    events.push(key(KeyCode::Enter));
+    events.push(key(KeyCode::Enter));
+    events.push(key(KeyCode::Esc));
+    events.push(key(KeyCode::Char('y')));
+
+    assert_eq!(
+        run_tui(events),
+        vec![("TOKEN".to_string(), "abc ".to_string())]
+    );
+}
+
+#[test]
+fn test_tui_rejects_lint_errors() {
+    let mut events = typed("TOKEN");
+    events.push(key(KeyCode::Enter));
+    events.extend(typed("token"));
+    events.push(key(KeyCode::Enter));
+    events.push(key(KeyCode::Enter));
+    // Still in value mode: ESC goes back to the key, a second ESC finishes.
+    events.push(key(KeyCode::Esc));
+    events.push(key(KeyCode::Esc));
+    events.push(key(KeyCode::Char('y')));
+
+    assert!(run_tui(events).is_empty());
+}
@@ -0,0 +1,124 @@
+use crossterm::event::KeyCode;
+use ratatui::{Terminal, backend::TestBackend, style::Color};
+
+use github_secrets::prompt::{self, InputMode, SecretPair, ValueDisplay};
+
+mod common;
+use common::{FakeEventSource, ctrl, key};
+
+fn pair(key: &str, value: &str) -> SecretPair {
+    SecretPair {
+        key: key.to_string(),
+        value: value.to_string(),
+    }
+}
+
+/// Render the input screen and return its text, one string per row.
+fn render(
+    secrets: &[SecretPair],
+    mode: InputMode,
+    current_value: &str,
+    mask: ValueDisplay,
+) -> Vec<String> {
+    let mut terminal = Terminal::new(TestBackend::new(100, 24)).unwrap();
+    terminal
+        .draw(|f| {
+            prompt::render_secret_input_ui(
+                f,
+                secrets,
+                "KEY",
+                current_value,
+                &mode,
+                "",
+                Color::Yellow,
+                mask,
+                &Default::default(),
+            )
+        })
+        .unwrap();
+    let buffer = terminal.backend().buffer();
+    (0..buffer.area.height)
+        .map(|y| {
+            (0..buffer.area.width)
+                .map(|x| buffer.get(x, y).symbol())
+                .collect()
+        })
+        .collect()
+}
+
+fn row_with<'a>(rows: &'a [String], needle: &str) -> &'a str {
+    rows.iter()
+        .find(|row| row.contains(needle))
+        .unwrap_or_else(|| panic!("'{}' not rendered", needle))
+}
+
+#[test]
+fn test_added_values_do_not_leak_length() {
+    let secrets = [pair("SHORT", "abc"), pair("LONG", &"z".repeat(40))];
+    let rows = render(&secrets, InputMode::Key, "", ValueDisplay::default());
+
+    let short = row_with(&rows, "SHORT =");
+    let long = row_with(&rows, "LONG =");
+    assert_eq!(short.matches('•').count(), long.matches('•').count());
+    assert!(!long.contains('z'));
+    assert!(!short.contains("chars"));
+}
+
+#[test]
+fn test_length_hint_is_optional() {
+    let secrets = [pair("SHORT", "abc")];
+    let mask = ValueDisplay {
+        show_length: true,
+        ..Default::default()
+    };
+    let rows = render(&secrets, InputMode::Value, "hunter2", mask);
+
+    assert!(row_with(&rows, "SHORT =").contains("(3 chars)"));
+    assert!(row_with(&rows, "Secret value:").contains("(7 chars)"));
+}
+
+#[test]
+fn test_typed_value_is_masked_unless_revealed() {
+    let hidden = render(&[], InputMode::Value, "hunter2", ValueDisplay::default());
+    let value_row = row_with(&hidden, "Secret value:");
+    assert!(!value_row.contains("hunter2"));
+    assert_eq!(
+        value_row.matches('•').count(),
+        render(&[], InputMode::Value, "x", ValueDisplay::default())
+            .iter()
+            .find(|r| r.contains("Secret value:"))
+            .unwrap()
+            .matches('•')
+            .count()
+    );
+
+    let revealed = render(
+        &[],
+        InputMode::Value,
+        "hunter2",
+        ValueDisplay {
+            reveal: true,
+            ..Default::default()
+        },
+    );
+    assert!(row_with(&revealed, "Secret value:").contains("hunter2"));
+}
+
+#[test]
+fn test_ctrl_r_toggles_reveal_without_changing_value() {
+    let mut events = vec![key(KeyCode::Char('K')), key(KeyCode::Enter)];
+    events.extend("pw".chars().map(|c| key(KeyCode::Char(c))));
+    events.push(ctrl('r'));
+    events.push(ctrl('r'));
+    events.push(ctrl('n'));
+    events.push(key(KeyCode::Enter));
+    events.push(key(KeyCode::Esc));
+    events.push(key(KeyCode::Char('y')));
+
+    let mut terminal = Terminal::new(TestBackend::new(100, 24)).unwrap();
+    let mut src = FakeEventSource::new(events);
+    let secrets = prompt::prompt_secrets_with(&mut terminal, &mut src).unwrap();
+
+    assert_eq!(secrets.len(), 1);
+    assert_eq!(secrets[0].value, "pw");
+}
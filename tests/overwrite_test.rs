@@ -0,0 +1,325 @@
+use std::sync::Arc;
+
+use anyhow::Result;
+use chrono::{Duration, Utc};
+
+use github_secrets::app::{App, RunOptions};
+use github_secrets::config;
+use github_secrets::overwrite::{
+    AnswerScope, OverwriteAnswer, OverwriteDecisions, OverwritePolicy,
+};
+
+mod common;
+use common::{FakeGitHub, FakePrompt, NoopRateLimiter};
+
+/// Answer for `keys`, the second one only read for a wider scope.
+fn answer(keys: &str) -> OverwriteAnswer {
+    let mut keys = keys.chars();
+    let first = keys.next().unwrap();
+    OverwriteAnswer::from_keys(first, |_| Ok(keys.next().unwrap())).unwrap()
+}
+
+#[test]
+fn test_answer_keys() {
+    assert_eq!(answer("y"), OverwriteAnswer::YES);
+    assert_eq!(answer("n"), OverwriteAnswer::NO);
+    assert_eq!(answer("x"), OverwriteAnswer::NO);
+    assert_eq!(
+        answer("ry"),
+        OverwriteAnswer::new(true, AnswerScope::Repository)
+    );
+    assert_eq!(
+        answer("sn"),
+        OverwriteAnswer::new(false, AnswerScope::Secret)
+    );
+    assert_eq!(answer("an"), OverwriteAnswer::new(false, AnswerScope::All));
+}
+
+#[test]
+fn test_answer_keys_ignore_case() {
+    // With Caps Lock on, the keys still mean what the prompt says.
+    assert_eq!(answer("Y"), OverwriteAnswer::YES);
+    assert_eq!(answer("N"), OverwriteAnswer::NO);
+    assert_eq!(answer("AY"), OverwriteAnswer::new(true, AnswerScope::All));
+    assert_eq!(
+        answer("RN"),
+        OverwriteAnswer::new(false, AnswerScope::Repository)
+    );
+}
+
+#[test]
+fn test_run_wide_answer_needs_a_second_key() {
+    let mut asked = None;
+    let answer = OverwriteAnswer::from_keys('a', |scope| {
+        asked = Some(scope);
+        Ok('\n')
+    })
+    .unwrap();
+    assert_eq!(asked, Some(AnswerScope::All));
+    assert_eq!(answer, OverwriteAnswer::new(false, AnswerScope::All));
+
+    // A one-off answer doesn't ask again.
+    let answer = OverwriteAnswer::from_keys('Y', |_| unreachable!()).unwrap();
+    assert_eq!(answer, OverwriteAnswer::YES);
+}
+
+#[test]
+fn test_decisions_by_scope() {
+    let mut decisions = OverwriteDecisions::default();
+    assert!(decisions.record("o/a", "ONE", OverwriteAnswer::YES));
+    assert_eq!(decisions.decided("o/a", "ONE"), None);
+
+    assert!(!decisions.record("o/a", "TWO", answer("sn")));
+    assert_eq!(decisions.decided("o/b", "two"), Some(false));
+    assert_eq!(decisions.decided("o/b", "ONE"), None);
+
+    // A later repository-wide answer covers the secret decided before.
+    assert!(decisions.record("o/c", "ONE", answer("ry")));
+    assert_eq!(decisions.decided("o/c", "TWO"), Some(true));
+    assert_eq!(decisions.decided("o/d", "TWO"), Some(false));
+
+    assert!(!decisions.record("o/d", "ONE", answer("an")));
+    assert_eq!(decisions.decided("o/c", "TWO"), Some(false));
+}
+
+#[test]
+fn test_parse_policies() {
+    assert_eq!(
+        "prompt".parse::<OverwritePolicy>().unwrap(),
+        OverwritePolicy::Prompt
+    );
+    assert_eq!(
+        "Always".parse::<OverwritePolicy>().unwrap(),
+        OverwritePolicy::Always
+    );
+    assert_eq!(
+        "if-older-than=7d".parse::<OverwritePolicy>().unwrap(),
+        OverwritePolicy::IfOlderThan(Duration::days(7))
+    );
+    let policy: OverwritePolicy = "if-newer-than=36h".parse().unwrap();
+    assert_eq!(policy.to_string(), "if-newer-than=36h");
+    assert_eq!(
+        OverwritePolicy::IfOlderThan(Duration::days(14)).to_string(),
+        "if-older-than=2w"
+    );
+
+    assert_eq!(
+        "if-newer-than".parse::<OverwritePolicy>().unwrap(),
+        OverwritePolicy::IfNewer
+    );
+    assert_eq!(OverwritePolicy::IfNewer.to_string(), "if-newer-than");
+
+    for invalid in ["sometimes", "if-older-than", "if-older-than=7", "never=7d"] {
+        assert!(invalid.parse::<OverwritePolicy>().is_err(), "{}", invalid);
+    }
+}
+
+#[test]
+fn test_policy_decisions() {
+    let now = Utc::now();
+    let days_ago = |days: i64| (now - Duration::days(days)).to_rfc3339();
+    let older = OverwritePolicy::IfOlderThan(Duration::days(7));
+    let newer = OverwritePolicy::IfNewerThan(Duration::days(7));
+
+    assert_eq!(
+        OverwritePolicy::Prompt.decide(Some(&days_ago(1)), now, now),
+        None
+    );
+    assert_eq!(OverwritePolicy::Always.decide(None, now, now), Some(true));
+    assert_eq!(OverwritePolicy::Never.decide(None, now, now), Some(false));
+    assert_eq!(older.decide(Some(&days_ago(30)), now, now), Some(true));
+    assert_eq!(older.decide(Some(&days_ago(1)), now, now), Some(false));
+    assert_eq!(newer.decide(Some(&days_ago(1)), now, now), Some(true));
+    assert_eq!(newer.decide(Some(&days_ago(30)), now, now), Some(false));
+    // Unknown age: keep the secret.
+    assert_eq!(older.decide(None, now, now), Some(false));
+    assert_eq!(newer.decide(Some("garbage"), now, now), Some(false));
+}
+
+#[test]
+fn test_if_newer_compares_with_the_value() {
+    let now = Utc::now();
+    let days_ago = |days: i64| (now - Duration::days(days)).to_rfc3339();
+    let changed = now - Duration::days(3);
+
+    let policy = OverwritePolicy::IfNewer;
+    assert_eq!(policy.decide(Some(&days_ago(7)), changed, now), Some(true));
+    assert_eq!(policy.decide(Some(&days_ago(1)), changed, now), Some(false));
+    // A value made during the run is newer than any existing secret.
+    assert_eq!(policy.decide(Some(&days_ago(1)), now, now), Some(true));
+    assert_eq!(policy.decide(None, now, now), Some(false));
+}
+
+/// Run with three repositories and three existing secrets, answering with `keys`.
+async fn run(keys: &str) -> Result<(Vec<String>, Vec<String>)> {
+    run_with(keys, &[None, None, None], &RunOptions::default()).await
+}
+
+/// Like [`run`], with an `overwrite` setting per repository and run options.
+async fn run_with(
+    keys: &str,
+    overwrite: &[Option<&str>],
+    options: &RunOptions,
+) -> Result<(Vec<String>, Vec<String>)> {
+    // `ONE` was updated 30 days ago, `TWO` yesterday and `THREE` at an unknown time.
+    let github = FakeGitHub::default().with_secrets_everywhere(&[
+        ("ONE", Some(30)),
+        ("TWO", Some(1)),
+        ("THREE", None),
+    ]);
+    let prompt = FakePrompt::with_secrets(&[
+        ("ONE", "a-real-value"),
+        ("TWO", "a-real-value"),
+        ("THREE", "a-real-value"),
+    ])
+    .answering(keys);
+    let config = config::Config {
+        repositories: ["a", "b", "c"]
+            .iter()
+            .zip(overwrite)
+            .map(|(name, overwrite)| config::Repository {
+                owner: "o".to_string(),
+                name: name.to_string(),
+                overwrite: overwrite.map(str::to_string),
+                ..Default::default()
+            })
+            .collect(),
+        ..Default::default()
+    };
+    App::run_with_options(
+        &github,
+        &prompt,
+        &mut NoopRateLimiter,
+        Arc::new("token".to_string()),
+        config,
+        options,
+    )
+    .await?;
+    let asked = prompt.asked.into_inner().unwrap();
+    let uploaded = github
+        .uploads()
+        .into_iter()
+        .map(|(repo, name, _)| format!("{}:{}", repo, name))
+        .collect();
+    Ok((asked, uploaded))
+}
+
+#[tokio::test]
+async fn test_yes_to_all_asks_once() -> Result<()> {
+    let (asked, uploaded) = run("ay").await?;
+    assert_eq!(asked, vec!["o/a:ONE"]);
+    assert_eq!(uploaded.len(), 9);
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_caps_lock_yes_to_all_overwrites() -> Result<()> {
+    let (asked, uploaded) = run("YAY").await?;
+    assert_eq!(asked, vec!["o/a:ONE", "o/a:TWO"]);
+    assert_eq!(uploaded.len(), 9);
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_no_to_all_after_first_answer() -> Result<()> {
+    let (asked, uploaded) = run("yAN").await?;
+    assert_eq!(asked, vec!["o/a:ONE", "o/a:TWO"]);
+    assert_eq!(uploaded, vec!["a:ONE"]);
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_repository_and_secret_scopes() -> Result<()> {
+    // a: keep ONE everywhere, then overwrite the rest of a.
+    // b: keep the rest of b. c: overwrite TWO, keep THREE.
+    let (asked, uploaded) = run("snryrnyn").await?;
+    assert_eq!(
+        asked,
+        vec!["o/a:ONE", "o/a:TWO", "o/b:TWO", "o/c:TWO", "o/c:THREE"]
+    );
+    assert_eq!(uploaded, vec!["a:TWO", "a:THREE", "c:TWO"]);
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_policies_per_repository() -> Result<()> {
+    // a: only secrets older than a week; b: never; c: asks, and `a` answers for the rest.
+    let (asked, uploaded) = run_with(
+        "ay",
+        &[Some("if-older-than=7d"), Some("never"), None],
+        &RunOptions::default(),
+    )
+    .await?;
+    assert_eq!(asked, vec!["o/c:ONE"]);
+    assert_eq!(uploaded, vec!["a:ONE", "c:ONE", "c:TWO", "c:THREE"]);
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_flag_overrides_repository_policies() -> Result<()> {
+    let options = RunOptions {
+        overwrite: Some("if-newer-than=7d".parse()?),
+        ..Default::default()
+    };
+    let (asked, uploaded) = run_with("", &[Some("always"), Some("never"), None], &options).await?;
+    assert!(asked.is_empty());
+    assert_eq!(uploaded, vec!["a:TWO", "b:TWO", "c:TWO"]);
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_bare_if_newer_than_overwrites_with_typed_values() -> Result<()> {
+    // Typed values are newer than any secret with a known update time.
+    let options = RunOptions {
+        overwrite: Some("if-newer-than".parse()?),
+        ..Default::default()
+    };
+    let (asked, uploaded) = run_with("", &[None, None, None], &options).await?;
+    assert!(asked.is_empty());
+    assert_eq!(
+        uploaded,
+        vec!["a:ONE", "a:TWO", "b:ONE", "b:TWO", "c:ONE", "c:TWO"]
+    );
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_invalid_repository_policy_fails_before_uploading() {
+    let err = run_with("", &[None, Some("sometimes"), None], &RunOptions::default())
+        .await
+        .unwrap_err();
+    assert!(
+        format!("{:#}", err).contains("Invalid overwrite for o/b"),
+        "{:#}",
+        err
+    );
+}
+
+#[test]
+fn test_overwrite_in_config() {
+    let temp_dir = tempfile::TempDir::new().unwrap();
+    let path = temp_dir.path().join("config.toml");
+
+    std::fs::write(
+        &path,
+        "[[repositories]]\nowner = \"o\"\nname = \"a\"\noverwrite = \"if-older-than=7d\"\n",
+    )
+    .unwrap();
+    let config = config::Config::from_file(path.to_str().unwrap()).unwrap();
+    assert_eq!(
+        config.repositories[0].overwrite_policy().unwrap(),
+        Some(OverwritePolicy::IfOlderThan(Duration::days(7)))
+    );
+
+    std::fs::write(
+        &path,
+        "[[repositories]]\nowner = \"o\"\nname = \"a\"\noverwrite = \"if-older-than=soon\"\n",
+    )
+    .unwrap();
+    let err = config::Config::from_file(path.to_str().unwrap()).unwrap_err();
+    assert!(
+        format!("{:#}", err).contains("Invalid overwrite in repository #1"),
+        "{:#}",
+        err
+    );
+}
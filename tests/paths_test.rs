@@ -1,4 +1,6 @@
-use github_secrets::paths::{find_config_file, get_config_creation_path, load_env_file};
+use github_secrets::paths::{
+    find_config_file, find_config_layers, get_config_creation_path, load_env_file,
+};
 use serial_test::serial;
 use std::env;
 use std::fs;
@@ -143,6 +145,57 @@ fn test_find_config_file_xdg_config_home() {
     }
 }
 
+#[test]
+#[serial]
+fn test_find_config_file_prefers_personal_over_current_directory() {
+    let temp_dir = TempDir::new().unwrap();
+    let xdg_config_home = temp_dir.path().join("config");
+    let personal = xdg_config_home.join("github-secrets").join("config.toml");
+    fs::create_dir_all(personal.parent().unwrap()).unwrap();
+    fs::write(
+        &personal,
+        "[[repositories]]\nowner = \"me\"\nname = \"mine\"",
+    )
+    .unwrap();
+
+    let cwd = temp_dir.path().join("project");
+    fs::create_dir_all(&cwd).unwrap();
+    fs::write(
+        cwd.join("config.toml"),
+        "[[repositories]]\nowner = \"team\"\nname = \"shared\"",
+    )
+    .unwrap();
+
+    let original_xdg = env::var("XDG_CONFIG_HOME").ok();
+    let original_config_path = env::var("CONFIG_PATH").ok();
+    let original_cwd = env::current_dir().ok();
+    unsafe {
+        env::remove_var("CONFIG_PATH");
+        env::set_var("XDG_CONFIG_HOME", &xdg_config_home);
+    }
+    env::set_current_dir(&cwd).unwrap();
+
+    // Edits go to the layer whose settings win when merging.
+    let found = find_config_file().unwrap();
+    let layers = find_config_layers();
+
+    if let Some(dir) = original_cwd {
+        let _ = env::set_current_dir(dir);
+    }
+    unsafe {
+        match original_xdg {
+            Some(val) => env::set_var("XDG_CONFIG_HOME", val),
+            None => env::remove_var("XDG_CONFIG_HOME"),
+        }
+        if let Some(val) = original_config_path {
+            env::set_var("CONFIG_PATH", val);
+        }
+    }
+
+    assert_eq!(found, personal);
+    assert_eq!(layers[0], personal);
+}
+
 #[test]
 #[serial]
 fn test_load_env_file_priority() {
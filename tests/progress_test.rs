@@ -0,0 +1,269 @@
+use std::sync::Arc;
+
+use anyhow::Result;
+use crossterm::event::{Event, KeyCode};
+use ratatui::{Terminal, backend::TestBackend};
+
+use github_secrets::app::{App, UpdateResult};
+use github_secrets::progress::{CellStatus, Progress};
+use github_secrets::prompt;
+
+mod common;
+use common::{CountingRateLimiter, FakeEventSource, FakeGitHub, FakePrompt, key, repositories};
+
+#[test]
+fn test_progress_counts_and_retry_clears_errors() {
+    let mut progress = Progress::new(
+        vec!["A".to_string(), "B".to_string()],
+        vec!["o/one".to_string(), "o/two".to_string()],
+    );
+    progress.set(0, 0, CellStatus::Created);
+    progress.set(0, 1, CellStatus::Skipped);
+    progress.fail(1, 0, "boom".to_string());
+    progress.fail(1, 0, "boom again".to_string());
+    progress.note(1, 1, "note".to_string());
+
+    let counts = progress.counts();
+    assert_eq!(
+        (
+            counts.created,
+            counts.skipped,
+            counts.failed,
+            counts.pending
+        ),
+        (1, 1, 1, 1)
+    );
+    assert_eq!(counts.done(), 3);
+    assert_eq!(progress.failed(), vec![(1, 0)]);
+    // A second failure replaces the first error; notes are kept.
+    assert_eq!(progress.details.len(), 2);
+    assert_eq!(progress.details[0].message, "boom again");
+
+    progress.set(1, 0, CellStatus::Updated);
+    assert!(progress.failed().is_empty());
+    assert_eq!(progress.details.len(), 1);
+    assert!(!progress.details[0].is_error);
+    assert_eq!(progress.repository_counts(1).updated, 1);
+}
+
+#[test]
+fn test_record_update_results() {
+    let mut progress = Progress::new(
+        vec!["A".to_string()],
+        vec!["o/one".to_string(), "o/two".to_string()],
+    );
+    let failure = UpdateResult::new_failure("A".to_string(), "o/one".to_string(), "boom".into());
+    progress.record(0, 0, &failure, true);
+    progress.record(
+        1,
+        0,
+        &UpdateResult::new_success("A".to_string(), "o/two".to_string()),
+        false,
+    );
+    assert_eq!(progress.status(0, 0), CellStatus::Failed);
+    assert_eq!(progress.details[0].message, "boom");
+    assert_eq!(progress.status(1, 0), CellStatus::Created);
+
+    let success = UpdateResult::new_success("A".to_string(), "o/one".to_string());
+    progress.record(0, 0, &success, true);
+    assert_eq!(progress.status(0, 0), CellStatus::Updated);
+    assert!(progress.details.is_empty());
+}
+
+/// Prompt entering `NEW` and `EXISTING` with `value`, answering overwrite
+/// questions with `overwrite` and retrying from the summary `retries` times.
+fn entering(value: &str, overwrite: bool, retries: usize) -> FakePrompt {
+    FakePrompt::with_secrets(&[("NEW", value), ("EXISTING", value)])
+        .overwriting(overwrite)
+        .retrying(retries)
+}
+
+async fn run(prompt: &FakePrompt, failures: usize) -> Result<()> {
+    run_with_lookup_failures(prompt, failures, 0).await
+}
+
+/// Run against `stable` and `flaky`, where `EXISTING` already exists and the first
+/// `failures` uploads to `flaky` fail, and its first `lookup_failures` lookups.
+async fn run_with_lookup_failures(
+    prompt: &FakePrompt,
+    failures: usize,
+    lookup_failures: usize,
+) -> Result<()> {
+    let github = FakeGitHub::default()
+        .with_secrets_everywhere(&[("EXISTING", None)])
+        .failing_updates("flaky", failures)
+        .failing_lookups("flaky", lookup_failures);
+    let mut rate_limiter = CountingRateLimiter::default();
+    App::run_with_deps(
+        &github,
+        prompt,
+        &mut rate_limiter,
+        Arc::new("token".to_string()),
+        repositories(&["stable", "flaky"]),
+    )
+    .await?;
+    assert_eq!(
+        rate_limiter.in_flight, 0,
+        "every request releases the limiter"
+    );
+    Ok(())
+}
+
+fn statuses(progress: &Progress) -> Vec<Vec<&'static str>> {
+    progress
+        .cells
+        .iter()
+        .map(|row| row.iter().map(|s| s.label()).collect())
+        .collect()
+}
+
+#[tokio::test]
+async fn test_run_reports_every_cell() -> Result<()> {
+    let prompt = entering("a-real-value", false, 0);
+    run(&prompt, 1).await?;
+
+    let shown = prompt.shown.lock().unwrap();
+    assert_eq!(
+        statuses(&shown[0]),
+        vec![vec!["pending", "pending"], vec!["pending", "pending"]]
+    );
+    assert!(shown.iter().any(|p| p.status(0, 0) == CellStatus::Running));
+
+    let reviewed = prompt.reviewed.lock().unwrap();
+    assert_eq!(reviewed.len(), 1);
+    assert_eq!(
+        statuses(&reviewed[0]),
+        vec![vec!["created", "skipped"], vec!["failed", "skipped"]]
+    );
+    assert_eq!(reviewed[0].repositories, vec!["o/stable", "o/flaky"]);
+    assert!(reviewed[0].details[0].message.contains("502 Bad Gateway"));
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_retry_from_summary_until_nothing_fails() -> Result<()> {
+    // Both uploads to `flaky` fail, and the first retry of one of them too.
+    let prompt = entering("a-real-value", true, 5);
+    run(&prompt, 3).await?;
+
+    let reviewed = prompt.reviewed.lock().unwrap();
+    let failed: Vec<usize> = reviewed.iter().map(|p| p.counts().failed).collect();
+    assert_eq!(failed, vec![2, 1, 0]);
+    let last = reviewed.last().unwrap();
+    assert_eq!(
+        statuses(last),
+        vec![vec!["created", "updated"], vec!["created", "updated"]]
+    );
+    assert!(last.details.is_empty());
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_retry_reuses_resolved_values() -> Result<()> {
+    let temp_dir = tempfile::TempDir::new()?;
+    let log = temp_dir.path().join("resolved.log");
+    let value = format!("cmd:echo x >> '{}'; printf a-real-value", log.display());
+    let prompt = entering(&value, true, 5);
+    run(&prompt, 3).await?;
+
+    // One command run per secret, however many uploads were retried.
+    assert_eq!(std::fs::read_to_string(&log)?.lines().count(), 2);
+    let reviewed = prompt.reviewed.lock().unwrap();
+    assert_eq!(reviewed.last().unwrap().counts().failed, 0);
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_failed_existence_check_is_a_failed_cell() -> Result<()> {
+    // Looking up `EXISTING` in `flaky` fails once; the rest of the run goes on.
+    let prompt = entering("a-real-value", true, 5);
+    run_with_lookup_failures(&prompt, 0, 2).await?;
+
+    let reviewed = prompt.reviewed.lock().unwrap();
+    assert_eq!(
+        statuses(&reviewed[0]),
+        vec![vec!["created", "updated"], vec!["failed", "failed"]]
+    );
+    assert!(
+        reviewed[0].details[0]
+            .message
+            .contains("Failed to check if secret exists")
+    );
+    // The retry checks again and asks about the secret that turned out to exist.
+    assert_eq!(
+        statuses(reviewed.last().unwrap()),
+        vec![vec!["created", "updated"], vec!["created", "updated"]]
+    );
+    assert_eq!(prompt.asked.lock().unwrap().len(), 2);
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_lint_findings_go_to_details() -> Result<()> {
+    let prompt = entering("cmd:printf '\"a-real-value\"'", false, 0);
+    run(&prompt, 0).await?;
+
+    let reviewed = prompt.reviewed.lock().unwrap();
+    let notes: Vec<_> = reviewed[0]
+        .details
+        .iter()
+        .filter(|d| d.message.contains("wrapped in quotes"))
+        .collect();
+    assert_eq!(notes.len(), 1, "{:?}", reviewed[0].details);
+    assert!(!notes[0].is_error);
+    Ok(())
+}
+
+fn sample() -> Progress {
+    let mut progress = Progress::new(
+        vec!["API_KEY".to_string(), "DB_URL".to_string()],
+        vec!["o/api".to_string(), "o/web".to_string()],
+    );
+    progress.set(0, 0, CellStatus::Created);
+    progress.set(0, 1, CellStatus::Updated);
+    progress.set(1, 0, CellStatus::Skipped);
+    progress.fail(1, 1, "403 Forbidden".to_string());
+    progress
+}
+
+fn review(progress: &Progress, events: Vec<Event>) -> (bool, String) {
+    let mut terminal = Terminal::new(TestBackend::new(100, 24)).unwrap();
+    let mut src = FakeEventSource::new(events);
+    let retry = prompt::review_progress_with(&mut terminal, &mut src, progress).unwrap();
+    let buffer = terminal.backend().buffer();
+    let text = (0..buffer.area.height)
+        .map(|y| {
+            (0..buffer.area.width)
+                .map(|x| buffer.get(x, y).symbol())
+                .collect::<String>()
+        })
+        .collect::<Vec<_>>()
+        .join("\n");
+    (retry, text)
+}
+
+#[test]
+fn test_summary_screen_offers_retry_when_something_failed() {
+    let (retry, text) = review(&sample(), vec![key(KeyCode::Char('r'))]);
+    assert!(retry);
+    assert!(
+        text.contains("4/4 done | 1 created | 1 updated | 1 skipped | 1 failed"),
+        "{}",
+        text
+    );
+    assert!(text.contains("o/web / DB_URL: 403 Forbidden"));
+    assert!(text.contains("r: retry 1 failed"));
+
+    let (retry, _) = review(&sample(), vec![key(KeyCode::Enter)]);
+    assert!(!retry);
+
+    // Nothing to retry: `r` is ignored and the screen waits for exit.
+    let mut all_good = sample();
+    all_good.set(1, 1, CellStatus::Updated);
+    let (retry, text) = review(
+        &all_good,
+        vec![key(KeyCode::Char('r')), key(KeyCode::Char('q'))],
+    );
+    assert!(!retry);
+    assert!(text.contains("No errors"));
+}
@@ -277,7 +277,7 @@ fn test_prompt_confirm_yes() {
     let result =
         github_secrets::prompt::prompt_confirm_with(&mut terminal, &mut event_source, "Test?");
     assert!(result.is_ok());
-    assert_eq!(result.unwrap(), true);
+    assert!(result.unwrap());
 }
 
 #[test]
@@ -292,7 +292,7 @@ fn test_prompt_confirm_no() {
     let result =
         github_secrets::prompt::prompt_confirm_with(&mut terminal, &mut event_source, "Test?");
     assert!(result.is_ok());
-    assert_eq!(result.unwrap(), false);
+    assert!(!result.unwrap());
 }
 
 #[test]
@@ -307,5 +307,5 @@ fn test_prompt_confirm_navigation() {
     let result =
         github_secrets::prompt::prompt_confirm_with(&mut terminal, &mut event_source, "Test?");
     assert!(result.is_ok());
-    assert_eq!(result.unwrap(), false);
+    assert!(!result.unwrap());
 }
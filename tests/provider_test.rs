@@ -0,0 +1,266 @@
+use github_secrets::config::Config;
+use github_secrets::provider::{ProviderConfig, Providers, executable, fetch};
+use github_secrets::secret_ref::{SecretSource, resolve_value};
+use serial_test::serial;
+use std::env;
+use std::fs;
+use tempfile::TempDir;
+
+#[test]
+fn test_parse_provider_reference() {
+    assert_eq!(
+        SecretSource::parse("provider:onepassword/Prod/DB#password"),
+        SecretSource::Provider {
+            name: "onepassword".to_string(),
+            reference: "Prod/DB".to_string(),
+            field: Some("password".to_string()),
+        }
+    );
+    assert_eq!(
+        SecretSource::parse("provider:vault/secret/data/app"),
+        SecretSource::Provider {
+            name: "vault".to_string(),
+            reference: "secret/data/app".to_string(),
+            field: None,
+        }
+    );
+    assert_eq!(
+        SecretSource::parse("provider:onepassword/Prod/DB#password").to_string(),
+        "provider:onepassword/Prod/DB#password"
+    );
+    assert!(
+        SecretSource::parse("provider:onepassword")
+            .validate()
+            .is_err()
+    );
+    assert!(SecretSource::parse("provider:/item").validate().is_err());
+}
+
+#[test]
+fn test_executable_name() {
+    assert_eq!(executable("pass", None), "github-secrets-provider-pass");
+    let config = ProviderConfig {
+        command: Some("/opt/op-provider".to_string()),
+        ..Default::default()
+    };
+    assert_eq!(executable("pass", Some(&config)), "/opt/op-provider");
+}
+
+#[test]
+fn test_missing_provider_errors() {
+    let err = fetch("does-not-exist-xyz", "item", None, &Providers::new()).unwrap_err();
+    assert!(
+        err.to_string()
+            .contains("providers.does-not-exist-xyz.command")
+    );
+}
+
+#[test]
+fn test_provider_config_parsing_and_merging() {
+    let temp_dir = TempDir::new().unwrap();
+    let team = temp_dir.path().join("team.toml");
+    fs::write(
+        &team,
+        "[providers.vault]\ncommand = \"team-vault\"\n\n[providers.pass]\ncommand = \"team-pass\"\n",
+    )
+    .unwrap();
+    let main = temp_dir.path().join("config.toml");
+    fs::write(
+        &main,
+        r#"include = ["team.toml"]
+
+[[repositories]]
+owner = "o"
+name = "r"
+
+[providers.vault]
+command = "my-vault"
+args = ["--profile", "prod"]
+options = { address = "https://vault.example.com" }
+"#,
+    )
+    .unwrap();
+
+    let config = Config::load_layered(&[main]).unwrap();
+    let vault = &config.providers["vault"];
+    assert_eq!(vault.command.as_deref(), Some("my-vault"));
+    assert_eq!(vault.args, vec!["--profile", "prod"]);
+    assert_eq!(
+        vault.options["address"].as_str(),
+        Some("https://vault.example.com")
+    );
+    assert_eq!(
+        config.providers["pass"].command.as_deref(),
+        Some("team-pass")
+    );
+
+    fs::write(&team, "[providers.vault]\ncomand = \"typo\"\n").unwrap();
+    assert!(Config::load_editable(team.to_str().unwrap()).is_err());
+}
+
+#[cfg(unix)]
+mod script {
+    use super::*;
+    use std::os::unix::fs::PermissionsExt;
+    use std::path::Path;
+
+    /// Write an executable test provider and return its path.
+    fn write_provider(dir: &Path, body: &str) -> String {
+        let path = dir.join("github-secrets-provider-test");
+        fs::write(&path, format!("#!/bin/sh\n{}\n", body)).unwrap();
+        fs::set_permissions(&path, fs::Permissions::from_mode(0o755)).unwrap();
+        path.to_string_lossy().into_owned()
+    }
+
+    fn providers(command: String) -> Providers {
+        let mut options = toml::Table::new();
+        options.insert("vault".to_string(), toml::Value::from("Prod"));
+        let mut providers = Providers::new();
+        providers.insert(
+            "test".to_string(),
+            ProviderConfig {
+                command: Some(command),
+                args: vec![],
+                options,
+                untrusted_source: None,
+            },
+        );
+        providers
+    }
+
+    #[test]
+    fn test_provider_round_trip() {
+        let temp_dir = TempDir::new().unwrap();
+        let request_log = temp_dir.path().join("request.json");
+        let command = write_provider(
+            temp_dir.path(),
+            &format!(
+                "cat > '{}'\nprintf '{{\"value\": \"s3cr3t\"}}'",
+                request_log.display()
+            ),
+        );
+
+        let value = resolve_value("provider:test/Prod/DB#password", &providers(command)).unwrap();
+        assert_eq!(value, "s3cr3t");
+
+        let request: serde_json::Value =
+            serde_json::from_str(&fs::read_to_string(&request_log).unwrap()).unwrap();
+        assert_eq!(request["version"], 1);
+        assert_eq!(request["reference"], "Prod/DB");
+        assert_eq!(request["field"], "password");
+        assert_eq!(request["options"]["vault"], "Prod");
+    }
+
+    #[test]
+    fn test_provider_error_response() {
+        let temp_dir = TempDir::new().unwrap();
+        let command = write_provider(
+            temp_dir.path(),
+            "cat > /dev/null\nprintf '{\"error\": \"item not found\"}'",
+        );
+
+        let err = resolve_value("provider:test/Missing", &providers(command)).unwrap_err();
+        assert!(format!("{:#}", err).contains("item not found"));
+    }
+
+    #[test]
+    fn test_provider_nonzero_exit() {
+        let temp_dir = TempDir::new().unwrap();
+        let command = write_provider(
+            temp_dir.path(),
+            "cat > /dev/null\necho 'not signed in' >&2\nexit 2",
+        );
+
+        let err = resolve_value("provider:test/Item", &providers(command)).unwrap_err();
+        assert!(format!("{:#}", err).contains("not signed in"));
+    }
+
+    #[test]
+    fn test_provider_that_ignores_its_request() {
+        let temp_dir = TempDir::new().unwrap();
+        let command = write_provider(temp_dir.path(), "printf '{\"value\": \"ok\"}'");
+
+        // Larger than a pipe buffer, so writing it fails once the provider exits.
+        let mut providers = providers(command);
+        let options = &mut providers.get_mut("test").unwrap().options;
+        options.insert(
+            "padding".to_string(),
+            toml::Value::from("x".repeat(1 << 20)),
+        );
+
+        assert_eq!(
+            resolve_value("provider:test/Item", &providers).unwrap(),
+            "ok"
+        );
+    }
+
+    #[test]
+    #[serial]
+    fn test_provider_command_from_current_directory_is_not_run() {
+        let temp_dir = TempDir::new().unwrap();
+        let ran = temp_dir.path().join("ran");
+        let command = write_provider(
+            temp_dir.path(),
+            &format!("touch '{}'\nprintf '{{\"value\": \"x\"}}'", ran.display()),
+        );
+        let project = temp_dir.path().join("project");
+        fs::create_dir_all(&project).unwrap();
+        fs::write(
+            project.join("config.toml"),
+            format!(
+                "[[repositories]]\nowner = \"o\"\nname = \"r\"\n\n\
+                 [providers.test]\ncommand = \"{}\"\n",
+                command
+            ),
+        )
+        .unwrap();
+
+        let original_cwd = env::current_dir().ok();
+        let original_xdg = env::var("XDG_CONFIG_HOME").ok();
+        let original_config_path = env::var("CONFIG_PATH").ok();
+        unsafe {
+            env::remove_var("CONFIG_PATH");
+            env::set_var("XDG_CONFIG_HOME", temp_dir.path().join("xdg"));
+        }
+        env::set_current_dir(&project).unwrap();
+
+        let shared = Config::load_layered(&[project.join("config.toml")]);
+        unsafe { env::set_var("CONFIG_PATH", project.join("config.toml")) };
+        let chosen = Config::load_layered(&[project.join("config.toml")]);
+
+        if let Some(cwd) = original_cwd {
+            let _ = env::set_current_dir(cwd);
+        }
+        unsafe {
+            match original_xdg {
+                Some(value) => env::set_var("XDG_CONFIG_HOME", value),
+                None => env::remove_var("XDG_CONFIG_HOME"),
+            }
+            match original_config_path {
+                Some(value) => env::set_var("CONFIG_PATH", value),
+                None => env::remove_var("CONFIG_PATH"),
+            }
+        }
+
+        let err = resolve_value("provider:test/Item", &shared.unwrap().providers).unwrap_err();
+        assert!(
+            format!("{:#}", err).contains("current directory"),
+            "{:#}",
+            err
+        );
+        assert!(!ran.exists());
+
+        let value = resolve_value("provider:test/Item", &chosen.unwrap().providers).unwrap();
+        assert_eq!(value, "x");
+        assert!(ran.exists());
+    }
+
+    #[test]
+    fn test_provider_invalid_json() {
+        let temp_dir = TempDir::new().unwrap();
+        let command = write_provider(temp_dir.path(), "cat > /dev/null\necho nope");
+
+        let err = resolve_value("provider:test/Item", &providers(command)).unwrap_err();
+        assert!(format!("{:#}", err).contains("invalid response"));
+    }
+}
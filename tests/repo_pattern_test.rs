@@ -0,0 +1,142 @@
+use github_secrets::config::Repository;
+use github_secrets::selector::{RepoPattern, closest_names, select};
+
+fn repo(owner: &str, name: &str, alias: Option<&str>, groups: &[&str]) -> Repository {
+    Repository {
+        owner: owner.to_string(),
+        name: name.to_string(),
+        alias: alias.map(str::to_string),
+        groups: groups.iter().map(|s| s.to_string()).collect(),
+        ..Default::default()
+    }
+}
+
+fn repos() -> Vec<Repository> {
+    vec![
+        repo("my-org", "service-api", Some("API"), &["prod"]),
+        repo("my-org", "service-web", None, &["prod"]),
+        repo("my-org", "legacy-billing", None, &[]),
+        repo("other", "service-api", None, &[]),
+    ]
+}
+
+fn patterns(list: &[&str]) -> Vec<String> {
+    list.iter().map(|s| s.to_string()).collect()
+}
+
+#[test]
+fn test_exact_path_and_alias() {
+    let repos = repos();
+    assert_eq!(
+        select(&repos, &[], &[], &patterns(&["my-org/service-web"])).unwrap(),
+        vec![1]
+    );
+    assert_eq!(
+        select(&repos, &[], &[], &patterns(&["api"])).unwrap(),
+        vec![0]
+    );
+}
+
+#[test]
+fn test_exact_name_does_not_match_prefix() {
+    let pattern = RepoPattern::parse("my-org/service").unwrap();
+    assert!(!pattern.matches(&repos()[0]));
+}
+
+#[test]
+fn test_glob_pattern() {
+    let repos = repos();
+    assert_eq!(
+        select(&repos, &[], &[], &patterns(&["my-org/service-*"])).unwrap(),
+        vec![0, 1]
+    );
+    assert_eq!(
+        select(&repos, &[], &[], &patterns(&["*/service-ap?"])).unwrap(),
+        vec![0, 3]
+    );
+}
+
+#[test]
+fn test_negation_only_starts_from_all() {
+    let repos = repos();
+    assert_eq!(
+        select(&repos, &[], &[], &patterns(&["!my-org/legacy-*"])).unwrap(),
+        vec![0, 1, 3]
+    );
+}
+
+#[test]
+fn test_include_then_exclude() {
+    let repos = repos();
+    assert_eq!(
+        select(
+            &repos,
+            &[],
+            &[],
+            &patterns(&["my-org/*", "!my-org/legacy-*"])
+        )
+        .unwrap(),
+        vec![0, 1]
+    );
+}
+
+#[test]
+fn test_regex_pattern() {
+    let repos = repos();
+    assert_eq!(
+        select(
+            &repos,
+            &[],
+            &[],
+            &patterns(&["re:^(my-org|other)/service-api$"])
+        )
+        .unwrap(),
+        vec![0, 3]
+    );
+}
+
+#[test]
+fn test_patterns_combine_with_groups() {
+    let repos = repos();
+    let groups = vec!["prod".to_string()];
+    assert_eq!(
+        select(&repos, &groups, &[], &patterns(&["!*/service-web"])).unwrap(),
+        vec![0]
+    );
+    assert_eq!(
+        select(&repos, &groups, &[], &patterns(&["other/*"])).unwrap(),
+        vec![0, 1, 3]
+    );
+}
+
+#[test]
+fn test_unmatched_pattern_lists_closest_names() {
+    let repos = repos();
+    let err = select(&repos, &[], &[], &patterns(&["my-org/servce-web"]))
+        .unwrap_err()
+        .to_string();
+    assert!(err.contains("my-org/servce-web"));
+    assert!(err.contains("Closest names: my-org/service-web"));
+}
+
+#[test]
+fn test_everything_excluded_errors() {
+    let repos = repos();
+    let result = select(&repos, &[], &[], &patterns(&["!*"]));
+    assert!(result.is_err());
+}
+
+#[test]
+fn test_invalid_patterns() {
+    assert!(RepoPattern::parse("!").is_err());
+    assert!(RepoPattern::parse("re:(").is_err());
+}
+
+#[test]
+fn test_closest_names_limits_and_deduplicates() {
+    let names = closest_names(&repos(), "service");
+    assert_eq!(names.len(), 3);
+    let mut unique = names.clone();
+    unique.dedup();
+    assert_eq!(unique.len(), names.len());
+}
@@ -0,0 +1,163 @@
+use crossterm::event::KeyCode;
+use github_secrets::config::{
+    Config, GroupKind, Repository, collect_groups, parse_label_list, select_by_labels,
+};
+use github_secrets::prompt::select_repositories_with;
+use ratatui::{Terminal, backend::TestBackend};
+use std::fs;
+use tempfile::TempDir;
+
+mod common;
+use common::{FakeEventSource, key};
+
+fn repo(name: &str, groups: &[&str], tags: &[&str]) -> Repository {
+    Repository {
+        owner: "org".to_string(),
+        name: name.to_string(),
+        groups: groups.iter().map(|s| s.to_string()).collect(),
+        tags: tags.iter().map(|s| s.to_string()).collect(),
+        ..Default::default()
+    }
+}
+
+#[test]
+fn test_config_parses_groups_and_tags() {
+    let temp_dir = TempDir::new().unwrap();
+    let config_path = temp_dir.path().join("config.toml");
+
+    let content = r#"
+[[repositories]]
+owner = "org"
+name = "billing"
+groups = ["prod"]
+tags = ["payments"]
+
+[[repositories]]
+owner = "org"
+name = "docs"
+"#;
+    fs::write(&config_path, content).unwrap();
+
+    let config = Config::from_file(config_path.to_str().unwrap()).unwrap();
+    assert_eq!(config.repositories[0].groups, vec!["prod".to_string()]);
+    assert_eq!(config.repositories[0].tags, vec!["payments".to_string()]);
+    assert!(config.repositories[1].groups.is_empty());
+    assert!(config.repositories[1].tags.is_empty());
+}
+
+#[test]
+fn test_config_rejects_invalid_label() {
+    let temp_dir = TempDir::new().unwrap();
+    let config_path = temp_dir.path().join("config.toml");
+
+    let content = r#"
+[[repositories]]
+owner = "org"
+name = "billing"
+groups = ["not valid"]
+"#;
+    fs::write(&config_path, content).unwrap();
+
+    let result = Config::from_file(config_path.to_str().unwrap());
+    assert!(result.is_err());
+    assert!(format!("{:#}", result.unwrap_err()).contains("group or tag"));
+}
+
+#[test]
+fn test_empty_labels_are_not_serialized() {
+    let toml_string = toml::to_string(&repo("docs", &[], &[])).unwrap();
+    assert!(!toml_string.contains("groups"));
+    assert!(!toml_string.contains("tags"));
+}
+
+#[test]
+fn test_collect_groups_orders_groups_before_tags() {
+    let repos = vec![
+        repo("billing", &["prod"], &["payments"]),
+        repo("ledger", &["prod", "batch"], &["payments"]),
+        repo("docs", &[], &[]),
+    ];
+
+    let groups = collect_groups(&repos);
+    let labels: Vec<String> = groups.iter().map(|g| g.display_name()).collect();
+    assert_eq!(
+        labels,
+        vec!["Group: batch (1)", "Group: prod (2)", "Tag: payments (2)"]
+    );
+    assert_eq!(groups[1].kind, GroupKind::Group);
+    assert_eq!(groups[1].members, vec![0, 1]);
+    assert_eq!(groups[2].kind, GroupKind::Tag);
+}
+
+#[test]
+fn test_select_by_labels_union() {
+    let repos = vec![
+        repo("billing", &["prod"], &[]),
+        repo("ledger", &[], &["payments"]),
+        repo("docs", &["staging"], &[]),
+    ];
+
+    let selected =
+        select_by_labels(&repos, &["prod".to_string()], &["payments".to_string()]).unwrap();
+    assert_eq!(selected, vec![0, 1]);
+}
+
+#[test]
+fn test_select_by_labels_unknown_group_errors() {
+    let repos = vec![repo("billing", &["prod"], &[])];
+
+    let result = select_by_labels(&repos, &["nope".to_string()], &[]);
+    assert!(result.is_err());
+    assert!(result.unwrap_err().to_string().contains("'nope'"));
+}
+
+#[test]
+fn test_parse_label_list() {
+    assert_eq!(
+        parse_label_list(" prod, payments ,, prod"),
+        vec!["prod".to_string(), "payments".to_string()]
+    );
+    assert!(parse_label_list("  ").is_empty());
+}
+
+#[test]
+fn test_select_group_entry_in_tui() {
+    let repos = vec![
+        repo("billing", &["prod"], &[]),
+        repo("docs", &[], &[]),
+        repo("ledger", &["prod"], &[]),
+    ];
+
+    // Rows: Select All, Group: prod, billing, docs, ledger
+    let mut events = FakeEventSource::new(vec![
+        key(KeyCode::Down),
+        key(KeyCode::Char(' ')),
+        key(KeyCode::Enter),
+    ]);
+    let mut terminal = Terminal::new(TestBackend::new(80, 24)).unwrap();
+
+    let selected = select_repositories_with(&mut terminal, &mut events, &repos).unwrap();
+    assert_eq!(selected, vec![0, 2]);
+}
+
+#[test]
+fn test_repository_row_after_group_rows_in_tui() {
+    let repos = vec![
+        repo("billing", &["prod"], &[]),
+        repo("docs", &[], &["public"]),
+    ];
+
+    // Rows: Select All, Group: prod, Tag: public, billing, docs
+    let mut events = FakeEventSource::new(vec![
+        key(KeyCode::Down),
+        key(KeyCode::Down),
+        key(KeyCode::Down),
+        key(KeyCode::Down),
+        key(KeyCode::Char(' ')),
+        key(KeyCode::Enter),
+    ]);
+    let mut terminal = Terminal::new(TestBackend::new(80, 24)).unwrap();
+
+    let selected = select_repositories_with(&mut terminal, &mut events, &repos).unwrap();
+    assert_eq!(selected, vec![1]);
+}
@@ -0,0 +1,214 @@
+use std::sync::Arc;
+
+use anyhow::Result;
+use tempfile::TempDir;
+
+use github_secrets::app::{App, NewValue, RotateOptions, RunOptions};
+use github_secrets::config;
+use github_secrets::rotation;
+
+mod common;
+use common::{FakeGitHub, FakePrompt, NoopRateLimiter};
+
+fn repo(name: &str, groups: &[&str]) -> config::Repository {
+    config::Repository {
+        owner: "o".to_string(),
+        name: name.to_string(),
+        groups: groups.iter().map(|g| g.to_string()).collect(),
+        ..Default::default()
+    }
+}
+
+fn sample_config() -> config::Config {
+    config::Config {
+        repositories: vec![
+            repo("a", &["prod"]),
+            repo("b", &["prod"]),
+            repo("c", &["dev"]),
+        ],
+        ..Default::default()
+    }
+}
+
+/// Secret names by repository; repositories not listed here can't be listed.
+fn github(secrets: &[(&str, &[&str])]) -> FakeGitHub {
+    secrets
+        .iter()
+        .fold(FakeGitHub::default(), |github, (repo, names)| {
+            let secrets: Vec<_> = names.iter().map(|name| (*name, Some(30))).collect();
+            github.with_secrets(repo, &secrets)
+        })
+}
+
+/// Prompt answering the rotation question with `answer`.
+fn confirm(answer: bool) -> FakePrompt {
+    FakePrompt::default().confirming(answer)
+}
+
+fn options(value: &str) -> RotateOptions {
+    RotateOptions {
+        secret: "DB_PASSWORD".to_string(),
+        value: NewValue::Given(value.to_string()),
+        ..Default::default()
+    }
+}
+
+async fn rotate(
+    github: &FakeGitHub,
+    prompt: &FakePrompt,
+    options: &RotateOptions,
+    log: &std::path::Path,
+) -> Result<()> {
+    App::rotate_with_deps(
+        github,
+        prompt,
+        &mut NoopRateLimiter,
+        Arc::new("token".to_string()),
+        sample_config(),
+        options,
+        log,
+    )
+    .await
+}
+
+#[tokio::test]
+async fn test_rotate_updates_only_repositories_with_the_secret() -> Result<()> {
+    let temp_dir = TempDir::new()?;
+    let log = temp_dir.path().join("rotations.json");
+    let github = github(&[
+        ("a", &["DB_PASSWORD", "OTHER"]),
+        ("b", &["OTHER"]),
+        ("c", &["db_password"]),
+    ]);
+    let prompt = confirm(true);
+
+    rotate(&github, &prompt, &options("new-value"), &log).await?;
+
+    assert_eq!(
+        *prompt.questions.lock().unwrap(),
+        vec!["Rotate 'DB_PASSWORD' in 2 repositories?".to_string()]
+    );
+    // One confirmation covers every repository.
+    assert!(prompt.asked.lock().unwrap().is_empty());
+    assert_eq!(
+        github.uploads(),
+        vec![
+            (
+                "a".to_string(),
+                "DB_PASSWORD".to_string(),
+                "new-value".to_string()
+            ),
+            (
+                "c".to_string(),
+                "DB_PASSWORD".to_string(),
+                "new-value".to_string()
+            ),
+        ]
+    );
+
+    let records = rotation::load(&log)?;
+    assert_eq!(records.len(), 1);
+    assert_eq!(records[0].secret, "DB_PASSWORD");
+    assert_eq!(records[0].repositories, vec!["o/a", "o/c"]);
+    assert!(records[0].rotated_at().is_some());
+    assert!(!std::fs::read_to_string(&log)?.contains("new-value"));
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_rotate_create_adds_missing_secret_in_selection() -> Result<()> {
+    let temp_dir = TempDir::new()?;
+    let log = temp_dir.path().join("rotations.json");
+    let github = github(&[("a", &["DB_PASSWORD"]), ("b", &[]), ("c", &[])]);
+    let prompt = confirm(true);
+
+    let options = RotateOptions {
+        create: true,
+        selection: RunOptions {
+            groups: vec!["prod".to_string()],
+            ..Default::default()
+        },
+        ..options("v2")
+    };
+    rotate(&github, &prompt, &options, &log).await?;
+
+    let repos: Vec<String> = github
+        .uploads()
+        .into_iter()
+        .map(|(repo, _, _)| repo)
+        .collect();
+    assert_eq!(repos, vec!["a", "b"]);
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_rotate_strict_refuses_lint_warnings() -> Result<()> {
+    let temp_dir = TempDir::new()?;
+    let log = temp_dir.path().join("rotations.json");
+    let github = github(&[("a", &["DB_PASSWORD"]), ("b", &[]), ("c", &[])]);
+
+    let strict = RotateOptions {
+        yes: true,
+        selection: RunOptions {
+            strict: true,
+            ..Default::default()
+        },
+        ..options("\"quoted-value\"")
+    };
+    let err = rotate(&github, &confirm(true), &strict, &log)
+        .await
+        .unwrap_err();
+    assert!(err.to_string().contains("failed lint"), "{}", err);
+    assert!(github.uploads().is_empty());
+
+    // Without --strict the warning is only shown.
+    let relaxed = RotateOptions {
+        yes: true,
+        ..options("\"quoted-value\"")
+    };
+    rotate(&github, &confirm(true), &relaxed, &log).await?;
+    assert_eq!(github.uploads().len(), 1);
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_rotate_declined_or_absent_changes_nothing() -> Result<()> {
+    let temp_dir = TempDir::new()?;
+    let log = temp_dir.path().join("rotations.json");
+
+    let declined = github(&[("a", &["DB_PASSWORD"]), ("b", &[]), ("c", &[])]);
+    rotate(&declined, &confirm(false), &options("x"), &log).await?;
+    assert!(declined.uploads().is_empty());
+
+    let absent = github(&[("a", &[]), ("b", &[]), ("c", &[])]);
+    let err = rotate(&absent, &confirm(true), &options("x"), &log)
+        .await
+        .unwrap_err();
+    assert!(err.to_string().contains("--create"), "{}", err);
+
+    assert!(!log.exists());
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_rotate_reports_failures_and_records_partial_rotation() -> Result<()> {
+    let temp_dir = TempDir::new()?;
+    let log = temp_dir.path().join("rotations.json");
+    // "b" can't be listed and "c" fails to update.
+    let github = github(&[("a", &["DB_PASSWORD"]), ("c", &["DB_PASSWORD"])])
+        .failing_updates("c", usize::MAX);
+
+    let options = RotateOptions {
+        yes: true,
+        ..options("x")
+    };
+    let err = rotate(&github, &confirm(false), &options, &log)
+        .await
+        .unwrap_err();
+    assert!(err.to_string().contains("o/b, o/c"), "{}", err);
+
+    let records = rotation::load(&log)?;
+    assert_eq!(records[0].repositories, vec!["o/a"]);
+    assert_eq!(records[0].failed, vec!["o/b", "o/c"]);
+    Ok(())
+}
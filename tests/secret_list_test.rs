@@ -0,0 +1,127 @@
+use crossterm::event::{Event, KeyCode};
+use ratatui::{Terminal, backend::TestBackend};
+
+use github_secrets::prompt;
+
+mod common;
+use common::{FakeEventSource, key, shift, typed};
+
+/// Events adding each `(key, value)` pair from the key field.
+fn added(pairs: &[(&str, &str)]) -> Vec<Event> {
+    let mut events = Vec::new();
+    for (k, v) in pairs {
+        events.extend(typed(k));
+        events.push(key(KeyCode::Enter));
+        events.extend(typed(v));
+        events.push(key(KeyCode::Enter));
+    }
+    events
+}
+
+/// Run the input screen, leaving the list (if focused) and confirming the exit.
+fn run_tui(mut events: Vec<Event>) -> Vec<(String, String)> {
+    events.push(key(KeyCode::Tab));
+    events.push(key(KeyCode::Esc));
+    events.push(key(KeyCode::Char('y')));
+    let mut terminal = Terminal::new(TestBackend::new(120, 30)).unwrap();
+    let mut src = FakeEventSource::new(events);
+    prompt::prompt_secrets_with(&mut terminal, &mut src)
+        .unwrap()
+        .into_iter()
+        .map(|s| (s.key, s.value))
+        .collect()
+}
+
+fn pairs(expected: &[(&str, &str)]) -> Vec<(String, String)> {
+    expected
+        .iter()
+        .map(|(k, v)| (k.to_string(), v.to_string()))
+        .collect()
+}
+
+#[test]
+fn test_toggled_off_entry_is_not_submitted() {
+    let mut events = added(&[("A", "1"), ("B", "2"), ("C", "3")]);
+    // Up focuses the last entry; toggle B off, C off and on again.
+    events.push(key(KeyCode::Up));
+    events.push(key(KeyCode::Char(' ')));
+    events.push(key(KeyCode::Char(' ')));
+    events.push(key(KeyCode::Up));
+    events.push(key(KeyCode::Char(' ')));
+
+    assert_eq!(run_tui(events), pairs(&[("A", "1"), ("C", "3")]));
+}
+
+#[test]
+fn test_edit_value_and_key_in_place() {
+    let mut events = added(&[("A", "1"), ("B", "2")]);
+    events.push(key(KeyCode::Up));
+    events.push(key(KeyCode::Up));
+    events.push(key(KeyCode::Enter));
+    events.push(key(KeyCode::Backspace));
+    events.extend(typed("one"));
+    events.push(key(KeyCode::Enter));
+    // Back in the list on the same entry: rename it.
+    events.push(key(KeyCode::Char('k')));
+    events.push(key(KeyCode::Backspace));
+    events.extend(typed("ALPHA"));
+    events.push(key(KeyCode::Enter));
+
+    assert_eq!(run_tui(events), pairs(&[("ALPHA", "one"), ("B", "2")]));
+}
+
+#[test]
+fn test_cancelled_edits_change_nothing() {
+    let mut events = added(&[("A", "1"), ("B", "2")]);
+    events.push(key(KeyCode::Up));
+    events.push(key(KeyCode::Enter));
+    events.extend(typed("changed"));
+    events.push(key(KeyCode::Esc));
+    // A rename to a name already in the list is refused.
+    events.push(key(KeyCode::Char('k')));
+    events.push(key(KeyCode::Backspace));
+    events.push(key(KeyCode::Char('a')));
+    events.push(key(KeyCode::Enter));
+    events.push(key(KeyCode::Esc));
+
+    assert_eq!(run_tui(events), pairs(&[("A", "1"), ("B", "2")]));
+}
+
+#[test]
+fn test_delete_and_undo() {
+    let mut events = added(&[("A", "1"), ("B", "2"), ("C", "3")]);
+    events.push(key(KeyCode::Up));
+    events.push(key(KeyCode::Up));
+    events.push(key(KeyCode::Char('d')));
+    events.push(key(KeyCode::Char('u')));
+    events.push(key(KeyCode::Down));
+    events.push(key(KeyCode::Char('d')));
+
+    assert_eq!(run_tui(events), pairs(&[("A", "1"), ("B", "2")]));
+}
+
+#[test]
+fn test_undo_keeps_toggle_and_position() {
+    let mut events = added(&[("A", "1"), ("B", "2")]);
+    events.push(key(KeyCode::Up));
+    events.push(key(KeyCode::Up));
+    events.push(key(KeyCode::Char(' ')));
+    events.push(key(KeyCode::Char('d')));
+    events.push(key(KeyCode::Char('u')));
+
+    assert_eq!(run_tui(events), pairs(&[("B", "2")]));
+}
+
+#[test]
+fn test_reorder_entries() {
+    let mut events = added(&[("A", "1"), ("B", "2"), ("C", "3")]);
+    events.push(key(KeyCode::Up));
+    events.push(shift(KeyCode::Up));
+    events.push(shift(KeyCode::Up));
+    events.push(shift(KeyCode::Down));
+
+    assert_eq!(
+        run_tui(events),
+        pairs(&[("A", "1"), ("C", "3"), ("B", "2")])
+    );
+}
@@ -0,0 +1,98 @@
+use std::sync::Arc;
+
+use anyhow::Result;
+use crossterm::event::{Event, KeyCode};
+use ratatui::{Terminal, backend::TestBackend};
+
+use github_secrets::app::{App, RunOptions};
+use github_secrets::prompt;
+
+mod common;
+use common::{FakeEventSource, FakeGitHub, FakePrompt, NoopRateLimiter, key, repositories, typed};
+
+async fn run(github: &FakeGitHub, prompt: &FakePrompt) -> Result<()> {
+    App::run_with_options(
+        github,
+        prompt,
+        &mut NoopRateLimiter,
+        Arc::new("token".to_string()),
+        repositories(&["a"]),
+        &RunOptions::default(),
+    )
+    .await
+}
+
+/// Prompt entering `keys` with a value that passes lint.
+fn entering(keys: &[&str]) -> FakePrompt {
+    let secrets: Vec<_> = keys.iter().map(|key| (*key, "a-real-value")).collect();
+    FakePrompt::with_secrets(&secrets)
+}
+
+#[tokio::test]
+async fn test_run_rejects_case_insensitive_duplicates() {
+    let github = FakeGitHub::default();
+    let err = run(&github, &entering(&["DB_URL", "TOKEN", "db_url"]))
+        .await
+        .unwrap_err();
+    assert!(err.to_string().contains("'DB_URL' and 'db_url'"), "{}", err);
+    assert!(github.uploads().is_empty());
+}
+
+#[tokio::test]
+async fn test_run_treats_differently_cased_remote_secret_as_existing() -> Result<()> {
+    let github = FakeGitHub::default().with_secrets_everywhere(&[("DB_URL", Some(30))]);
+    let prompt = entering(&["db_url", "NEW_ONE"]);
+    run(&github, &prompt).await?;
+
+    // Only the secret that already exists (under another case) asks to overwrite.
+    assert_eq!(*prompt.asked.lock().unwrap(), vec!["o/a:db_url"]);
+    assert_eq!(github.uploaded_names(), vec!["db_url", "NEW_ONE"]);
+    Ok(())
+}
+
+fn run_tui(events: Vec<Event>) -> Vec<(String, String)> {
+    let mut terminal = Terminal::new(TestBackend::new(120, 30)).unwrap();
+    let mut src = FakeEventSource::new(events);
+    prompt::prompt_secrets_with(&mut terminal, &mut src)
+        .unwrap()
+        .into_iter()
+        .map(|s| (s.key, s.value))
+        .collect()
+}
+
+#[test]
+fn test_tui_tab_applies_suggested_name() {
+    let mut events = typed("db-url");
+    // Rejected: hyphens aren't allowed; Tab normalizes to DB_URL.
+    events.push(key(KeyCode::Enter));
+    events.push(key(KeyCode::Tab));
+    events.push(key(KeyCode::Enter));
+    events.extend(typed("postgres://x"));
+    events.push(key(KeyCode::Enter));
+    events.push(key(KeyCode::Esc));
+    events.push(key(KeyCode::Char('y')));
+
+    assert_eq!(
+        run_tui(events),
+        vec![("DB_URL".to_string(), "postgres://x".to_string())]
+    );
+}
+
+#[test]
+fn test_tui_same_name_in_other_case_replaces_entry() {
+    let mut events = typed("DB_URL");
+    events.push(key(KeyCode::Enter));
+    events.extend(typed("first"));
+    events.push(key(KeyCode::Enter));
+    events.extend(typed("db_url"));
+    events.push(key(KeyCode::Enter));
+    events.extend(typed("second"));
+    events.push(key(KeyCode::Enter));
+    events.push(key(KeyCode::Esc));
+    events.push(key(KeyCode::Char('y')));
+
+    assert_eq!(
+        run_tui(events),
+        vec![("db_url".to_string(), "second".to_string())]
+    );
+}
@@ -0,0 +1,140 @@
+use std::path::PathBuf;
+use std::sync::Arc;
+
+use anyhow::Result;
+use serial_test::serial;
+use tempfile::TempDir;
+
+use github_secrets::app::App;
+use github_secrets::provider::Providers;
+use github_secrets::secret_ref::{SecretSource, resolve_value};
+
+mod common;
+use common::{FakeGitHub, FakePrompt, NoopRateLimiter, repositories};
+
+#[test]
+fn test_parse_sources() {
+    assert_eq!(
+        SecretSource::parse("env:DB_PASSWORD"),
+        SecretSource::Env("DB_PASSWORD".to_string())
+    );
+    assert_eq!(
+        SecretSource::parse("file:/run/secrets/db"),
+        SecretSource::File(PathBuf::from("/run/secrets/db"))
+    );
+    assert_eq!(
+        SecretSource::parse("cmd: pass show db"),
+        SecretSource::Command("pass show db".to_string())
+    );
+    assert_eq!(
+        SecretSource::parse("hunter2"),
+        SecretSource::Literal("hunter2".to_string())
+    );
+    assert_eq!(
+        SecretSource::parse("literal:env:HOME"),
+        SecretSource::Literal("env:HOME".to_string())
+    );
+    assert!(!SecretSource::parse("plain").is_reference());
+    assert!(SecretSource::parse("env:X").is_reference());
+}
+
+#[test]
+fn test_validate_rejects_empty_references() {
+    assert!(SecretSource::parse("env:").validate().is_err());
+    assert!(SecretSource::parse("file: ").validate().is_err());
+    assert!(SecretSource::parse("cmd:").validate().is_err());
+    assert!(SecretSource::parse("anything").validate().is_ok());
+}
+
+#[test]
+fn test_display_hides_literals() {
+    assert_eq!(SecretSource::parse("hunter2").to_string(), "literal value");
+    assert_eq!(SecretSource::parse("env:X").to_string(), "env:X");
+}
+
+#[test]
+#[serial]
+fn test_resolve_env() {
+    unsafe { std::env::set_var("SECRET_REF_TEST_VAR", "from-env") };
+    assert_eq!(
+        resolve_value("env:SECRET_REF_TEST_VAR", &Providers::new()).unwrap(),
+        "from-env"
+    );
+    unsafe { std::env::remove_var("SECRET_REF_TEST_VAR") };
+
+    let err = resolve_value("env:SECRET_REF_TEST_VAR", &Providers::new()).unwrap_err();
+    assert!(format!("{:#}", err).contains("is not set"));
+}
+
+#[test]
+fn test_resolve_file_keeps_contents() {
+    let temp_dir = TempDir::new().unwrap();
+    let path = temp_dir.path().join("secret");
+    std::fs::write(&path, "line1\nline2\n").unwrap();
+
+    let value = resolve_value(&format!("file:{}", path.display()), &Providers::new()).unwrap();
+    assert_eq!(value, "line1\nline2\n");
+
+    assert!(resolve_value("file:/definitely/missing/secret", &Providers::new()).is_err());
+}
+
+#[cfg(unix)]
+#[test]
+fn test_resolve_command() {
+    assert_eq!(
+        resolve_value("cmd:printf 'abc\\n\\n'", &Providers::new()).unwrap(),
+        "abc"
+    );
+
+    let err = resolve_value("cmd:echo oops >&2; exit 3", &Providers::new()).unwrap_err();
+    let message = format!("{:#}", err);
+    assert!(message.contains("oops"), "{}", message);
+}
+
+#[test]
+fn test_resolve_literal() {
+    assert_eq!(
+        resolve_value("plain value", &Providers::new()).unwrap(),
+        "plain value"
+    );
+}
+
+#[tokio::test]
+#[serial]
+async fn test_run_resolves_references_before_upload() -> Result<()> {
+    unsafe { std::env::set_var("SECRET_REF_RUN_VAR", "resolved") };
+
+    let github = FakeGitHub::default();
+    let prompt = FakePrompt::with_secrets(&[
+        ("FROM_ENV", "env:SECRET_REF_RUN_VAR"),
+        ("BROKEN", "env:SECRET_REF_RUN_MISSING"),
+        ("PLAIN", "literal"),
+    ]);
+
+    App::run_with_deps(
+        &github,
+        &prompt,
+        &mut NoopRateLimiter,
+        Arc::new("token".to_string()),
+        repositories(&["a", "b"]),
+    )
+    .await?;
+
+    unsafe { std::env::remove_var("SECRET_REF_RUN_VAR") };
+
+    let uploads: Vec<_> = github
+        .uploads()
+        .into_iter()
+        .map(|(_, name, value)| (name, value))
+        .collect();
+    assert_eq!(
+        uploads,
+        vec![
+            ("FROM_ENV".to_string(), "resolved".to_string()),
+            ("PLAIN".to_string(), "literal".to_string()),
+            ("FROM_ENV".to_string(), "resolved".to_string()),
+            ("PLAIN".to_string(), "literal".to_string()),
+        ]
+    );
+    Ok(())
+}
@@ -0,0 +1,133 @@
+use crossterm::event::{Event, KeyCode};
+use ratatui::{Terminal, backend::TestBackend, widgets::ListState};
+
+use github_secrets::config::Repository;
+use github_secrets::prompt::{RepoFilter, render_selection_ui, select_repositories_with};
+use github_secrets::selector::fuzzy_matches;
+
+mod common;
+use common::{FakeEventSource, key, typed};
+
+fn repos() -> Vec<Repository> {
+    ["backend-api", "frontend-web", "backend-worker", "docs"]
+        .iter()
+        .map(|name| Repository {
+            owner: "org".to_string(),
+            name: name.to_string(),
+            ..Default::default()
+        })
+        .collect()
+}
+
+fn select(events: Vec<Event>) -> anyhow::Result<Vec<usize>> {
+    let mut terminal = Terminal::new(TestBackend::new(100, 20)).unwrap();
+    let mut src = FakeEventSource::ending_with(events, KeyCode::Enter);
+    select_repositories_with(&mut terminal, &mut src, &repos())
+}
+
+#[test]
+fn test_fuzzy_matches() {
+    assert!(fuzzy_matches("bapi", "org/backend-api"));
+    assert!(fuzzy_matches("ORG BE", "org/backend-api"));
+    assert!(fuzzy_matches("", "anything"));
+    assert!(!fuzzy_matches("ipa", "org/backend-api"));
+}
+
+#[test]
+fn test_filter_then_select_all_visible() {
+    let mut events = vec![key(KeyCode::Char('/'))];
+    events.extend(typed("backend"));
+    events.push(key(KeyCode::Enter));
+    events.push(key(KeyCode::Char('a')));
+
+    assert_eq!(select(events).unwrap(), vec![0, 2]);
+}
+
+#[test]
+fn test_selections_survive_filter_changes() {
+    // Select docs through a filter, then filter for the API and select it too.
+    let mut events = vec![key(KeyCode::Char('/'))];
+    events.extend(typed("docs"));
+    events.push(key(KeyCode::Down));
+    events.push(key(KeyCode::Enter));
+    events.push(key(KeyCode::Char(' ')));
+    events.push(key(KeyCode::Char('/')));
+    for _ in 0.."docs".len() {
+        events.push(key(KeyCode::Backspace));
+    }
+    events.extend(typed("api"));
+    events.push(key(KeyCode::Down));
+    events.push(key(KeyCode::Char(' ')));
+    // ESC clears the filter instead of cancelling.
+    events.push(key(KeyCode::Esc));
+
+    assert_eq!(select(events).unwrap(), vec![0, 3]);
+}
+
+#[test]
+fn test_invert_selection() {
+    // Select the first repository, then invert everything.
+    let events = vec![
+        key(KeyCode::Down),
+        key(KeyCode::Char(' ')),
+        key(KeyCode::Char('i')),
+    ];
+    assert_eq!(select(events).unwrap(), vec![1, 2, 3]);
+
+    // Inverting only touches visible repositories.
+    let mut events = vec![key(KeyCode::Char('/'))];
+    events.extend(typed("front"));
+    events.push(key(KeyCode::Enter));
+    events.push(key(KeyCode::Char('i')));
+    assert_eq!(select(events).unwrap(), vec![1]);
+}
+
+#[test]
+fn test_typing_a_filter_does_not_trigger_actions() {
+    // 'a' and 'i' are part of the query while typing.
+    let mut events = vec![key(KeyCode::Char('/'))];
+    events.extend(typed("ai"));
+    events.push(key(KeyCode::Esc));
+    events.push(key(KeyCode::Esc));
+
+    assert!(select(events).is_err());
+}
+
+#[test]
+fn test_render_shows_filter_and_count() {
+    let repositories = repos();
+    let mut selected = vec![false; repositories.len() + 1];
+    selected[4] = true;
+    let filter = RepoFilter {
+        query: "backend".to_string(),
+        editing: false,
+    };
+
+    let mut terminal = Terminal::new(TestBackend::new(100, 20)).unwrap();
+    terminal
+        .draw(|f| {
+            render_selection_ui(
+                f,
+                &repositories,
+                &selected,
+                &mut ListState::default(),
+                &filter,
+            )
+        })
+        .unwrap();
+    let buffer = terminal.backend().buffer();
+    let text: String = (0..buffer.area.height)
+        .map(|y| {
+            (0..buffer.area.width)
+                .map(|x| buffer.get(x, y).symbol())
+                .collect::<String>()
+                + "\n"
+        })
+        .collect();
+
+    assert!(text.contains("1 of 4 selected"), "{}", text);
+    assert!(text.contains("filter: /backend (2 of 4 shown)"), "{}", text);
+    assert!(text.contains("Select All Visible"));
+    assert!(text.contains("org/backend-worker"));
+    assert!(!text.contains("org/docs"));
+}
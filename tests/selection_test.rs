@@ -45,11 +45,13 @@ fn test_select_repositories_select_all() {
             owner: "owner1".to_string(),
             name: "repo1".to_string(),
             alias: None,
+            ..Default::default()
         },
         Repository {
             owner: "owner2".to_string(),
             name: "repo2".to_string(),
             alias: None,
+            ..Default::default()
         },
     ];
 
@@ -74,16 +76,19 @@ fn test_select_repositories_select_subset() {
             owner: "owner1".to_string(),
             name: "repo1".to_string(),
             alias: None,
+            ..Default::default()
         },
         Repository {
             owner: "owner2".to_string(),
             name: "repo2".to_string(),
             alias: None,
+            ..Default::default()
         },
         Repository {
             owner: "owner3".to_string(),
             name: "repo3".to_string(),
             alias: None,
+            ..Default::default()
         },
     ];
 
@@ -113,6 +118,7 @@ fn test_select_repositories_cancel() {
         owner: "owner1".to_string(),
         name: "repo1".to_string(),
         alias: None,
+        ..Default::default()
     }];
 
     let events = vec![
@@ -140,6 +146,7 @@ fn test_select_repositories_no_selection() {
         owner: "owner1".to_string(),
         name: "repo1".to_string(),
         alias: None,
+        ..Default::default()
     }];
 
     let events = vec![
@@ -181,10 +181,8 @@ fn test_comprehensive_validation_scenarios() {
         "UPPER_CASE",
         "lower_case",
         "MixedCase",
-        "key-with-hyphens",
         "key_with_underscores",
         "key123",
-        "123key",
         "KEY_123",
         "a", // Minimum length
     ];
@@ -274,6 +272,8 @@ fn test_render_secret_input_ui() {
                 &input_mode,
                 message,
                 message_color,
+                Default::default(),
+                &Default::default(),
             );
         })
         .unwrap();
@@ -317,11 +317,13 @@ fn test_render_selection_ui() {
             owner: "owner1".to_string(),
             name: "repo1".to_string(),
             alias: None,
+            ..Default::default()
         },
         Repository {
             owner: "owner2".to_string(),
             name: "repo2".to_string(),
             alias: Some("Alias".to_string()),
+            ..Default::default()
         },
     ];
 
@@ -332,7 +334,13 @@ fn test_render_selection_ui() {
 
     terminal
         .draw(|f| {
-            render_selection_ui(f, &repositories, &selected, &mut list_state);
+            render_selection_ui(
+                f,
+                &repositories,
+                &selected,
+                &mut list_state,
+                &Default::default(),
+            );
         })
         .unwrap();
 
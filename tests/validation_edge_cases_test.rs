@@ -72,19 +72,21 @@ fn test_validate_secret_key_underscore_only() {
 #[test]
 fn test_validate_secret_key_hyphen_only() {
     let result = validate_secret_key("-");
-    assert!(result.is_ok());
+    assert!(result.is_err());
 }
 
 #[test]
 fn test_validate_secret_key_mixed_valid_chars() {
-    let result = validate_secret_key("My-Secret_Key123");
+    let result = validate_secret_key("My_Secret_Key123");
     assert!(result.is_ok());
+    assert!(validate_secret_key("My-Secret_Key123").is_err());
 }
 
 #[test]
 fn test_validate_secret_key_starts_with_number() {
     let result = validate_secret_key("123_SECRET");
-    assert!(result.is_ok());
+    assert!(result.is_err());
+    assert!(result.unwrap_err().to_string().contains("number"));
 }
 
 #[test]
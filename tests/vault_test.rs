@@ -0,0 +1,211 @@
+use crossterm::event::{Event, KeyCode};
+use ratatui::{Terminal, backend::TestBackend};
+use tempfile::TempDir;
+
+use github_secrets::app::{App, VaultAction};
+use github_secrets::prompt::{self, SecretPair};
+use github_secrets::vault::{Vault, VaultSession};
+
+mod common;
+use common::{FakeEventSource, ctrl, key, typed};
+
+fn pair(key: &str, value: &str) -> SecretPair {
+    SecretPair {
+        key: key.to_string(),
+        value: value.to_string(),
+    }
+}
+
+fn keys_and_values(secrets: &[SecretPair]) -> Vec<(String, String)> {
+    secrets
+        .iter()
+        .map(|s| (s.key.clone(), s.value.clone()))
+        .collect()
+}
+
+fn run_tui(events: Vec<Event>, session: &mut VaultSession) -> Vec<SecretPair> {
+    let mut terminal = Terminal::new(TestBackend::new(100, 30)).unwrap();
+    let mut src = FakeEventSource::new(events);
+    prompt::prompt_secrets_with_vault(&mut terminal, &mut src, Some(session)).unwrap()
+}
+
+#[test]
+fn test_vault_round_trip_and_wrong_passphrase() {
+    let temp_dir = TempDir::new().unwrap();
+    let path = temp_dir.path().join("data").join("vault.json");
+
+    let mut vault = Vault::open(&path, "correct horse").unwrap();
+    assert!(vault.set_names().is_empty());
+    vault
+        .insert(
+            "datadog",
+            &[pair("DD_API_KEY", "abc"), pair("DD_APP_KEY", "def")],
+        )
+        .unwrap();
+    vault.save(&path, "correct horse").unwrap();
+
+    let raw = std::fs::read_to_string(&path).unwrap();
+    assert!(!raw.contains("DD_API_KEY"));
+    assert!(!raw.contains("datadog"));
+
+    let reopened = Vault::open(&path, "correct horse").unwrap();
+    assert_eq!(reopened.set_names(), vec!["datadog"]);
+    assert_eq!(
+        keys_and_values(&reopened.get("datadog").unwrap()),
+        vec![
+            ("DD_API_KEY".to_string(), "abc".to_string()),
+            ("DD_APP_KEY".to_string(), "def".to_string()),
+        ]
+    );
+
+    let err = Vault::open(&path, "wrong").err().unwrap();
+    assert!(err.to_string().contains("Wrong passphrase"), "{}", err);
+}
+
+#[cfg(unix)]
+#[test]
+fn test_vault_file_is_private() {
+    use std::os::unix::fs::PermissionsExt;
+
+    let temp_dir = TempDir::new().unwrap();
+    let path = temp_dir.path().join("vault.json");
+    Vault::open(&path, "pw").unwrap().save(&path, "pw").unwrap();
+
+    let mode = std::fs::metadata(&path).unwrap().permissions().mode();
+    assert_eq!(mode & 0o777, 0o600);
+}
+
+#[test]
+fn test_vault_rename_and_remove() {
+    let temp_dir = TempDir::new().unwrap();
+    let path = temp_dir.path().join("vault.json");
+    let mut vault = Vault::open(&path, "pw").unwrap();
+    vault.insert("a", &[pair("A", "1")]).unwrap();
+    vault.insert("b", &[pair("B", "2")]).unwrap();
+    vault.save(&path, "pw").unwrap();
+
+    let message = App::vault_edit_at(
+        &path,
+        "pw",
+        VaultAction::Rename {
+            from: "a".to_string(),
+            to: "c".to_string(),
+        },
+    )
+    .unwrap();
+    assert_eq!(message, "Renamed secret set 'a' to 'c'");
+
+    assert!(
+        App::vault_edit_at(
+            &path,
+            "pw",
+            VaultAction::Rename {
+                from: "b".to_string(),
+                to: "c".to_string(),
+            },
+        )
+        .is_err()
+    );
+    assert!(App::vault_edit_at(&path, "pw", VaultAction::Remove("missing".to_string())).is_err());
+
+    App::vault_edit_at(&path, "pw", VaultAction::Remove("b".to_string())).unwrap();
+    assert_eq!(App::vault_list_at(&path, "pw").unwrap(), "c (1): A");
+}
+
+#[test]
+fn test_tui_saves_and_loads_sets() {
+    let temp_dir = TempDir::new().unwrap();
+    let path = temp_dir.path().join("vault.json");
+
+    // Enter a secret, save it as "team" (creating the vault), then finish.
+    let mut events = typed("TOKEN");
+    events.push(key(KeyCode::Enter));
+    events.extend(typed("s3cret"));
+    events.push(key(KeyCode::Enter));
+    events.push(ctrl('s'));
+    events.extend(typed("pw"));
+    events.push(key(KeyCode::Enter));
+    events.extend(typed("pw"));
+    events.push(key(KeyCode::Enter));
+    events.extend(typed("team"));
+    events.push(key(KeyCode::Enter));
+    events.push(key(KeyCode::Esc));
+    events.push(key(KeyCode::Char('y')));
+
+    let mut session = VaultSession::new(path.clone());
+    let secrets = run_tui(events, &mut session);
+    assert_eq!(secrets.len(), 1);
+    assert_eq!(
+        Vault::open(&path, "pw").unwrap().set_names(),
+        vec!["team".to_string()]
+    );
+
+    // A new session: unlock, pick the only set, and get its secrets back.
+    let mut events = vec![ctrl('l')];
+    events.extend(typed("pw"));
+    events.push(key(KeyCode::Enter));
+    events.push(key(KeyCode::Enter));
+    events.push(key(KeyCode::Esc));
+    events.push(key(KeyCode::Char('y')));
+
+    let mut session = VaultSession::new(path);
+    let secrets = run_tui(events, &mut session);
+    assert_eq!(
+        keys_and_values(&secrets),
+        vec![("TOKEN".to_string(), "s3cret".to_string())]
+    );
+}
+
+#[test]
+fn test_tui_wrong_passphrase_loads_nothing() {
+    let temp_dir = TempDir::new().unwrap();
+    let path = temp_dir.path().join("vault.json");
+    let mut vault = Vault::open(&path, "pw").unwrap();
+    vault.insert("team", &[pair("TOKEN", "x")]).unwrap();
+    vault.save(&path, "pw").unwrap();
+
+    let mut events = vec![ctrl('l')];
+    events.extend(typed("nope"));
+    events.push(key(KeyCode::Enter));
+    events.push(key(KeyCode::Enter));
+
+    let mut session = VaultSession::new(path);
+    let secrets = run_tui(events, &mut session);
+    assert!(secrets.is_empty());
+    assert!(!session.is_unlocked());
+}
+
+#[test]
+fn test_tui_new_vault_passphrase_must_be_confirmed() {
+    let temp_dir = TempDir::new().unwrap();
+    let path = temp_dir.path().join("vault.json");
+
+    // An empty passphrase is refused, then a mismatched confirmation starts
+    // over, and only a matching pair creates the vault.
+    let mut events = typed("TOKEN");
+    events.push(key(KeyCode::Enter));
+    events.extend(typed("s3cret"));
+    events.push(key(KeyCode::Enter));
+    events.push(ctrl('s'));
+    events.push(key(KeyCode::Enter));
+    events.extend(typed("pw"));
+    events.push(key(KeyCode::Enter));
+    events.extend(typed("typo"));
+    events.push(key(KeyCode::Enter));
+    events.extend(typed("good"));
+    events.push(key(KeyCode::Enter));
+    events.extend(typed("good"));
+    events.push(key(KeyCode::Enter));
+    events.extend(typed("team"));
+    events.push(key(KeyCode::Enter));
+    events.push(key(KeyCode::Esc));
+    events.push(key(KeyCode::Char('y')));
+
+    let mut session = VaultSession::new(path.clone());
+    run_tui(events, &mut session);
+    assert!(Vault::open(&path, "pw").is_err());
+    assert_eq!(
+        Vault::open(&path, "good").unwrap().set_names(),
+        vec!["team".to_string()]
+    );
+}